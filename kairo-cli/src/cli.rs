@@ -10,7 +10,35 @@ pub fn dispatch(cli: Cli, conn: &mut SqliteConnection, config: &AppConfig) {
         Commands::Note { command } => handler::handle_note_command(command, conn, config),
         Commands::Project { command } => handler::handle_project_command(command, conn, config),
         Commands::Task { command } => handler::handle_task_command(command, conn, config),
-        Commands::Tag { command } => handler::handle_tag_command(command, conn),
-        Commands::Link { command } => handler::handle_link_command(command, conn),
+        Commands::Area { command } => handler::handle_area_command(command, conn),
+        Commands::Tag { command } => handler::handle_tag_command(command, conn, config),
+        Commands::Link { command } => handler::handle_link_command(command, conn, config),
+        Commands::Report { command } => handler::handle_report_command(command, conn),
+        Commands::Export { command } => handler::handle_export_command(command, conn, config),
+        Commands::Import { command } => handler::handle_import_command(command, conn, config),
+        Commands::Query {
+            arg_query,
+            arg_format,
+        } => handler::handle_query_command(arg_query, arg_format, conn),
+        Commands::View { command } => handler::handle_view_command(command, conn),
+        Commands::Inbox => handler::handle_inbox_command(conn, config),
+        Commands::Daily {
+            arg_date,
+            arg_template,
+        } => handler::handle_daily_command(arg_date, arg_template, conn, config),
+        Commands::Capture {
+            arg_text,
+            arg_title,
+            arg_sub_type,
+            arg_tags,
+        } => handler::handle_capture_command(
+            arg_text,
+            arg_title,
+            arg_sub_type,
+            arg_tags,
+            conn,
+            config,
+        ),
+        Commands::Trash { command } => handler::handle_trash_command(command, conn, config),
     }
 }
@@ -0,0 +1,78 @@
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+#[command(about = "Manage areas (ongoing responsibilities that group projects)")]
+pub enum AreaCommands {
+    #[command(
+        about = "Create a new area.",
+        long_about = "Creates a new area with a title and optional description. Areas group projects by ongoing responsibility (e.g. Health, Finance)."
+    )]
+    Create {
+        #[arg(short = 't', long = "title")]
+        arg_title: String,
+        #[arg(short = 'd', long = "description")]
+        arg_description: Option<String>,
+    },
+    #[command(
+        about = "List areas.",
+        long_about = "Displays areas with rolled-up counts of projects, open and completed tasks, and notes (including sub-projects)."
+    )]
+    List {
+        #[arg(long = "archived")]
+        arg_archived: Option<bool>,
+        #[arg(long = "deleted")]
+        arg_deleted: Option<bool>,
+    },
+    #[command(
+        about = "Get an area by ID.",
+        long_about = "Retrieves and displays an area with the specified ID, followed by the tree of its projects."
+    )]
+    Get {
+        #[arg(long = "id")]
+        arg_id: String,
+    },
+    #[command(
+        about = "Update an existing area.",
+        long_about = "Updates the title and/or description of the specified area."
+    )]
+    Update {
+        #[arg(long = "id")]
+        arg_id: String,
+        #[arg(short = 't', long = "title")]
+        arg_title: Option<String>,
+        #[arg(short = 'd', long = "description")]
+        arg_description: Option<String>,
+    },
+    #[command(
+        about = "Archive an area.",
+        long_about = "Marks an area as archived. Archived areas are not shown by default."
+    )]
+    Archive {
+        #[arg(long = "id")]
+        arg_id: String,
+    },
+    #[command(
+        about = "Soft-delete an area.",
+        long_about = "Marks an area as deleted without permanently removing it from the database."
+    )]
+    Delete {
+        #[arg(long = "id")]
+        arg_id: String,
+    },
+    #[command(
+        about = "Unarchive an area.",
+        long_about = "Reverts the archived status of an area, making it active again."
+    )]
+    Unarchive {
+        #[arg(long = "id")]
+        arg_id: String,
+    },
+    #[command(
+        about = "Restore a deleted area.",
+        long_about = "Restores a previously soft-deleted area."
+    )]
+    Restore {
+        #[arg(long = "id")]
+        arg_id: String,
+    },
+}
@@ -0,0 +1,32 @@
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+#[command(about = "Export tasks to other tools and formats")]
+pub enum ExportCommands {
+    #[command(
+        about = "Export tasks as iCalendar.",
+        long_about = "Writes tasks to an RFC 5545 .ics file. Each task becomes a VTODO (or an all-day VEVENT for tasks with a due date when --component event is given). Prints to stdout when no output file is specified."
+    )]
+    Ical {
+        #[arg(short = 'o', long = "output")]
+        arg_output: Option<String>,
+        #[arg(long = "component")]
+        arg_component: Option<String>,
+    },
+    #[command(
+        about = "Export tasks as todo.txt.",
+        long_about = "Writes tasks in todo.txt format. Priorities become (A)/(B)/(C), projects +project, tags @context, due dates due:YYYY-MM-DD and kairo IDs id:. Completed (archived) tasks are marked with x. Prints to stdout when no output file is specified."
+    )]
+    Todotxt {
+        #[arg(short = 'o', long = "output")]
+        arg_output: Option<String>,
+    },
+    #[command(
+        about = "Export tasks as Taskwarrior JSON.",
+        long_about = "Writes tasks as a JSON array readable by `task import`. The kairo task ID is kept in the kairo_id attribute so that tasks can be moved back. Prints to stdout when no output file is specified."
+    )]
+    Taskwarrior {
+        #[arg(short = 'o', long = "output")]
+        arg_output: Option<String>,
+    },
+}
@@ -0,0 +1,30 @@
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+#[command(about = "Import tasks from other tools and formats")]
+pub enum ImportCommands {
+    #[command(
+        about = "Import tasks from iCalendar.",
+        long_about = "Reads VTODO and VEVENT entries from an .ics file. Entries exported by kairo are matched to existing tasks by UID and updated, others are created as new tasks."
+    )]
+    Ical {
+        #[arg(value_name = "FILE")]
+        arg_file: String,
+    },
+    #[command(
+        about = "Import tasks from todo.txt.",
+        long_about = "Reads tasks from a todo.txt file. Lines with an id: key are matched to existing tasks and updated, others are created as new tasks. Unknown +projects are created."
+    )]
+    Todotxt {
+        #[arg(value_name = "FILE")]
+        arg_file: String,
+    },
+    #[command(
+        about = "Import tasks from Taskwarrior.",
+        long_about = "Reads the JSON produced by `task export`. Description, project, tags, priority (H/M/L), due date and status are mapped to tasks, and annotations are written into the task Markdown body. Tasks with a kairo_id are updated in place."
+    )]
+    Taskwarrior {
+        #[arg(value_name = "FILE")]
+        arg_file: String,
+    },
+}
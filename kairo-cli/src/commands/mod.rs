@@ -1,15 +1,27 @@
+pub mod area;
+pub mod export;
+pub mod import;
 pub mod linked_note;
 pub mod note;
 pub mod project;
+pub mod report;
 pub mod tag;
 pub mod task;
+pub mod trash;
+pub mod view;
 
+use area::AreaCommands;
 use clap::{Parser, Subcommand};
+use export::ExportCommands;
+use import::ImportCommands;
 use linked_note::LinkCommands;
 use note::NoteCommands;
 use project::ProjectCommands;
+use report::ReportCommands;
 use tag::TagCommands;
 use task::TaskCommands;
+use trash::TrashCommands;
+use view::ViewCommands;
 
 #[derive(Debug, Parser)]
 pub struct Cli {
@@ -31,6 +43,10 @@ pub enum Commands {
         #[command(subcommand)]
         command: TaskCommands,
     },
+    Area {
+        #[command(subcommand)]
+        command: AreaCommands,
+    },
     Tag {
         #[command(subcommand)]
         command: TagCommands,
@@ -39,4 +55,69 @@ pub enum Commands {
         #[command(subcommand)]
         command: LinkCommands,
     },
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+    Export {
+        #[command(subcommand)]
+        command: ExportCommands,
+    },
+    Import {
+        #[command(subcommand)]
+        command: ImportCommands,
+    },
+    #[command(
+        about = "Search notes, tasks and projects with a query.",
+        long_about = "Searches notes, tasks and projects with space-separated conditions, all of which must match. Conditions are field:value pairs (is, type, sub, tag, project, task, area, priority, status, title, archived, deleted) or date comparisons (created, updated, due with :, <, <=, >, >=; dates are YYYY-MM-DD, today, yesterday or tomorrow). Prefix a condition with - to negate it; words without a field match the title. Example: kairo query 'type:permanent tag:rust -tag:draft created>=2025-01-01'"
+    )]
+    Query {
+        #[arg(
+            allow_hyphen_values = true,
+            help = "Query such as 'tag:rust -tag:draft due<today'"
+        )]
+        arg_query: String,
+        #[arg(long = "format", help = "table, json or csv")]
+        arg_format: Option<String>,
+    },
+    View {
+        #[command(subcommand)]
+        command: ViewCommands,
+    },
+    #[command(
+        about = "Process fleeting notes one by one.",
+        long_about = "Walks through active (not archived or deleted) fleeting notes from oldest to newest and asks what to do with each: promote it to permanent, split it into a new permanent note, archive, delete, add tags, link it to another note, skip or quit."
+    )]
+    Inbox,
+    #[command(
+        about = "Open or create the daily note.",
+        long_about = "Opens the daily note for the date (today by default), creating it when it does not exist yet. A new daily note is a permanent Log note with the ID daily-YYYY-MM-DD, rendered from the daily.md template when present and linked to the previous daily note. Tasks completed or due that day and notes created that day are listed in a generated section that is refreshed every time."
+    )]
+    Daily {
+        #[arg(long = "date", help = "YYYY-MM-DD, today, yesterday or tomorrow")]
+        arg_date: Option<String>,
+        #[arg(
+            long = "template",
+            help = "Template name in the templates directory (without .md)"
+        )]
+        arg_template: Option<String>,
+    },
+    #[command(
+        about = "Quickly capture a fleeting note.",
+        long_about = "Creates a fleeting note from the text given as arguments, or from stdin when no text (or -) is given. Without --title the first line becomes the title and the rest the Markdown body. Example: echo 'idea\nmore detail' | kairo capture --tag inbox"
+    )]
+    Capture {
+        #[arg(help = "Text to capture (reads stdin when omitted or -)")]
+        arg_text: Vec<String>,
+        #[arg(short = 't', long = "title")]
+        arg_title: Option<String>,
+        #[arg(short = 's', long = "sub-type")]
+        arg_sub_type: Option<String>,
+        #[arg(long = "tag")]
+        arg_tags: Option<Vec<String>>,
+    },
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommands,
+    },
 }
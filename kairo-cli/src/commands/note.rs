@@ -1,15 +1,15 @@
-use clap::Subcommand;
+use clap::{Args, Subcommand};
 
 #[derive(Debug, Subcommand)]
 #[command(about = "Create and manage Zettelkasten notes")]
 pub enum NoteCommands {
     #[command(
         about = "Create a new Zettelkasten note.",
-        long_about = "Creates a new Zettelkasten note with optional metadata such as type (fleeting or permanent), subtype (log, idea, etc.), tags, and associations with projects or tasks."
+        long_about = "Creates a new Zettelkasten note with optional metadata such as type (fleeting or permanent), subtype (log, idea, etc.), tags, and associations with projects or tasks. --body or --body-file (- for stdin) sets the Markdown body; without --title the first line of the body becomes the title. The body is rendered from a template in the templates directory (default: templates/ next to config.toml): --template <name> uses <name>.md, otherwise note/<type>/<sub-type>.md, note/<type>.md or note.md is used when present."
     )]
     Create {
         #[arg(short = 't', long = "title")]
-        arg_title: String,
+        arg_title: Option<String>,
         #[arg(short = 'n', long = "note-type")]
         arg_note_type: String,
         #[arg(short = 's', long = "sub-type")]
@@ -20,20 +20,54 @@ pub enum NoteCommands {
         arg_task_id: Option<String>,
         #[arg(long = "tag")]
         arg_tags: Option<Vec<String>>,
+        #[arg(
+            long = "template",
+            help = "Template name in the templates directory (without .md)"
+        )]
+        arg_template: Option<String>,
+        #[arg(
+            long = "body",
+            conflicts_with = "arg_body_file",
+            help = "Markdown body; the title defaults to its first line"
+        )]
+        arg_body: Option<String>,
+        #[arg(long = "body-file", help = "Read the body from a file (- for stdin)")]
+        arg_body_file: Option<String>,
     },
     #[command(
         about = "List Zettelkasten notes.",
-        long_about = "Displays a list of notes. You can filter by archived or deleted status, associated tags, and sort by creation date."
+        long_about = "Displays a list of notes. You can filter by archived or deleted status, associated tags, area (notes of the area's projects and sub-projects), note type and subtype, related project or task, created/updated date ranges and title text, sort by creation date, update date or title, and page through results with --limit and --offset. Tag filters can be combined: --tag-all (all of), --tag-any or --tag (any of) and --tag-not (none of); a parent tag also matches its child tags."
     )]
     List {
         #[arg(long = "archived")]
         arg_archived: Option<bool>,
         #[arg(long = "deleted")]
         arg_deleted: Option<bool>,
-        #[arg(long = "tag")]
+        #[arg(
+            long = "tag-any",
+            visible_alias = "tag",
+            value_delimiter = ',',
+            help = "Show notes with any of these tags"
+        )]
         arg_tags: Option<Vec<String>>,
+        #[arg(
+            long = "tag-all",
+            value_delimiter = ',',
+            help = "Show notes with all of these tags"
+        )]
+        arg_tag_all: Option<Vec<String>>,
+        #[arg(
+            long = "tag-not",
+            value_delimiter = ',',
+            help = "Hide notes with any of these tags"
+        )]
+        arg_tag_not: Option<Vec<String>>,
         #[arg(long = "order")]
         arg_order: Option<String>,
+        #[arg(long = "area")]
+        arg_area_id: Option<String>,
+        #[command(flatten)]
+        arg_filter: Box<NoteListFilterArgs>,
     },
     #[command(
         about = "Show a specific note by ID.",
@@ -43,6 +77,18 @@ pub enum NoteCommands {
         #[arg(long = "id")]
         arg_id: String,
     },
+    #[command(
+        about = "Show a note with its Markdown body.",
+        long_about = "Prints the front matter and the Markdown body of the note, rendered for the terminal: headings, lists, checkboxes, quotes and code blocks are formatted, and [[ID]] or [[title]] wikilinks are shown with the linked note, task or project title. Long output is shown in $PAGER (less -R by default). Colors are disabled when the output is not a terminal or NO_COLOR is set."
+    )]
+    Show {
+        #[arg(long = "id")]
+        arg_id: String,
+        #[arg(long = "raw", help = "Print the Markdown file as is")]
+        arg_raw: bool,
+        #[arg(long = "no-pager")]
+        arg_no_pager: bool,
+    },
     #[command(
         about = "Update an existing note.",
         long_about = "Updates the specified note. You can modify the title, type, subtype, tags, and associated project or task."
@@ -103,4 +149,60 @@ pub enum NoteCommands {
         #[arg(long = "id")]
         arg_id: String,
     },
+    #[command(
+        about = "Promote a fleeting note to permanent.",
+        long_about = "Converts a fleeting note into a permanent note. With --split, the fleeting note is left as is and a new permanent note is created from it (same sub type, tags and associations, body copied as a starting point) with a structure or reference link back to the source."
+    )]
+    Promote {
+        #[arg(long = "id")]
+        arg_id: String,
+        #[arg(long = "split")]
+        arg_split: bool,
+        #[arg(
+            short = 't',
+            long = "title",
+            requires = "arg_split",
+            help = "Title of the new note (defaults to the source title)"
+        )]
+        arg_title: Option<String>,
+        #[arg(
+            long = "link-type",
+            requires = "arg_split",
+            help = "structure or reference (default: reference)"
+        )]
+        arg_link_type: Option<String>,
+    },
+}
+
+// note list の追加の絞り込み条件（List が大きくなりすぎないよう Box で持つ）
+#[derive(Debug, Args)]
+pub struct NoteListFilterArgs {
+    #[arg(short = 'n', long = "note-type", help = "fleeting or permanent")]
+    pub arg_note_type: Option<String>,
+    #[arg(
+        short = 's',
+        long = "sub-type",
+        help = "Sub type, or _ for notes without one"
+    )]
+    pub arg_sub_type: Option<String>,
+    #[arg(long = "pid")]
+    pub arg_project_id: Option<String>,
+    #[arg(long = "tid")]
+    pub arg_task_id: Option<String>,
+    #[arg(long = "created-from", help = "YYYY-MM-DD")]
+    pub arg_created_from: Option<String>,
+    #[arg(long = "created-to", help = "YYYY-MM-DD (inclusive)")]
+    pub arg_created_to: Option<String>,
+    #[arg(long = "updated-from", help = "YYYY-MM-DD")]
+    pub arg_updated_from: Option<String>,
+    #[arg(long = "updated-to", help = "YYYY-MM-DD (inclusive)")]
+    pub arg_updated_to: Option<String>,
+    #[arg(long = "title", help = "Show notes whose title contains this text")]
+    pub arg_title: Option<String>,
+    #[arg(long = "sort", help = "created, updated or title")]
+    pub arg_sort: Option<String>,
+    #[arg(long = "limit")]
+    pub arg_limit: Option<i64>,
+    #[arg(long = "offset")]
+    pub arg_offset: Option<i64>,
 }
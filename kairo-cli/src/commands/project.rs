@@ -5,29 +5,66 @@ use clap::Subcommand;
 pub enum ProjectCommands {
     #[command(
         about = "Create a new project.",
-        long_about = "Creates a new project with a title and optional description. Projects can be linked to notes and tasks."
+        long_about = "Creates a new project with a title and optional description, status (planned/active/on_hold/completed), start date and target date. A project can be nested under a parent project (--parent) and grouped into an area (--area); sub-projects inherit the parent's area by default. Projects can be linked to notes and tasks. --body or --body-file (- for stdin) sets the Markdown body; without --title the first line of the body becomes the title."
     )]
     Create {
         #[arg(short = 't', long = "title")]
-        arg_title: String,
+        arg_title: Option<String>,
         #[arg(short = 'd', long = "description")]
         arg_description: Option<String>,
         #[arg(long = "tag")]
         arg_tags: Option<Vec<String>>,
+        #[arg(long = "status")]
+        arg_status: Option<String>,
+        #[arg(long = "start")]
+        arg_start_date: Option<String>,
+        #[arg(long = "target")]
+        arg_target_date: Option<String>,
+        #[arg(long = "parent")]
+        arg_parent_id: Option<String>,
+        #[arg(long = "area")]
+        arg_area_id: Option<String>,
+        #[arg(
+            long = "body",
+            conflicts_with = "arg_body_file",
+            help = "Markdown body; the title defaults to its first line"
+        )]
+        arg_body: Option<String>,
+        #[arg(long = "body-file", help = "Read the body from a file (- for stdin)")]
+        arg_body_file: Option<String>,
     },
     #[command(
         about = "List all projects.",
-        long_about = "Displays all projects with optional filters for archived and deleted status."
+        long_about = "Displays all projects with optional filters for archived and deleted status, tags and area (including sub-projects). Tag filters can be combined: --tag-all (all of), --tag-any or --tag (any of) and --tag-not (none of); a parent tag also matches its child tags."
     )]
     List {
         #[arg(long = "archived")]
         arg_archived: Option<bool>,
         #[arg(long = "deleted")]
         arg_deleted: Option<bool>,
-        #[arg(long = "tag")]
+        #[arg(
+            long = "tag-any",
+            visible_alias = "tag",
+            value_delimiter = ',',
+            help = "Show projects with any of these tags"
+        )]
         arg_tags: Option<Vec<String>>,
+        #[arg(
+            long = "tag-all",
+            value_delimiter = ',',
+            help = "Show projects with all of these tags"
+        )]
+        arg_tag_all: Option<Vec<String>>,
+        #[arg(
+            long = "tag-not",
+            value_delimiter = ',',
+            help = "Hide projects with any of these tags"
+        )]
+        arg_tag_not: Option<Vec<String>>,
         #[arg(long = "order")]
         arg_order: Option<String>,
+        #[arg(long = "area")]
+        arg_area_id: Option<String>,
     },
     #[command(
         about = "Get a project by ID.",
@@ -37,9 +74,30 @@ pub enum ProjectCommands {
         #[arg(long = "id")]
         arg_id: String,
     },
+    #[command(
+        about = "Show an overview of a project.",
+        long_about = "Shows the project with its tags and sub-projects, its tasks grouped by status and priority, its notes grouped by type and subtype, and the links among those notes. With --content, prints the front matter and the Markdown body rendered for the terminal instead (wikilinks resolved to titles, long output shown in $PAGER)."
+    )]
+    Show {
+        #[arg(long = "id")]
+        arg_id: String,
+        #[arg(
+            long = "content",
+            help = "Show the Markdown file instead of the overview"
+        )]
+        arg_content: bool,
+        #[arg(
+            long = "raw",
+            requires = "arg_content",
+            help = "Print the Markdown file as is"
+        )]
+        arg_raw: bool,
+        #[arg(long = "no-pager", requires = "arg_content")]
+        arg_no_pager: bool,
+    },
     #[command(
         about = "Update an existing project.",
-        long_about = "Updates the title and/or description of the specified project."
+        long_about = "Updates the title, description, tags, status, start date, target date, parent project and/or area of the specified project. Pass `none` to --parent or --area to detach it."
     )]
     Update {
         #[arg(long = "id")]
@@ -50,22 +108,36 @@ pub enum ProjectCommands {
         arg_description: Option<String>,
         #[arg(long = "tag")]
         arg_tags: Option<Vec<String>>,
+        #[arg(long = "status")]
+        arg_status: Option<String>,
+        #[arg(long = "start")]
+        arg_start_date: Option<String>,
+        #[arg(long = "target")]
+        arg_target_date: Option<String>,
+        #[arg(long = "parent")]
+        arg_parent_id: Option<String>,
+        #[arg(long = "area")]
+        arg_area_id: Option<String>,
     },
     #[command(
         about = "Archive a project.",
-        long_about = "Marks a project as archived. Archived projects are not shown by default."
+        long_about = "Marks a project as archived. Archived projects are not shown by default. With --cascade, its sub-projects, tasks and notes are archived in the same transaction and recorded so that `unarchive --cascade` reverts exactly those."
     )]
     Archive {
         #[arg(long = "id")]
         arg_id: String,
+        #[arg(long = "cascade")]
+        arg_cascade: bool,
     },
     #[command(
         about = "Soft-delete a project.",
-        long_about = "Marks a project as deleted without permanently removing it from the database."
+        long_about = "Marks a project as deleted without permanently removing it from the database. With --cascade, its sub-projects, tasks and notes are soft-deleted in the same transaction and recorded so that `restore --cascade` reverts exactly those."
     )]
     Delete {
         #[arg(long = "id")]
         arg_id: String,
+        #[arg(long = "cascade")]
+        arg_cascade: bool,
     },
     #[command(
         about = "Permanently delete a project.",
@@ -77,18 +149,35 @@ pub enum ProjectCommands {
     },
     #[command(
         about = "Unarchive a project.",
-        long_about = "Reverts the archived status of a project, making it active again."
+        long_about = "Reverts the archived status of a project, making it active again. With --cascade, the children archived by `archive --cascade` are unarchived as well."
     )]
     Unarchive {
         #[arg(long = "id")]
         arg_id: String,
+        #[arg(long = "cascade")]
+        arg_cascade: bool,
     },
     #[command(
         about = "Restore a deleted project.",
-        long_about = "Restores a previously soft-deleted project."
+        long_about = "Restores a previously soft-deleted project. With --cascade, the children deleted by `delete --cascade` are restored as well."
     )]
     Restore {
         #[arg(long = "id")]
         arg_id: String,
+        #[arg(long = "cascade")]
+        arg_cascade: bool,
     },
+    #[command(
+        about = "Show a progress report for a project.",
+        long_about = "Shows task counts by status and priority, overdue tasks, the number of linked notes and the percent complete. The report is also written into the project's Markdown file as a generated summary section."
+    )]
+    Report {
+        #[arg(long = "id")]
+        arg_id: String,
+    },
+    #[command(
+        about = "Show projects as a tree.",
+        long_about = "Shows active projects grouped by area, with sub-projects nested under their parents. Task and note counts include sub-projects."
+    )]
+    Tree,
 }
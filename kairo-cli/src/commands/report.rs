@@ -0,0 +1,20 @@
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+#[command(about = "Generate reports from tracked data")]
+pub enum ReportCommands {
+    #[command(
+        about = "Report tracked time.",
+        long_about = "Aggregates tracked hours per task, project and tag. You can filter by date range (YYYY-MM-DD) and project ID, and output as a table or CSV."
+    )]
+    Time {
+        #[arg(long = "from")]
+        arg_from: Option<String>,
+        #[arg(long = "to")]
+        arg_to: Option<String>,
+        #[arg(long = "pid")]
+        arg_project_id: Option<String>,
+        #[arg(long = "format")]
+        arg_format: Option<String>,
+    },
+}
@@ -11,18 +11,95 @@ pub enum TagCommands {
         #[arg(long = "deleted")]
         arg_deleted: Option<bool>,
     },
+    #[command(
+        about = "Show a tag.",
+        long_about = "Shows the tag and the IDs of the notes, tasks and projects it is attached to, including when the tag is deleted."
+    )]
     Get {
         #[arg(long = "id")]
         arg_id: String,
     },
+    #[command(
+        about = "Rename a tag.",
+        long_about = "Renames the tag and all of its child tags, then rewrites the tags in the front matter of every affected note, task and project."
+    )]
     Update {
         #[arg(long = "id")]
         arg_id: String,
         #[arg(short = 'n', long = "name")]
         arg_tag_name: String,
     },
+    #[command(
+        about = "Soft-delete a tag.",
+        long_about = "Marks the tag as deleted. It is hidden from tag lists, filters and Markdown front matter, but stays attached so it can be restored."
+    )]
     Delete {
         #[arg(long = "id")]
         arg_id: String,
     },
+    #[command(
+        about = "Restore a soft-deleted tag.",
+        long_about = "Recovers a soft-deleted tag and shows it again on the notes, tasks and projects it is attached to."
+    )]
+    Restore {
+        #[arg(long = "id")]
+        arg_id: String,
+    },
+    #[command(
+        about = "Permanently delete a tag.",
+        long_about = "Removes the tag from the database together with its links to notes, tasks and projects and its aliases. This action cannot be undone."
+    )]
+    Purge {
+        #[arg(long = "id")]
+        arg_id: String,
+    },
+    #[command(
+        about = "Show tags as a tree.",
+        long_about = "Shows slash-separated tags (e.g. lang/rust/async) as a hierarchy with the number of notes, tasks and projects using each tag."
+    )]
+    Tree,
+    #[command(
+        about = "Show tag usage statistics.",
+        long_about = "Lists each tag with the number of notes, tasks and projects using it, the first and last time it was used, and the tags it most often appears with. Sort by name, total, notes, tasks, projects, first or last, and output as a table or JSON."
+    )]
+    Stats {
+        #[arg(
+            long = "sort",
+            help = "name, total, notes, tasks, projects, first or last"
+        )]
+        arg_sort: Option<String>,
+        #[arg(long = "order", help = "asc or desc")]
+        arg_order: Option<String>,
+        #[arg(long = "unused", help = "Show only tags that are not used")]
+        arg_unused: bool,
+        #[arg(long = "format", help = "table or json")]
+        arg_format: Option<String>,
+    },
+    #[command(
+        about = "Merge one tag into another.",
+        long_about = "Moves every note, task and project from one tag to another, removing duplicates. The merged tag's name is kept as an alias of the target tag, and affected Markdown files are rewritten."
+    )]
+    Merge {
+        #[arg(long = "from", help = "Tag ID or name to merge away")]
+        arg_from: String,
+        #[arg(long = "into", help = "Tag ID or name to keep")]
+        arg_into: String,
+    },
+    #[command(
+        about = "Add an alias for a tag.",
+        long_about = "Adds an alternative name that resolves to the tag wherever a tag name is accepted (e.g. --tags and --tag)."
+    )]
+    Alias {
+        #[arg(short = 'n', long = "name")]
+        arg_alias: String,
+        #[arg(long = "tag", help = "Tag ID or name the alias points to")]
+        arg_tag: String,
+    },
+    #[command(about = "Remove a tag alias.")]
+    Unalias {
+        #[arg(short = 'n', long = "name")]
+        arg_alias: String,
+    },
+    #[command(about = "List tag aliases.")]
+    Aliases,
 }
@@ -5,11 +5,11 @@ use clap::Subcommand;
 pub enum TaskCommands {
     #[command(
         about = "Create a new task.",
-        long_about = "Creates a new task with title, description, priority (low/medium/high), due date, and optional project association."
+        long_about = "Creates a new task with title, description, priority (low/medium/high), due date, and optional project association. --body or --body-file (- for stdin) sets the Markdown body; without --title the first line of the body becomes the title."
     )]
     Create {
         #[arg(short = 't', long = "title")]
-        arg_title: String,
+        arg_title: Option<String>,
         #[arg(short = 'd', long = "description")]
         arg_description: Option<String>,
         #[arg(short = 'p', long = "priority")]
@@ -20,24 +20,51 @@ pub enum TaskCommands {
         arg_project_id: Option<String>,
         #[arg(long = "tag")]
         arg_tags: Option<Vec<String>>,
+        #[arg(
+            long = "body",
+            conflicts_with = "arg_body_file",
+            help = "Markdown body; the title defaults to its first line"
+        )]
+        arg_body: Option<String>,
+        #[arg(long = "body-file", help = "Read the body from a file (- for stdin)")]
+        arg_body_file: Option<String>,
     },
     #[command(
         about = "List all tasks.",
-        long_about = "Displays all tasks with optional filters such as archived/deleted status, tags, priority, and project ID."
+        long_about = "Displays all tasks with optional filters such as archived/deleted status, tags, priority, project ID, and area (tasks of the area's projects and sub-projects). Tag filters can be combined: --tag-all (all of), --tag-any or --tag (any of) and --tag-not (none of); a parent tag also matches its child tags."
     )]
     List {
         #[arg(long = "archived")]
         arg_archived: Option<bool>,
         #[arg(long = "deleted")]
         arg_deleted: Option<bool>,
-        #[arg(long = "tag")]
+        #[arg(
+            long = "tag-any",
+            visible_alias = "tag",
+            value_delimiter = ',',
+            help = "Show tasks with any of these tags"
+        )]
         arg_tags: Option<Vec<String>>,
+        #[arg(
+            long = "tag-all",
+            value_delimiter = ',',
+            help = "Show tasks with all of these tags"
+        )]
+        arg_tag_all: Option<Vec<String>>,
+        #[arg(
+            long = "tag-not",
+            value_delimiter = ',',
+            help = "Hide tasks with any of these tags"
+        )]
+        arg_tag_not: Option<Vec<String>>,
         #[arg(long = "order")]
         arg_order: Option<String>,
         #[arg(long = "priority")]
         arg_priority: Option<String>,
         #[arg(long = "pid")]
         arg_project_id: Option<String>,
+        #[arg(long = "area")]
+        arg_area_id: Option<String>,
     },
     #[command(
         about = "Get a task by ID.",
@@ -47,6 +74,18 @@ pub enum TaskCommands {
         #[arg(long = "id")]
         arg_id: String,
     },
+    #[command(
+        about = "Show a task with its Markdown body.",
+        long_about = "Prints the front matter and the Markdown body of the task, rendered for the terminal: headings, lists, checkboxes, quotes and code blocks are formatted, and [[ID]] or [[title]] wikilinks are shown with the linked note, task or project title. Long output is shown in $PAGER (less -R by default). Colors are disabled when the output is not a terminal or NO_COLOR is set."
+    )]
+    Show {
+        #[arg(long = "id")]
+        arg_id: String,
+        #[arg(long = "raw", help = "Print the Markdown file as is")]
+        arg_raw: bool,
+        #[arg(long = "no-pager")]
+        arg_no_pager: bool,
+    },
     #[command(
         about = "Update an existing task.",
         long_about = "Updates the title, description, priority, due date, or associated project of a task."
@@ -107,4 +146,31 @@ pub enum TaskCommands {
         #[arg(long = "id")]
         arg_id: String,
     },
+    #[command(
+        about = "Start a timer on a task.",
+        long_about = "Starts tracking time on the specified task. Only one timer can run at a time."
+    )]
+    StartTimer {
+        #[arg(long = "id")]
+        arg_id: String,
+        #[arg(long = "note")]
+        arg_note: Option<String>,
+    },
+    #[command(
+        about = "Stop the running timer.",
+        long_about = "Stops the currently running timer and records the elapsed time."
+    )]
+    StopTimer,
+    #[command(
+        about = "Log time spent on a task.",
+        long_about = "Records a time entry for the specified task with a duration such as 1h30m, 2h or 45m."
+    )]
+    Log {
+        #[arg(long = "id")]
+        arg_id: String,
+        #[arg(long = "duration")]
+        arg_duration: String,
+        #[arg(long = "note")]
+        arg_note: Option<String>,
+    },
 }
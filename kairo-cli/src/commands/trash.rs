@@ -0,0 +1,14 @@
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+#[command(about = "Manage soft-deleted notes, tasks and projects")]
+pub enum TrashCommands {
+    #[command(
+        about = "Purge soft-deleted items.",
+        long_about = "Permanently deletes soft-deleted notes, tasks and projects together with their Markdown files in .trash/. With --older-than only items deleted before that age are purged (for example --older-than 30d); without it the whole trash is emptied."
+    )]
+    Empty {
+        #[arg(long = "older-than", help = "Age such as 12h, 30d or 2w")]
+        arg_older_than: Option<String>,
+    },
+}
@@ -0,0 +1,63 @@
+use clap::{Args, Subcommand};
+
+#[derive(Debug, Subcommand)]
+#[command(about = "Manage saved views (named queries)")]
+pub enum ViewCommands {
+    #[command(
+        about = "Save a view.",
+        long_about = "Saves the conditions under a name, replacing an existing view with the same name. The options are converted into a kairo query (for example --entity note --type fleeting --tag-not processed becomes 'is:note type:fleeting -tag:processed'); --query adds raw query conditions. Relative dates such as today are resolved each time the view runs."
+    )]
+    Save {
+        #[arg(help = "View name (letters, digits, - and _)")]
+        arg_name: String,
+        #[command(flatten)]
+        arg_conditions: Box<ViewConditionArgs>,
+    },
+    #[command(
+        about = "Run a view.",
+        long_about = "Runs the saved query and prints the results as a table (default), JSON or CSV."
+    )]
+    Run {
+        arg_name: String,
+        #[arg(long = "format", help = "table, json or csv")]
+        arg_format: Option<String>,
+    },
+    List,
+    Delete {
+        arg_name: String,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct ViewConditionArgs {
+    #[arg(long = "entity", value_delimiter = ',', help = "note, task or project")]
+    pub arg_entities: Vec<String>,
+    #[arg(short = 'n', long = "type")]
+    pub arg_note_type: Option<String>,
+    #[arg(short = 's', long = "sub-type")]
+    pub arg_sub_type: Option<String>,
+    #[arg(
+        long = "tag",
+        value_delimiter = ',',
+        help = "Tags that must all be attached"
+    )]
+    pub arg_tags: Vec<String>,
+    #[arg(long = "tag-not", value_delimiter = ',')]
+    pub arg_tag_not: Vec<String>,
+    #[arg(long = "pid")]
+    pub arg_project_id: Option<String>,
+    #[arg(long = "area")]
+    pub arg_area_id: Option<String>,
+    #[arg(long = "priority")]
+    pub arg_priority: Option<String>,
+    #[arg(long = "status")]
+    pub arg_status: Option<String>,
+    #[arg(long = "title")]
+    pub arg_title: Option<String>,
+    #[arg(
+        long = "query",
+        allow_hyphen_values = true,
+        help = "Additional conditions in kairo query syntax"
+    )]
+    pub arg_query: Option<String>,
+}
@@ -0,0 +1,58 @@
+use crate::commands::area::AreaCommands;
+use diesel::SqliteConnection;
+use kairo_core::usecase::area::*;
+
+pub fn handle_area_command(command: AreaCommands, conn: &mut SqliteConnection) {
+    match command {
+        AreaCommands::Create {
+            arg_title,
+            arg_description,
+        } => {
+            if let Err(e) = handle_create_area(conn, arg_title, arg_description) {
+                eprintln!("Failed to create area: {}", e);
+            }
+        }
+        AreaCommands::List {
+            arg_archived,
+            arg_deleted,
+        } => {
+            if let Err(e) = handle_list_areas(conn, arg_archived, arg_deleted) {
+                eprintln!("Failed to list areas: {}", e);
+            }
+        }
+        AreaCommands::Get { arg_id } => {
+            if let Err(e) = handle_get_area(conn, arg_id) {
+                eprintln!("Failed to get area: {}", e);
+            }
+        }
+        AreaCommands::Update {
+            arg_id,
+            arg_title,
+            arg_description,
+        } => {
+            if let Err(e) = handle_update_area(conn, arg_id, arg_title, arg_description) {
+                eprintln!("Failed to update area: {}", e);
+            }
+        }
+        AreaCommands::Archive { arg_id } => {
+            if let Err(e) = handle_archive_area(conn, arg_id) {
+                eprintln!("Failed to archive area: {}", e);
+            }
+        }
+        AreaCommands::Delete { arg_id } => {
+            if let Err(e) = handle_delete_area(conn, arg_id) {
+                eprintln!("Failed to delete area: {}", e);
+            }
+        }
+        AreaCommands::Unarchive { arg_id } => {
+            if let Err(e) = handle_unarchive_area(conn, arg_id) {
+                eprintln!("Failed to unarchive area: {}", e);
+            }
+        }
+        AreaCommands::Restore { arg_id } => {
+            if let Err(e) = handle_restore_area(conn, arg_id) {
+                eprintln!("Failed to restore area: {}", e);
+            }
+        }
+    }
+}
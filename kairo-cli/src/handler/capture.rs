@@ -0,0 +1,28 @@
+use diesel::SqliteConnection;
+use kairo_core::{config::AppConfig, usecase::note::handle_capture, util::read_body_input};
+
+pub fn handle_capture_command(
+    text: Vec<String>,
+    title: Option<String>,
+    sub_type: Option<String>,
+    tags: Option<Vec<String>>,
+    conn: &mut SqliteConnection,
+    config: &AppConfig,
+) {
+    // 引数がなければ（または "-" なら）標準入力から読む
+    let text = if text.is_empty() || text == ["-"] {
+        match read_body_input(None, Some("-".to_string())) {
+            Ok(body) => body.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("Failed to read stdin: {}", e);
+                return;
+            }
+        }
+    } else {
+        text.join(" ")
+    };
+
+    if let Err(e) = handle_capture(config, conn, text, title, sub_type, tags) {
+        eprintln!("Failed to capture note: {}", e);
+    }
+}
@@ -0,0 +1,13 @@
+use diesel::SqliteConnection;
+use kairo_core::{config::AppConfig, usecase::daily::handle_daily};
+
+pub fn handle_daily_command(
+    date: Option<String>,
+    template: Option<String>,
+    conn: &mut SqliteConnection,
+    config: &AppConfig,
+) {
+    if let Err(e) = handle_daily(config, conn, date, template) {
+        eprintln!("Failed to open daily note: {}", e);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::commands::export::ExportCommands;
+use diesel::SqliteConnection;
+use kairo_core::{
+    config::AppConfig,
+    usecase::{ical::*, taskwarrior::*, todotxt::*},
+};
+
+pub fn handle_export_command(
+    command: ExportCommands,
+    conn: &mut SqliteConnection,
+    config: &AppConfig,
+) {
+    match command {
+        ExportCommands::Ical {
+            arg_output,
+            arg_component,
+        } => {
+            if let Err(e) = handle_export_ical(conn, arg_output, arg_component) {
+                eprintln!("Failed to export iCalendar: {}", e);
+            }
+        }
+        ExportCommands::Todotxt { arg_output } => {
+            if let Err(e) = handle_export_todotxt(conn, arg_output) {
+                eprintln!("Failed to export todo.txt: {}", e);
+            }
+        }
+        ExportCommands::Taskwarrior { arg_output } => {
+            if let Err(e) = handle_export_taskwarrior(config, conn, arg_output) {
+                eprintln!("Failed to export Taskwarrior JSON: {}", e);
+            }
+        }
+    }
+}
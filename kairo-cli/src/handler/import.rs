@@ -0,0 +1,30 @@
+use crate::commands::import::ImportCommands;
+use diesel::SqliteConnection;
+use kairo_core::{
+    config::AppConfig,
+    usecase::{ical::*, taskwarrior::*, todotxt::*},
+};
+
+pub fn handle_import_command(
+    command: ImportCommands,
+    conn: &mut SqliteConnection,
+    config: &AppConfig,
+) {
+    match command {
+        ImportCommands::Ical { arg_file } => {
+            if let Err(e) = handle_import_ical(config, conn, arg_file) {
+                eprintln!("Failed to import iCalendar: {}", e);
+            }
+        }
+        ImportCommands::Todotxt { arg_file } => {
+            if let Err(e) = handle_import_todotxt(config, conn, arg_file) {
+                eprintln!("Failed to import todo.txt: {}", e);
+            }
+        }
+        ImportCommands::Taskwarrior { arg_file } => {
+            if let Err(e) = handle_import_taskwarrior(config, conn, arg_file) {
+                eprintln!("Failed to import Taskwarrior JSON: {}", e);
+            }
+        }
+    }
+}
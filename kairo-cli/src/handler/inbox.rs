@@ -0,0 +1,9 @@
+use diesel::SqliteConnection;
+use kairo_core::{config::AppConfig, usecase::inbox::handle_inbox};
+use std::io;
+
+pub fn handle_inbox_command(conn: &mut SqliteConnection, config: &AppConfig) {
+    if let Err(e) = handle_inbox(config, conn, &mut io::stdin().lock()) {
+        eprintln!("Failed to process inbox: {}", e);
+    }
+}
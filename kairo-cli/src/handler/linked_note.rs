@@ -1,17 +1,22 @@
 use crate::{commands::linked_note::LinkCommands, kairo_core::store::*};
 use diesel::SqliteConnection;
-use kairo_core::util::print_links_as_table;
+use kairo_core::{
+    config::AppConfig,
+    usecase::{handle_create_link, handle_delete_link},
+    util::print_links_as_table,
+};
 
-pub fn handle_link_command(command: LinkCommands, conn: &mut SqliteConnection) {
+pub fn handle_link_command(command: LinkCommands, conn: &mut SqliteConnection, config: &AppConfig) {
     match command {
         LinkCommands::Create {
             arg_from,
             arg_to,
             arg_link_type,
-        } => match create_link(conn, arg_from, arg_to, arg_link_type) {
-            Ok(link) => println!("{:?}", link),
-            Err(e) => eprintln!("Failed to create link: {}", e),
-        },
+        } => {
+            if let Err(e) = handle_create_link(config, conn, arg_from, arg_to, arg_link_type) {
+                eprintln!("Failed to create link: {}", e)
+            }
+        }
         LinkCommands::List { arg_from, arg_to } => match list_links(conn, arg_from, arg_to) {
             Ok(links) => {
                 print_links_as_table(conn, &links);
@@ -31,9 +36,10 @@ pub fn handle_link_command(command: LinkCommands, conn: &mut SqliteConnection) {
                 println!("Database error: {:?}", e);
             }
         },
-        LinkCommands::Delete { arg_id } => match soft_delete_link(conn, &arg_id) {
-            Ok(link) => println!("{:?}", link),
-            Err(e) => eprintln!("Failed to delete link: {}", e),
-        },
+        LinkCommands::Delete { arg_id } => {
+            if let Err(e) = handle_delete_link(config, conn, arg_id) {
+                eprintln!("Failed to delete link: {}", e)
+            }
+        }
     }
 }
@@ -1,11 +1,31 @@
+pub mod area;
+pub mod capture;
+pub mod daily;
+pub mod export;
+pub mod import;
+pub mod inbox;
 pub mod linked_note;
 pub mod note;
 pub mod project;
+pub mod query;
+pub mod report;
 pub mod tag;
 pub mod task;
+pub mod trash;
+pub mod view;
 
+pub use area::*;
+pub use capture::*;
+pub use daily::*;
+pub use export::*;
+pub use import::*;
+pub use inbox::*;
 pub use linked_note::*;
 pub use note::*;
 pub use project::*;
+pub use query::*;
+pub use report::*;
 pub use tag::*;
 pub use task::*;
+pub use trash::*;
+pub use view::*;
@@ -1,6 +1,11 @@
 use crate::commands::note::NoteCommands;
 use diesel::SqliteConnection;
-use kairo_core::{config::AppConfig, usecase::note::*};
+use kairo_core::{
+    config::AppConfig,
+    model::{NewNoteInput, NoteFilter, TagFilter},
+    usecase::note::*,
+    util::read_body_input,
+};
 
 pub fn handle_note_command(command: NoteCommands, conn: &mut SqliteConnection, config: &AppConfig) {
     match command {
@@ -11,17 +16,25 @@ pub fn handle_note_command(command: NoteCommands, conn: &mut SqliteConnection, c
             arg_project_id,
             arg_task_id,
             arg_tags,
+            arg_template,
+            arg_body,
+            arg_body_file,
         } => {
-            if let Err(e) = handle_create_note(
-                config,
-                conn,
-                arg_title,
-                &arg_note_type,
-                &arg_sub_type,
-                arg_project_id,
-                arg_task_id,
-                arg_tags,
-            ) {
+            let body = match read_body_input(arg_body, arg_body_file) {
+                Ok(body) => body,
+                Err(e) => {
+                    eprintln!("Failed to read body: {}", e);
+                    return;
+                }
+            };
+            let input = NewNoteInput {
+                note_type: arg_note_type,
+                sub_type: arg_sub_type,
+                project_id: arg_project_id,
+                task_id: arg_task_id,
+                tags: arg_tags,
+            };
+            if let Err(e) = handle_create_note(config, conn, arg_title, input, arg_template, body) {
                 eprintln!("Failed to create note: {}", e);
             }
         }
@@ -29,13 +42,47 @@ pub fn handle_note_command(command: NoteCommands, conn: &mut SqliteConnection, c
             arg_archived,
             arg_deleted,
             arg_tags,
+            arg_tag_all,
+            arg_tag_not,
             arg_order,
+            arg_area_id,
+            arg_filter,
         } => {
-            if let Err(e) = handle_list_notes(conn, arg_archived, arg_deleted, arg_tags, arg_order)
-            {
+            let filter = NoteFilter {
+                note_type: arg_filter.arg_note_type,
+                sub_type: arg_filter.arg_sub_type,
+                project_id: arg_filter.arg_project_id,
+                task_id: arg_filter.arg_task_id,
+                created_from: arg_filter.arg_created_from,
+                created_to: arg_filter.arg_created_to,
+                updated_from: arg_filter.arg_updated_from,
+                updated_to: arg_filter.arg_updated_to,
+                title: arg_filter.arg_title,
+                sort: arg_filter.arg_sort,
+                limit: arg_filter.arg_limit,
+                offset: arg_filter.arg_offset,
+            };
+            if let Err(e) = handle_list_notes(
+                conn,
+                arg_archived,
+                arg_deleted,
+                TagFilter::new(arg_tag_all, arg_tags, arg_tag_not),
+                arg_order,
+                arg_area_id,
+                filter,
+            ) {
                 eprintln!("Failed to list notes: {}", e);
             }
         }
+        NoteCommands::Show {
+            arg_id,
+            arg_raw,
+            arg_no_pager,
+        } => {
+            if let Err(e) = handle_show_note(config, conn, arg_id, arg_raw, !arg_no_pager) {
+                eprintln!("Failed to show note: {}", e);
+            }
+        }
         NoteCommands::Get { arg_id } => {
             if let Err(e) = handle_get_note(conn, arg_id) {
                 eprintln!("Failed to get note: {}", e);
@@ -65,29 +112,41 @@ pub fn handle_note_command(command: NoteCommands, conn: &mut SqliteConnection, c
             }
         }
         NoteCommands::Archive { arg_id } => {
-            if let Err(e) = handle_archive_note(conn, arg_id) {
+            if let Err(e) = handle_archive_note(config, conn, arg_id) {
                 eprintln!("Failed to archive note: {}", e);
             }
         }
         NoteCommands::Delete { arg_id } => {
-            if let Err(e) = handle_delete_note(conn, arg_id) {
+            if let Err(e) = handle_delete_note(config, conn, arg_id) {
                 eprintln!("Failed to delete note: {}", e);
             }
         }
         NoteCommands::Purge { arg_id } => {
-            if let Err(e) = handle_purge_note(conn, arg_id) {
+            if let Err(e) = handle_purge_note(config, conn, arg_id) {
                 eprintln!("Failed to purge note: {}", e);
             }
         }
         NoteCommands::Unarchive { arg_id } => {
-            if let Err(e) = handle_unarchive_note(conn, arg_id) {
+            if let Err(e) = handle_unarchive_note(config, conn, arg_id) {
                 eprintln!("Failed to unarchive note: {}", e);
             }
         }
         NoteCommands::Restore { arg_id } => {
-            if let Err(e) = handle_restore_note(conn, arg_id) {
+            if let Err(e) = handle_restore_note(config, conn, arg_id) {
                 eprintln!("Failed to restore note: {}", e);
             }
         }
+        NoteCommands::Promote {
+            arg_id,
+            arg_split,
+            arg_title,
+            arg_link_type,
+        } => {
+            if let Err(e) =
+                handle_promote_note(config, conn, arg_id, arg_split, arg_title, arg_link_type)
+            {
+                eprintln!("Failed to promote note: {}", e);
+            }
+        }
     }
 }
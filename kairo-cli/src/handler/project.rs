@@ -1,6 +1,11 @@
 use crate::commands::project::ProjectCommands;
 use diesel::SqliteConnection;
-use kairo_core::{config::AppConfig, usecase::project::*};
+use kairo_core::{
+    config::AppConfig,
+    model::{NewProjectInput, TagFilter, UpdateProjectInput},
+    usecase::project::*,
+    util::read_body_input,
+};
 
 pub fn handle_project_command(
     command: ProjectCommands,
@@ -12,10 +17,31 @@ pub fn handle_project_command(
             arg_title,
             arg_description,
             arg_tags,
+            arg_status,
+            arg_start_date,
+            arg_target_date,
+            arg_parent_id,
+            arg_area_id,
+            arg_body,
+            arg_body_file,
         } => {
-            if let Err(e) =
-                handle_create_project(config, conn, arg_title, arg_description, arg_tags)
-            {
+            let body = match read_body_input(arg_body, arg_body_file) {
+                Ok(body) => body,
+                Err(e) => {
+                    eprintln!("Failed to read body: {}", e);
+                    return;
+                }
+            };
+            let input = NewProjectInput {
+                description: arg_description,
+                tags: arg_tags,
+                status: arg_status,
+                start_date: arg_start_date,
+                target_date: arg_target_date,
+                parent_id: arg_parent_id,
+                area_id: arg_area_id,
+            };
+            if let Err(e) = handle_create_project(config, conn, arg_title, input, body) {
                 eprintln!("Failed to create project: {}", e);
             }
         }
@@ -23,11 +49,19 @@ pub fn handle_project_command(
             arg_archived,
             arg_deleted,
             arg_tags,
+            arg_tag_all,
+            arg_tag_not,
             arg_order,
+            arg_area_id,
         } => {
-            if let Err(e) =
-                handle_list_projects(conn, arg_archived, arg_deleted, arg_tags, arg_order)
-            {
+            if let Err(e) = handle_list_projects(
+                conn,
+                arg_archived,
+                arg_deleted,
+                TagFilter::new(arg_tag_all, arg_tags, arg_tag_not),
+                arg_order,
+                arg_area_id,
+            ) {
                 eprintln!("Failed to list projects: {}", e);
             }
         }
@@ -36,42 +70,92 @@ pub fn handle_project_command(
                 eprintln!("Failed to get project: {}", e);
             }
         }
+        ProjectCommands::Show {
+            arg_id,
+            arg_content,
+            arg_raw,
+            arg_no_pager,
+        } => {
+            let result = if arg_content {
+                handle_show_project_content(config, conn, arg_id, arg_raw, !arg_no_pager)
+            } else {
+                handle_show_project(conn, arg_id)
+            };
+            if let Err(e) = result {
+                eprintln!("Failed to show project: {}", e);
+            }
+        }
         ProjectCommands::Update {
             arg_id,
             arg_title,
             arg_description,
             arg_tags,
+            arg_status,
+            arg_start_date,
+            arg_target_date,
+            arg_parent_id,
+            arg_area_id,
         } => {
-            if let Err(e) =
-                handle_update_project(config, conn, arg_id, arg_title, arg_description, arg_tags)
-            {
+            let input = UpdateProjectInput {
+                title: arg_title,
+                description: arg_description,
+                tags: arg_tags,
+                status: arg_status,
+                start_date: arg_start_date,
+                target_date: arg_target_date,
+                parent_id: arg_parent_id,
+                area_id: arg_area_id,
+            };
+            if let Err(e) = handle_update_project(config, conn, arg_id, input) {
                 eprintln!("Failed to update project: {}", e);
             }
         }
-        ProjectCommands::Archive { arg_id } => {
-            if let Err(e) = handle_archive_project(conn, arg_id) {
+        ProjectCommands::Archive {
+            arg_id,
+            arg_cascade,
+        } => {
+            if let Err(e) = handle_archive_project(config, conn, arg_id, arg_cascade) {
                 eprintln!("Failed to archive project: {}", e);
             }
         }
-        ProjectCommands::Delete { arg_id } => {
-            if let Err(e) = handle_delete_project(conn, arg_id) {
+        ProjectCommands::Delete {
+            arg_id,
+            arg_cascade,
+        } => {
+            if let Err(e) = handle_delete_project(config, conn, arg_id, arg_cascade) {
                 eprintln!("Failed to delete project: {}", e);
             }
         }
         ProjectCommands::Purge { arg_id } => {
-            if let Err(e) = handle_purge_project(conn, arg_id) {
+            if let Err(e) = handle_purge_project(config, conn, arg_id) {
                 eprintln!("Failed to purge project: {}", e);
             }
         }
-        ProjectCommands::Unarchive { arg_id } => {
-            if let Err(e) = handle_archive_project(conn, arg_id) {
+        ProjectCommands::Unarchive {
+            arg_id,
+            arg_cascade,
+        } => {
+            if let Err(e) = handle_unarchive_project(config, conn, arg_id, arg_cascade) {
                 eprintln!("Failed to unarchive project: {}", e);
             }
         }
-        ProjectCommands::Restore { arg_id } => {
-            if let Err(e) = handle_restore_project(conn, arg_id) {
+        ProjectCommands::Restore {
+            arg_id,
+            arg_cascade,
+        } => {
+            if let Err(e) = handle_restore_project(config, conn, arg_id, arg_cascade) {
                 eprintln!("Failed to restore project: {}", e);
             }
         }
+        ProjectCommands::Report { arg_id } => {
+            if let Err(e) = handle_project_report(config, conn, arg_id) {
+                eprintln!("Failed to report project: {}", e);
+            }
+        }
+        ProjectCommands::Tree => {
+            if let Err(e) = handle_project_tree(conn) {
+                eprintln!("Failed to show project tree: {}", e);
+            }
+        }
     }
 }
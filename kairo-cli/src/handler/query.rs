@@ -0,0 +1,8 @@
+use diesel::SqliteConnection;
+use kairo_core::usecase::query::handle_query;
+
+pub fn handle_query_command(query: String, format: Option<String>, conn: &mut SqliteConnection) {
+    if let Err(e) = handle_query(conn, query, format) {
+        eprintln!("Failed to run query: {}", e);
+    }
+}
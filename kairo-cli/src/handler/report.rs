@@ -0,0 +1,18 @@
+use crate::commands::report::ReportCommands;
+use diesel::SqliteConnection;
+use kairo_core::usecase::report::*;
+
+pub fn handle_report_command(command: ReportCommands, conn: &mut SqliteConnection) {
+    match command {
+        ReportCommands::Time {
+            arg_from,
+            arg_to,
+            arg_project_id,
+            arg_format,
+        } => {
+            if let Err(e) = handle_time_report(conn, arg_from, arg_to, arg_project_id, arg_format) {
+                eprintln!("Failed to report time: {}", e);
+            }
+        }
+    }
+}
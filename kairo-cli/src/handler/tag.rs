@@ -1,8 +1,8 @@
 use crate::commands::tag::TagCommands;
 use diesel::SqliteConnection;
-use kairo_core::{store::*, util::print_tags_as_table};
+use kairo_core::{config::AppConfig, store::*, usecase::tag::*, util::print_tags_as_table};
 
-pub fn handle_tag_command(command: TagCommands, conn: &mut SqliteConnection) {
+pub fn handle_tag_command(command: TagCommands, conn: &mut SqliteConnection, config: &AppConfig) {
     match command {
         TagCommands::Create { arg_tag_name } => match create_tag(conn, arg_tag_name) {
             Ok(tag) => println!("{:?}", tag),
@@ -17,27 +17,68 @@ pub fn handle_tag_command(command: TagCommands, conn: &mut SqliteConnection) {
             }
             Err(e) => eprintln!("Failed to fetch tags: {}", e),
         },
-        TagCommands::Get { arg_id } => match get_tag_by_id(conn, &arg_id) {
-            Ok(Some(tag)) => {
-                println!("{tag}");
+        TagCommands::Get { arg_id } => {
+            if let Err(e) = handle_get_tag(conn, arg_id) {
+                eprintln!("Failed to get tag: {}", e);
             }
-            Ok(None) => {
-                println!("Tag not found");
-            }
-            Err(e) => {
-                println!("Database error: {:?}", e);
-            }
-        },
+        }
         TagCommands::Update {
             arg_id,
             arg_tag_name,
-        } => match rename_tag(conn, &arg_id, arg_tag_name) {
-            Ok(tag) => println!("{:?}", tag),
-            Err(e) => eprintln!("Failed to update tag: {}", e),
-        },
-        TagCommands::Delete { arg_id } => match soft_delete_tag(conn, &arg_id) {
-            Ok(tag) => println!("{:?}", tag),
-            Err(e) => eprintln!("Failed to delete tag: {}", e),
-        },
+        } => {
+            if let Err(e) = handle_rename_tag(config, conn, arg_id, arg_tag_name) {
+                eprintln!("Failed to update tag: {}", e);
+            }
+        }
+        TagCommands::Delete { arg_id } => {
+            if let Err(e) = handle_delete_tag(config, conn, arg_id) {
+                eprintln!("Failed to delete tag: {}", e);
+            }
+        }
+        TagCommands::Restore { arg_id } => {
+            if let Err(e) = handle_restore_tag(config, conn, arg_id) {
+                eprintln!("Failed to restore tag: {}", e);
+            }
+        }
+        TagCommands::Purge { arg_id } => {
+            if let Err(e) = handle_purge_tag(config, conn, arg_id) {
+                eprintln!("Failed to purge tag: {}", e);
+            }
+        }
+        TagCommands::Tree => {
+            if let Err(e) = handle_tag_tree(conn) {
+                eprintln!("Failed to show tag tree: {}", e);
+            }
+        }
+        TagCommands::Stats {
+            arg_sort,
+            arg_order,
+            arg_unused,
+            arg_format,
+        } => {
+            if let Err(e) = handle_tag_stats(conn, arg_sort, arg_order, arg_unused, arg_format) {
+                eprintln!("Failed to show tag stats: {}", e);
+            }
+        }
+        TagCommands::Merge { arg_from, arg_into } => {
+            if let Err(e) = handle_merge_tags(config, conn, arg_from, arg_into) {
+                eprintln!("Failed to merge tags: {}", e);
+            }
+        }
+        TagCommands::Alias { arg_alias, arg_tag } => {
+            if let Err(e) = handle_add_tag_alias(conn, arg_alias, arg_tag) {
+                eprintln!("Failed to add tag alias: {}", e);
+            }
+        }
+        TagCommands::Unalias { arg_alias } => {
+            if let Err(e) = handle_remove_tag_alias(conn, arg_alias) {
+                eprintln!("Failed to remove tag alias: {}", e);
+            }
+        }
+        TagCommands::Aliases => {
+            if let Err(e) = handle_list_tag_aliases(conn) {
+                eprintln!("Failed to list tag aliases: {}", e);
+            }
+        }
     }
 }
@@ -1,6 +1,11 @@
 use crate::commands::task::TaskCommands;
 use diesel::SqliteConnection;
-use kairo_core::{config::AppConfig, usecase::task::*};
+use kairo_core::{
+    config::AppConfig,
+    model::{NewTaskInput, TagFilter},
+    usecase::{task::*, time_entry::*},
+    util::read_body_input,
+};
 
 pub fn handle_task_command(command: TaskCommands, conn: &mut SqliteConnection, config: &AppConfig) {
     match command {
@@ -11,17 +16,24 @@ pub fn handle_task_command(command: TaskCommands, conn: &mut SqliteConnection, c
             arg_due_date,
             arg_project_id,
             arg_tags,
+            arg_body,
+            arg_body_file,
         } => {
-            if let Err(e) = handle_create_task(
-                config,
-                conn,
-                arg_title,
-                arg_description,
-                arg_priority,
-                arg_due_date,
-                arg_project_id,
-                arg_tags,
-            ) {
+            let body = match read_body_input(arg_body, arg_body_file) {
+                Ok(body) => body,
+                Err(e) => {
+                    eprintln!("Failed to read body: {}", e);
+                    return;
+                }
+            };
+            let input = NewTaskInput {
+                description: arg_description,
+                priority: arg_priority,
+                due_date: arg_due_date,
+                project_id: arg_project_id,
+                tags: arg_tags,
+            };
+            if let Err(e) = handle_create_task(config, conn, arg_title, input, body) {
                 eprintln!("Failed to create task: {}", e);
             }
         }
@@ -29,22 +41,35 @@ pub fn handle_task_command(command: TaskCommands, conn: &mut SqliteConnection, c
             arg_archived,
             arg_deleted,
             arg_tags,
+            arg_tag_all,
+            arg_tag_not,
             arg_order,
             arg_priority,
             arg_project_id,
+            arg_area_id,
         } => {
             if let Err(e) = handle_list_tasks(
                 conn,
                 arg_archived,
                 arg_deleted,
-                arg_tags,
+                TagFilter::new(arg_tag_all, arg_tags, arg_tag_not),
                 arg_order,
                 arg_priority,
                 arg_project_id,
+                arg_area_id,
             ) {
                 eprintln!("Failed to list tasks: {}", e);
             }
         }
+        TaskCommands::Show {
+            arg_id,
+            arg_raw,
+            arg_no_pager,
+        } => {
+            if let Err(e) = handle_show_task(config, conn, arg_id, arg_raw, !arg_no_pager) {
+                eprintln!("Failed to show task: {}", e);
+            }
+        }
         TaskCommands::Get { arg_id } => {
             if let Err(e) = handle_get_task(conn, arg_id) {
                 eprintln!("Failed to get task: {}", e);
@@ -74,29 +99,48 @@ pub fn handle_task_command(command: TaskCommands, conn: &mut SqliteConnection, c
             }
         }
         TaskCommands::Archive { arg_id } => {
-            if let Err(e) = handle_archive_task(conn, arg_id) {
+            if let Err(e) = handle_archive_task(config, conn, arg_id) {
                 eprintln!("Failed to archive task: {}", e);
             }
         }
         TaskCommands::Delete { arg_id } => {
-            if let Err(e) = handle_delete_task(conn, arg_id) {
+            if let Err(e) = handle_delete_task(config, conn, arg_id) {
                 eprintln!("Failed to delete task: {}", e);
             }
         }
         TaskCommands::Purge { arg_id } => {
-            if let Err(e) = handle_purge_task(conn, arg_id) {
+            if let Err(e) = handle_purge_task(config, conn, arg_id) {
                 eprintln!("Failed to purge task: {}", e);
             }
         }
         TaskCommands::Unarchive { arg_id } => {
-            if let Err(e) = handle_unarchive_task(conn, arg_id) {
+            if let Err(e) = handle_unarchive_task(config, conn, arg_id) {
                 eprintln!("Failed to unarchive task: {}", e);
             }
         }
         TaskCommands::Restore { arg_id } => {
-            if let Err(e) = handle_restore_task(conn, arg_id) {
+            if let Err(e) = handle_restore_task(config, conn, arg_id) {
                 eprintln!("Failed to restore task: {}", e);
             }
         }
+        TaskCommands::StartTimer { arg_id, arg_note } => {
+            if let Err(e) = handle_start_timer(conn, arg_id, arg_note) {
+                eprintln!("Failed to start timer: {}", e);
+            }
+        }
+        TaskCommands::StopTimer => {
+            if let Err(e) = handle_stop_timer(conn) {
+                eprintln!("Failed to stop timer: {}", e);
+            }
+        }
+        TaskCommands::Log {
+            arg_id,
+            arg_duration,
+            arg_note,
+        } => {
+            if let Err(e) = handle_log_time(conn, arg_id, arg_duration, arg_note) {
+                eprintln!("Failed to log time: {}", e);
+            }
+        }
     }
 }
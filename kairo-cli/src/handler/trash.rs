@@ -0,0 +1,17 @@
+use crate::commands::trash::TrashCommands;
+use diesel::SqliteConnection;
+use kairo_core::{config::AppConfig, usecase::trash::handle_empty_trash};
+
+pub fn handle_trash_command(
+    command: TrashCommands,
+    conn: &mut SqliteConnection,
+    config: &AppConfig,
+) {
+    match command {
+        TrashCommands::Empty { arg_older_than } => {
+            if let Err(e) = handle_empty_trash(config, conn, arg_older_than) {
+                eprintln!("Failed to empty trash: {}", e);
+            }
+        }
+    }
+}
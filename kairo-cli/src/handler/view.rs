@@ -0,0 +1,49 @@
+use crate::commands::view::ViewCommands;
+use diesel::SqliteConnection;
+use kairo_core::usecase::view::{
+    ViewConditions, handle_delete_view, handle_list_views, handle_run_view, handle_save_view,
+};
+
+pub fn handle_view_command(command: ViewCommands, conn: &mut SqliteConnection) {
+    match command {
+        ViewCommands::Save {
+            arg_name,
+            arg_conditions,
+        } => {
+            let conditions = ViewConditions {
+                entities: arg_conditions.arg_entities,
+                note_type: arg_conditions.arg_note_type,
+                sub_type: arg_conditions.arg_sub_type,
+                tags: arg_conditions.arg_tags,
+                tags_not: arg_conditions.arg_tag_not,
+                project: arg_conditions.arg_project_id,
+                area: arg_conditions.arg_area_id,
+                priority: arg_conditions.arg_priority,
+                status: arg_conditions.arg_status,
+                title: arg_conditions.arg_title,
+                query: arg_conditions.arg_query,
+            };
+            if let Err(e) = handle_save_view(conn, arg_name, conditions) {
+                eprintln!("Failed to save view: {}", e);
+            }
+        }
+        ViewCommands::Run {
+            arg_name,
+            arg_format,
+        } => {
+            if let Err(e) = handle_run_view(conn, arg_name, arg_format) {
+                eprintln!("Failed to run view: {}", e);
+            }
+        }
+        ViewCommands::List => {
+            if let Err(e) = handle_list_views(conn) {
+                eprintln!("Failed to list views: {}", e);
+            }
+        }
+        ViewCommands::Delete { arg_name } => {
+            if let Err(e) = handle_delete_view(conn, arg_name) {
+                eprintln!("Failed to delete view: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE time_entries (
+    id TEXT NOT NULL PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    started_at TIMESTAMP NOT NULL,
+    ended_at TIMESTAMP,
+    note TEXT,
+    created_at TIMESTAMP NOT NULL,
+    FOREIGN KEY(task_id) REFERENCES tasks(id)
+);
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE projects DROP COLUMN target_date;
+ALTER TABLE projects DROP COLUMN start_date;
+ALTER TABLE projects DROP COLUMN status;
\ No newline at end of file
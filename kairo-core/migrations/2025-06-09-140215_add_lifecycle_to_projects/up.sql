@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE projects ADD COLUMN status TEXT NOT NULL DEFAULT 'active';
+ALTER TABLE projects ADD COLUMN start_date TIMESTAMP;
+ALTER TABLE projects ADD COLUMN target_date TIMESTAMP;
\ No newline at end of file
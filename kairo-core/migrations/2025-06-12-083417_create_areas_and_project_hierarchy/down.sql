@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE projects DROP COLUMN area_id;
+ALTER TABLE projects DROP COLUMN parent_id;
+DROP TABLE areas;
\ No newline at end of file
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE areas (
+    id TEXT NOT NULL PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    archived BOOLEAN NOT NULL DEFAULT FALSE,
+    deleted BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+ALTER TABLE projects ADD COLUMN parent_id TEXT REFERENCES projects(id);
+ALTER TABLE projects ADD COLUMN area_id TEXT REFERENCES areas(id);
\ No newline at end of file
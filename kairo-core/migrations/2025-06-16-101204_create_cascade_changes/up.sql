@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE cascade_changes (
+    project_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (project_id, action, entity_type, entity_id),
+    FOREIGN KEY(project_id) REFERENCES projects(id)
+);
\ No newline at end of file
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE TABLE tag_aliases (
+    alias TEXT NOT NULL PRIMARY KEY,
+    tag_id TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    FOREIGN KEY(tag_id) REFERENCES tags(id)
+);
\ No newline at end of file
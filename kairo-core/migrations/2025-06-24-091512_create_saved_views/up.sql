@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE TABLE saved_views (
+    name TEXT NOT NULL PRIMARY KEY,
+    query TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE projects DROP COLUMN file_path;
+ALTER TABLE tasks DROP COLUMN file_path;
+ALTER TABLE notes DROP COLUMN file_path;
\ No newline at end of file
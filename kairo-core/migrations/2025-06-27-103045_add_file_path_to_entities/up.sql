@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE notes ADD COLUMN file_path TEXT;
+ALTER TABLE tasks ADD COLUMN file_path TEXT;
+ALTER TABLE projects ADD COLUMN file_path TEXT;
\ No newline at end of file
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TABLE notes ADD COLUMN deleted_at TIMESTAMP;
+ALTER TABLE tasks ADD COLUMN deleted_at TIMESTAMP;
+ALTER TABLE projects ADD COLUMN deleted_at TIMESTAMP;
+UPDATE notes SET deleted_at = updated_at WHERE deleted = 1;
+UPDATE tasks SET deleted_at = updated_at WHERE deleted = 1;
+UPDATE projects SET deleted_at = updated_at WHERE deleted = 1;
\ No newline at end of file
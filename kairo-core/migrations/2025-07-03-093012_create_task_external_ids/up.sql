@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE task_external_ids (
+    source TEXT NOT NULL,
+    external_id TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (source, external_id),
+    FOREIGN KEY(task_id) REFERENCES tasks(id)
+);
\ No newline at end of file
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE tasks ADD COLUMN completed_at TIMESTAMP;
+UPDATE tasks SET completed_at = updated_at WHERE archived = 1;
\ No newline at end of file
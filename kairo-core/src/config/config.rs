@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize)]
 pub struct AppConfig {
     pub paths: PathsConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -12,4 +14,20 @@ pub struct PathsConfig {
     pub notes_dir: String,
     pub projects_dir: String,
     pub tasks_dir: String,
+    // 省略時は設定ファイルと同じディレクトリの templates/
+    #[serde(default)]
+    pub templates_dir: Option<String>,
+}
+
+// 各ディレクトリの中での Markdown ファイルの置き方。
+// `{type}/{id}-{slug}` のように、プレースホルダーと `/` でサブディレクトリとファイル名を書く。
+// 省略時は `{id}`（拡張子 .md は自動で付く）
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct LayoutConfig {
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub tasks: Option<String>,
+    #[serde(default)]
+    pub projects: Option<String>,
 }
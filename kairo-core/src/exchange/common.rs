@@ -0,0 +1,57 @@
+use crate::model::TaskPriority;
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportedStatus {
+    Pending,
+    Completed,
+    Deleted,
+}
+
+// 外部ツールでの識別子。source は識別子の種類（"ical" など）
+#[derive(Debug, Clone)]
+pub struct ExternalId {
+    pub source: &'static str,
+    pub id: String,
+}
+
+// 外部フォーマットから読み取ったタスク。
+// id は kairo 由来のエントリのみ設定され、既存タスクの更新に使う。
+// external_id は外部ツールで作られたエントリの識別子で、取り込み直したときの照合に使う。
+#[derive(Debug, Clone)]
+pub struct ImportedTask {
+    pub id: Option<String>,
+    pub external_id: Option<ExternalId>,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Option<TaskPriority>,
+    pub due: Option<NaiveDate>,
+    pub tags: Vec<String>,
+    pub project_id: Option<String>,
+    pub project_name: Option<String>,
+    pub status: Option<ImportedStatus>,
+    pub body: Option<String>,
+}
+
+impl ImportedTask {
+    pub fn new(title: String) -> Self {
+        ImportedTask {
+            id: None,
+            external_id: None,
+            title,
+            description: None,
+            priority: None,
+            due: None,
+            tags: Vec::new(),
+            project_id: None,
+            project_name: None,
+            status: None,
+            body: None,
+        }
+    }
+}
+
+// todo.txt の +project / @context のように空白を含められない場所で使う名前
+pub fn slug_token(title: &str) -> String {
+    title.split_whitespace().collect::<Vec<_>>().join("-")
+}
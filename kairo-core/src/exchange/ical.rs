@@ -0,0 +1,557 @@
+use crate::{
+    exchange::{ExternalId, ImportedStatus, ImportedTask},
+    model::{Task, TaskPriority},
+};
+use chrono::{NaiveDate, NaiveDateTime};
+
+const PRODID: &str = "-//kairo//kairo-cli//EN";
+const UID_DOMAIN: &str = "kairo";
+const EXTERNAL_ID_SOURCE: &str = "ical";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcalComponent {
+    Todo,
+    Event,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcalStatus {
+    NeedsAction,
+    InProcess,
+    Completed,
+    Cancelled,
+}
+
+// .ics から読み取った VTODO / VEVENT の内容
+#[derive(Debug, Clone)]
+pub struct IcalTodo {
+    pub component: IcalComponent,
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub priority: Option<TaskPriority>,
+    pub due: Option<NaiveDate>,
+    pub categories: Vec<String>,
+    pub status: Option<IcalStatus>,
+    pub project_id: Option<String>,
+}
+
+impl IcalTodo {
+    // kairo が出力した UID（"task-001@kairo"）であればタスクIDを返す
+    pub fn task_id(&self) -> Option<&str> {
+        self.uid
+            .as_deref()
+            .and_then(|uid| uid.strip_suffix(&format!("@{}", UID_DOMAIN)))
+    }
+
+    // SUMMARY のないエントリはタスクとして取り込めないため None を返す
+    pub fn to_imported(&self) -> Option<ImportedTask> {
+        let mut imported = ImportedTask::new(self.summary.clone()?);
+        imported.id = self.task_id().map(|tid| tid.to_string());
+        // ほかのカレンダーアプリで作られたエントリは UID で照合する
+        if imported.id.is_none() {
+            imported.external_id = self.uid.clone().map(|uid| ExternalId {
+                source: EXTERNAL_ID_SOURCE,
+                id: uid,
+            });
+        }
+        imported.description = self.description.clone();
+        imported.priority = self.priority;
+        imported.due = self.due;
+        imported.tags = self.categories.clone();
+        imported.project_id = self.project_id.clone();
+        imported.status = self.status.map(|status| match status {
+            IcalStatus::NeedsAction | IcalStatus::InProcess => ImportedStatus::Pending,
+            IcalStatus::Completed => ImportedStatus::Completed,
+            IcalStatus::Cancelled => ImportedStatus::Deleted,
+        });
+        Some(imported)
+    }
+}
+
+// ==============================
+// ▼ Export
+// ==============================
+
+// タスクとタグ名の組から VCALENDAR 文字列を生成する。
+// VEVENT は期日のあるタスクのみを終日イベントとして出力する。
+pub fn tasks_to_ical(tasks: &[(Task, Vec<String>)], component: IcalComponent) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for (task, tags) in tasks {
+        match component {
+            IcalComponent::Todo => push_vtodo(&mut lines, task, tags),
+            IcalComponent::Event => {
+                if task.due_date.is_some() {
+                    push_vevent(&mut lines, task, tags);
+                }
+            }
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut output = String::new();
+    for line in lines {
+        output.push_str(&fold_line(&line));
+        output.push_str("\r\n");
+    }
+    output
+}
+
+fn push_vtodo(lines: &mut Vec<String>, task: &Task, tags: &[String]) {
+    lines.push("BEGIN:VTODO".to_string());
+    push_common_properties(lines, task, tags);
+    if let Some(due) = task.due_date {
+        lines.push(format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")));
+    }
+    let priority = match task.priority {
+        Some(TaskPriority::High) => 1,
+        Some(TaskPriority::Medium) => 5,
+        Some(TaskPriority::Low) => 9,
+        None => 0,
+    };
+    lines.push(format!("PRIORITY:{}", priority));
+    let status = if task.deleted {
+        "CANCELLED"
+    } else if task.archived {
+        "COMPLETED"
+    } else {
+        "NEEDS-ACTION"
+    };
+    lines.push(format!("STATUS:{}", status));
+    lines.push("END:VTODO".to_string());
+}
+
+fn push_vevent(lines: &mut Vec<String>, task: &Task, tags: &[String]) {
+    lines.push("BEGIN:VEVENT".to_string());
+    push_common_properties(lines, task, tags);
+    if let Some(due) = task.due_date {
+        let start = due.date();
+        let end = start.succ_opt().unwrap_or(start);
+        lines.push(format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")));
+        lines.push(format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+    }
+    let status = if task.deleted {
+        "CANCELLED"
+    } else {
+        "CONFIRMED"
+    };
+    lines.push(format!("STATUS:{}", status));
+    lines.push("END:VEVENT".to_string());
+}
+
+fn push_common_properties(lines: &mut Vec<String>, task: &Task, tags: &[String]) {
+    lines.push(format!("UID:{}@{}", task.id, UID_DOMAIN));
+    lines.push(format!("DTSTAMP:{}", format_utc(&task.updated_at)));
+    lines.push(format!("CREATED:{}", format_utc(&task.created_at)));
+    lines.push(format!("LAST-MODIFIED:{}", format_utc(&task.updated_at)));
+    lines.push(format!("SUMMARY:{}", escape_text(&task.title)));
+    if let Some(description) = &task.description {
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+    }
+    if !tags.is_empty() {
+        let categories: Vec<String> = tags.iter().map(|t| escape_text(t)).collect();
+        lines.push(format!("CATEGORIES:{}", categories.join(",")));
+    }
+    if let Some(pid) = &task.project_id {
+        lines.push(format!("X-KAIRO-PROJECT-ID:{}", escape_text(pid)));
+    }
+}
+
+fn format_utc(datetime: &NaiveDateTime) -> String {
+    datetime.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// RFC 5545 3.3.11: バックスラッシュ・セミコロン・カンマ・改行をエスケープ
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// RFC 5545 3.1: 75オクテットを超える行は CRLF + 空白で折り返す
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if width + len > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(ch);
+        width += len;
+    }
+    folded
+}
+
+// ==============================
+// ▼ Import
+// ==============================
+pub fn parse_ical(content: &str) -> Result<Vec<IcalTodo>, String> {
+    let mut todos = Vec::new();
+    let mut current: Option<IcalTodo> = None;
+    // VTODO / VEVENT の中にある VALARM などのサブコンポーネントの深さ。
+    // その中のプロパティ（VALARM の DESCRIPTION など）はタスクに反映しない
+    let mut nested_depth = 0usize;
+
+    for (index, line) in unfold_lines(content).iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (name_and_params, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid content line {}: {}", index + 1, line))?;
+        let mut parts = name_and_params.split(';');
+        let name = parts.next().unwrap_or("").to_ascii_uppercase();
+
+        match (name.as_str(), value.to_ascii_uppercase().as_str()) {
+            ("BEGIN", _) if current.is_some() => nested_depth += 1,
+            ("END", _) if nested_depth > 0 => nested_depth -= 1,
+            ("BEGIN", "VTODO") => current = Some(empty_todo(IcalComponent::Todo)),
+            ("BEGIN", "VEVENT") => current = Some(empty_todo(IcalComponent::Event)),
+            ("END", "VTODO") | ("END", "VEVENT") => {
+                if let Some(todo) = current.take() {
+                    todos.push(todo);
+                }
+            }
+            _ => {
+                if nested_depth > 0 {
+                    continue;
+                }
+                if let Some(todo) = current.as_mut() {
+                    apply_property(todo, &name, value)
+                        .map_err(|e| format!("Line {}: {}", index + 1, e))?;
+                }
+            }
+        }
+    }
+
+    Ok(todos)
+}
+
+fn empty_todo(component: IcalComponent) -> IcalTodo {
+    IcalTodo {
+        component,
+        uid: None,
+        summary: None,
+        description: None,
+        priority: None,
+        due: None,
+        categories: Vec::new(),
+        status: None,
+        project_id: None,
+    }
+}
+
+fn apply_property(todo: &mut IcalTodo, name: &str, value: &str) -> Result<(), String> {
+    match name {
+        "UID" => todo.uid = Some(value.to_string()),
+        "SUMMARY" => todo.summary = Some(unescape_text(value)),
+        "DESCRIPTION" => todo.description = Some(unescape_text(value)),
+        "PRIORITY" => {
+            let number = value
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| format!("Invalid PRIORITY: {}", value))?;
+            // RFC 5545 3.8.1.9: 1-4 高, 5 中, 6-9 低, 0 未定義
+            todo.priority = match number {
+                1..=4 => Some(TaskPriority::High),
+                5 => Some(TaskPriority::Medium),
+                6..=9 => Some(TaskPriority::Low),
+                _ => None,
+            };
+        }
+        "DUE" => todo.due = Some(parse_date_value(value)?),
+        "DTSTART" if todo.component == IcalComponent::Event => {
+            todo.due = Some(parse_date_value(value)?)
+        }
+        "CATEGORIES" => todo.categories.extend(
+            split_unescaped_commas(value)
+                .into_iter()
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty()),
+        ),
+        "STATUS" => {
+            todo.status = match value.trim().to_ascii_uppercase().as_str() {
+                "NEEDS-ACTION" | "CONFIRMED" | "TENTATIVE" => Some(IcalStatus::NeedsAction),
+                "IN-PROCESS" => Some(IcalStatus::InProcess),
+                "COMPLETED" => Some(IcalStatus::Completed),
+                "CANCELLED" => Some(IcalStatus::Cancelled),
+                other => return Err(format!("Invalid STATUS: {}", other)),
+            }
+        }
+        "X-KAIRO-PROJECT-ID" => todo.project_id = Some(unescape_text(value)),
+        _ => {
+            // 未対応のプロパティは無視する
+        }
+    }
+    Ok(())
+}
+
+// "20250101", "20250101T090000", "20250101T090000Z" のいずれも日付部分のみ使う
+fn parse_date_value(value: &str) -> Result<NaiveDate, String> {
+    let date_part = value.trim().get(0..8).unwrap_or(value);
+    NaiveDate::parse_from_str(date_part, "%Y%m%d").map_err(|_| format!("Invalid date: {}", value))
+}
+
+// RFC 5545 3.1: 空白またはタブで始まる行は前の行の続き
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(rest) = line.strip_prefix([' ', '\t'])
+            && let Some(last) = lines.last_mut()
+        {
+            last.push_str(rest);
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+    lines
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut result = String::new();
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => result.push('\n'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+fn split_unescaped_commas(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                current.push(ch);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ',' => items.push(unescape_text(&std::mem::take(&mut current))),
+            _ => current.push(ch),
+        }
+    }
+    items.push(unescape_text(&current));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn sample_task() -> Task {
+        Task {
+            id: "task-001".to_string(),
+            title: "Write report; part 1, draft".to_string(),
+            description: Some("line one\nline two \\ end".to_string()),
+            priority: Some(TaskPriority::High),
+            due_date: Some(datetime("2025-03-31 00:00:00")),
+            created_at: datetime("2025-03-01 09:00:00"),
+            updated_at: datetime("2025-03-02 10:30:00"),
+            archived: false,
+            deleted: false,
+            deleted_at: None,
+            completed_at: None,
+            project_id: Some("p-001".to_string()),
+            file_path: None,
+        }
+    }
+
+    fn parse_one(body: &str) -> IcalTodo {
+        let content = format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\n{}END:VTODO\r\nEND:VCALENDAR\r\n",
+            body
+        );
+        let mut todos = parse_ical(&content).unwrap();
+        assert_eq!(todos.len(), 1);
+        todos.remove(0)
+    }
+
+    #[test]
+    fn unfolds_continuation_lines() {
+        let lines = unfold_lines("SUMMARY:Long\r\n  title\r\n\t continued\r\nUID:x\n");
+        assert_eq!(lines, vec!["SUMMARY:Long title continued", "UID:x", ""]);
+    }
+
+    #[test]
+    fn folds_long_lines_at_75_octets() {
+        let line = format!("SUMMARY:{}", "あ".repeat(40));
+        let folded = fold_line(&line);
+        assert!(folded.split("\r\n").all(|part| part.len() <= 75));
+        assert_eq!(unfold_lines(&folded), vec![line]);
+    }
+
+    #[test]
+    fn escapes_and_unescapes_text() {
+        let text = "a;b,c\\d\ne";
+        let escaped = escape_text(text);
+        assert_eq!(escaped, r"a\;b\,c\\d\ne");
+        assert_eq!(unescape_text(&escaped), text);
+        assert_eq!(escape_text("x\r\ny"), "x\\ny");
+        assert_eq!(unescape_text("A\\NB"), "A\nB");
+    }
+
+    #[test]
+    fn splits_categories_on_unescaped_commas() {
+        let todo = parse_one("SUMMARY:x\r\nCATEGORIES:work,a\\,b, ,home\r\n");
+        assert_eq!(todo.categories, vec!["work", "a,b", "home"]);
+    }
+
+    #[test]
+    fn parses_due_dates_in_any_form() {
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 31);
+        assert_eq!(parse_one("DUE;VALUE=DATE:20250131\r\n").due, expected);
+        assert_eq!(parse_one("DUE:20250131T090000\r\n").due, expected);
+        assert_eq!(parse_one("DUE:20250131T090000Z\r\n").due, expected);
+        assert!(parse_ical("BEGIN:VTODO\r\nDUE:2025-01-31\r\nEND:VTODO\r\n").is_err());
+    }
+
+    #[test]
+    fn maps_status_to_imported_status() {
+        let status = |value: &str| {
+            parse_one(&format!("SUMMARY:x\r\nSTATUS:{}\r\n", value))
+                .to_imported()
+                .unwrap()
+                .status
+        };
+        assert_eq!(status("NEEDS-ACTION"), Some(ImportedStatus::Pending));
+        assert_eq!(status("in-process"), Some(ImportedStatus::Pending));
+        assert_eq!(status("COMPLETED"), Some(ImportedStatus::Completed));
+        assert_eq!(status("CANCELLED"), Some(ImportedStatus::Deleted));
+        assert!(parse_ical("BEGIN:VTODO\r\nSTATUS:DONE\r\nEND:VTODO\r\n").is_err());
+    }
+
+    #[test]
+    fn maps_priority_ranges() {
+        let priority = |value: u8| parse_one(&format!("PRIORITY:{}\r\n", value)).priority;
+        assert_eq!(priority(0), None);
+        assert_eq!(priority(1), Some(TaskPriority::High));
+        assert_eq!(priority(4), Some(TaskPriority::High));
+        assert_eq!(priority(5), Some(TaskPriority::Medium));
+        assert_eq!(priority(9), Some(TaskPriority::Low));
+    }
+
+    #[test]
+    fn maps_kairo_uids_to_task_ids() {
+        let imported = parse_one("UID:task-007@kairo\r\nSUMMARY:x\r\n")
+            .to_imported()
+            .unwrap();
+        assert_eq!(imported.id.as_deref(), Some("task-007"));
+        assert!(imported.external_id.is_none());
+    }
+
+    #[test]
+    fn keeps_foreign_uids_as_external_ids() {
+        let imported = parse_one("UID:abc-123@example.com\r\nSUMMARY:x\r\n")
+            .to_imported()
+            .unwrap();
+        assert_eq!(imported.id, None);
+        let external = imported.external_id.unwrap();
+        assert_eq!(external.source, "ical");
+        assert_eq!(external.id, "abc-123@example.com");
+    }
+
+    #[test]
+    fn skips_entries_without_summary() {
+        assert!(parse_one("UID:x\r\n").to_imported().is_none());
+    }
+
+    #[test]
+    fn ignores_properties_of_nested_components() {
+        let todo = parse_one(
+            "SUMMARY:x\r\nBEGIN:VALARM\r\nDESCRIPTION:Reminder\r\nEND:VALARM\r\nDESCRIPTION:body\r\n",
+        );
+        assert_eq!(todo.description.as_deref(), Some("body"));
+    }
+
+    #[test]
+    fn round_trips_vtodo() {
+        let task = sample_task();
+        let tags = vec!["work".to_string(), "a,b".to_string()];
+        let output = tasks_to_ical(&[(task.clone(), tags.clone())], IcalComponent::Todo);
+
+        let todos = parse_ical(&output).unwrap();
+        assert_eq!(todos.len(), 1);
+        let imported = todos[0].to_imported().unwrap();
+        assert_eq!(imported.id.as_deref(), Some("task-001"));
+        assert_eq!(imported.title, task.title);
+        assert_eq!(imported.description, task.description);
+        assert_eq!(imported.priority, task.priority);
+        assert_eq!(imported.due, task.due_date.map(|d| d.date()));
+        assert_eq!(imported.tags, tags);
+        assert_eq!(imported.project_id, task.project_id);
+        assert_eq!(imported.status, Some(ImportedStatus::Pending));
+    }
+
+    #[test]
+    fn round_trips_completed_and_cancelled_status() {
+        let mut completed = sample_task();
+        completed.archived = true;
+        let mut cancelled = sample_task();
+        cancelled.id = "task-002".to_string();
+        cancelled.deleted = true;
+
+        let output = tasks_to_ical(
+            &[(completed, Vec::new()), (cancelled, Vec::new())],
+            IcalComponent::Todo,
+        );
+        let statuses: Vec<Option<ImportedStatus>> = parse_ical(&output)
+            .unwrap()
+            .iter()
+            .map(|todo| todo.to_imported().unwrap().status)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                Some(ImportedStatus::Completed),
+                Some(ImportedStatus::Deleted)
+            ]
+        );
+    }
+
+    #[test]
+    fn exports_only_tasks_with_due_dates_as_events() {
+        let mut undated = sample_task();
+        undated.id = "task-002".to_string();
+        undated.due_date = None;
+
+        let output = tasks_to_ical(
+            &[(sample_task(), Vec::new()), (undated, Vec::new())],
+            IcalComponent::Event,
+        );
+        assert!(output.contains("DTSTART;VALUE=DATE:20250331\r\n"));
+        assert!(output.contains("DTEND;VALUE=DATE:20250401\r\n"));
+
+        let todos = parse_ical(&output).unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].component, IcalComponent::Event);
+        assert_eq!(todos[0].due, NaiveDate::from_ymd_opt(2025, 3, 31));
+    }
+}
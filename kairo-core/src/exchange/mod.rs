@@ -0,0 +1,9 @@
+pub mod common;
+pub mod ical;
+pub mod taskwarrior;
+pub mod todotxt;
+
+pub use common::*;
+pub use ical::*;
+pub use taskwarrior::*;
+pub use todotxt::*;
@@ -0,0 +1,473 @@
+use std::collections::HashMap;
+
+use crate::{
+    exchange::{ExternalId, ImportedStatus, ImportedTask},
+    model::{Project, Task, TaskPriority},
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+pub const EXTERNAL_ID_SOURCE: &str = "taskwarrior";
+pub const ANNOTATIONS_SECTION: &str = "annotations";
+const ANNOTATIONS_HEADING: &str = "### Annotations";
+// Taskwarrior は注釈を日時で識別するため、本文にも秒まで書いておく
+const ANNOTATION_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// `task export` の1タスク分。kairo_id は kairo のタスクIDを保持する UDA。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskwarriorTask {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<TaskwarriorAnnotation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kairo_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskwarriorAnnotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+    pub description: String,
+}
+
+// ==============================
+// ▼ Export
+// ==============================
+
+// `task import` で読み込める JSON 配列を生成する。
+// uuids は Taskwarrior から取り込んだタスクの元の uuid、annotations は本文から読み取った注釈（いずれもタスクIDごと）
+pub fn tasks_to_taskwarrior(
+    tasks: &[(Task, Vec<String>)],
+    projects: &[Project],
+    uuids: &HashMap<String, String>,
+    annotations: &HashMap<String, Vec<TaskwarriorAnnotation>>,
+) -> Result<String, serde_json::Error> {
+    let project_titles: HashMap<&str, &str> = projects
+        .iter()
+        .map(|p| (p.id.as_str(), p.title.as_str()))
+        .collect();
+
+    let exported: Vec<TaskwarriorTask> = tasks
+        .iter()
+        .map(|(task, tags)| {
+            let status = if task.deleted {
+                "deleted"
+            } else if task.archived {
+                "completed"
+            } else {
+                "pending"
+            };
+
+            TaskwarriorTask {
+                uuid: Some(
+                    uuids
+                        .get(&task.id)
+                        .cloned()
+                        .unwrap_or_else(|| uuid_from_id(&task.id)),
+                ),
+                description: task.title.clone(),
+                status: Some(status.to_string()),
+                entry: Some(task.created_at.format(DATE_FORMAT).to_string()),
+                modified: Some(task.updated_at.format(DATE_FORMAT).to_string()),
+                end: if task.deleted {
+                    Some(task.deleted_at.unwrap_or(task.updated_at))
+                } else if task.archived {
+                    Some(task.completed_at.unwrap_or(task.updated_at))
+                } else {
+                    None
+                }
+                .map(|end| end.format(DATE_FORMAT).to_string()),
+                due: task.due_date.map(|d| d.format(DATE_FORMAT).to_string()),
+                project: task
+                    .project_id
+                    .as_deref()
+                    .map(|pid| project_titles.get(pid).copied().unwrap_or(pid).to_string()),
+                tags: tags.clone(),
+                priority: task.priority.map(|p| {
+                    match p {
+                        TaskPriority::High => "H",
+                        TaskPriority::Medium => "M",
+                        TaskPriority::Low => "L",
+                    }
+                    .to_string()
+                }),
+                // Taskwarrior は注釈を日時で識別するため、日付のない注釈はタスクの作成日時にする
+                annotations: annotations
+                    .get(&task.id)
+                    .into_iter()
+                    .flatten()
+                    .map(|annotation| TaskwarriorAnnotation {
+                        entry: annotation
+                            .entry
+                            .clone()
+                            .or_else(|| Some(task.created_at.format(DATE_FORMAT).to_string())),
+                        description: annotation.description.clone(),
+                    })
+                    .collect(),
+                kairo_id: Some(task.id.clone()),
+            }
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&exported)
+}
+
+// Taskwarrior は UUID でタスクを照合するため、タスクIDから毎回同じ UUID を導出する
+fn uuid_from_id(task_id: &str) -> String {
+    let high = fnv1a(task_id.as_bytes(), 0xcbf2_9ce4_8422_2325);
+    let low = fnv1a(task_id.as_bytes(), 0x6c62_272e_07bb_0142);
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&high.to_be_bytes());
+    bytes[8..].copy_from_slice(&low.to_be_bytes());
+
+    // RFC 9562 の version 8（独自形式）と variant を設定
+    bytes[6] = (bytes[6] & 0x0f) | 0x80;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn fnv1a(bytes: &[u8], offset_basis: u64) -> u64 {
+    bytes.iter().fold(offset_basis, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// ==============================
+// ▼ Import
+// ==============================
+
+// JSON 配列形式と、1行1タスクの形式（古い `task export`）の両方を受け付ける
+pub fn parse_taskwarrior(content: &str) -> Result<Vec<ImportedTask>, String> {
+    let tasks: Vec<TaskwarriorTask> = if content.trim_start().starts_with('[') {
+        serde_json::from_str(content).map_err(|e| e.to_string())?
+    } else {
+        let mut tasks = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim().trim_end_matches(',');
+            if line.is_empty() {
+                continue;
+            }
+            let task =
+                serde_json::from_str(line).map_err(|e| format!("Line {}: {}", index + 1, e))?;
+            tasks.push(task);
+        }
+        tasks
+    };
+
+    tasks.iter().map(to_imported).collect()
+}
+
+fn to_imported(task: &TaskwarriorTask) -> Result<ImportedTask, String> {
+    let mut imported = ImportedTask::new(task.description.clone());
+    imported.id = task.kairo_id.clone();
+    // Taskwarrior で作られたタスクは uuid で照合する
+    if imported.id.is_none() {
+        imported.external_id = task.uuid.clone().map(|uuid| ExternalId {
+            source: EXTERNAL_ID_SOURCE,
+            id: uuid,
+        });
+    }
+    imported.project_name = task.project.clone();
+    imported.tags = task.tags.clone();
+
+    imported.priority = match task.priority.as_deref() {
+        Some("H") => Some(TaskPriority::High),
+        Some("M") => Some(TaskPriority::Medium),
+        Some("L") => Some(TaskPriority::Low),
+        Some("") | None => None,
+        Some(other) => return Err(format!("Invalid priority: {}", other)),
+    };
+
+    imported.due = match task.due.as_deref() {
+        Some(due) => Some(parse_date(due)?),
+        None => None,
+    };
+
+    imported.status = match task.status.as_deref() {
+        Some("pending") | Some("waiting") | Some("recurring") => Some(ImportedStatus::Pending),
+        Some("completed") => Some(ImportedStatus::Completed),
+        Some("deleted") => Some(ImportedStatus::Deleted),
+        None => None,
+        Some(other) => return Err(format!("Invalid status: {}", other)),
+    };
+
+    // 注釈は Markdown 本文に箇条書きで残す
+    if !task.annotations.is_empty() {
+        imported.body = Some(annotations_to_markdown(&task.annotations));
+    }
+
+    Ok(imported)
+}
+
+// 注釈を "- 2025-01-01 09:30:00: 内容" の箇条書きにする
+fn annotations_to_markdown(annotations: &[TaskwarriorAnnotation]) -> String {
+    let mut lines = vec![ANNOTATIONS_HEADING.to_string(), String::new()];
+    for annotation in annotations {
+        let date = annotation
+            .entry
+            .as_deref()
+            .and_then(|e| NaiveDateTime::parse_from_str(e, DATE_FORMAT).ok())
+            .map(|dt| format!("{}: ", dt.format(ANNOTATION_DATE_FORMAT)))
+            .unwrap_or_default();
+        lines.push(format!("- {}{}", date, annotation.description));
+    }
+    lines.join("\n")
+}
+
+// annotations_to_markdown で書いた箇条書きを注釈に戻す。
+// 以前の形式（日付のみ）は時刻を 00:00:00 として扱う
+pub fn markdown_to_annotations(section: &str) -> Vec<TaskwarriorAnnotation> {
+    section
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("- "))
+        .map(|item| {
+            let dated = item.split_once(": ").and_then(|(date, description)| {
+                NaiveDateTime::parse_from_str(date, ANNOTATION_DATE_FORMAT)
+                    .ok()
+                    .or_else(|| {
+                        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                            .ok()
+                            .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    })
+                    .map(|dt| (dt, description))
+            });
+            match dated {
+                Some((datetime, description)) => TaskwarriorAnnotation {
+                    entry: Some(datetime.format(DATE_FORMAT).to_string()),
+                    description: description.to_string(),
+                },
+                None => TaskwarriorAnnotation {
+                    entry: None,
+                    description: item.to_string(),
+                },
+            }
+        })
+        .collect()
+}
+
+// "20250101T000000Z" 形式（ISO 8601 の拡張形式も許容）
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDateTime::parse_from_str(value, DATE_FORMAT)
+        .map(|dt| dt.date())
+        .or_else(|_| NaiveDate::parse_from_str(value.get(0..10).unwrap_or(value), "%Y-%m-%d"))
+        .map_err(|_| format!("Invalid date: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn sample_task() -> Task {
+        Task {
+            id: "task-001".to_string(),
+            title: "Review PR".to_string(),
+            description: None,
+            priority: Some(TaskPriority::Medium),
+            due_date: Some(datetime("2025-03-31 00:00:00")),
+            created_at: datetime("2025-03-01 09:00:00"),
+            updated_at: datetime("2025-03-02 10:30:00"),
+            archived: false,
+            deleted: false,
+            deleted_at: None,
+            completed_at: None,
+            project_id: None,
+            file_path: None,
+        }
+    }
+
+    fn export(task: Task) -> TaskwarriorTask {
+        let json =
+            tasks_to_taskwarrior(&[(task, Vec::new())], &[], &HashMap::new(), &HashMap::new())
+                .unwrap();
+        let mut exported: Vec<TaskwarriorTask> = serde_json::from_str(&json).unwrap();
+        exported.remove(0)
+    }
+
+    fn parse_one(json: &str) -> ImportedTask {
+        let mut tasks = parse_taskwarrior(json).unwrap();
+        assert_eq!(tasks.len(), 1);
+        tasks.remove(0)
+    }
+
+    #[test]
+    fn maps_status() {
+        let status = |value: &str| {
+            parse_one(&format!(r#"{{"description":"x","status":"{}"}}"#, value)).status
+        };
+        assert_eq!(status("pending"), Some(ImportedStatus::Pending));
+        assert_eq!(status("waiting"), Some(ImportedStatus::Pending));
+        assert_eq!(status("recurring"), Some(ImportedStatus::Pending));
+        assert_eq!(status("completed"), Some(ImportedStatus::Completed));
+        assert_eq!(status("deleted"), Some(ImportedStatus::Deleted));
+        assert_eq!(parse_one(r#"{"description":"x"}"#).status, None);
+        assert!(parse_taskwarrior(r#"{"description":"x","status":"done"}"#).is_err());
+    }
+
+    #[test]
+    fn maps_priority() {
+        let priority = |value: &str| {
+            parse_one(&format!(r#"{{"description":"x","priority":"{}"}}"#, value)).priority
+        };
+        assert_eq!(priority("H"), Some(TaskPriority::High));
+        assert_eq!(priority("M"), Some(TaskPriority::Medium));
+        assert_eq!(priority("L"), Some(TaskPriority::Low));
+        assert_eq!(priority(""), None);
+        assert!(parse_taskwarrior(r#"{"description":"x","priority":"X"}"#).is_err());
+    }
+
+    #[test]
+    fn parses_basic_and_extended_dates() {
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 31);
+        assert_eq!(parse_date("20250131T150000Z").ok(), expected);
+        assert_eq!(parse_date("2025-01-31T15:00:00Z").ok(), expected);
+        assert_eq!(parse_date("2025-01-31").ok(), expected);
+        assert_eq!(
+            parse_date("31/01/2025").unwrap_err(),
+            "Invalid date: 31/01/2025"
+        );
+    }
+
+    #[test]
+    fn accepts_json_array() {
+        let tasks = parse_taskwarrior(
+            r#"[
+  {"description":"one","uuid":"a"},
+  {"description":"two","uuid":"b"}
+]"#,
+        )
+        .unwrap();
+        let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["one", "two"]);
+    }
+
+    #[test]
+    fn accepts_line_delimited_json() {
+        let tasks =
+            parse_taskwarrior("{\"description\":\"one\"},\n\n{\"description\":\"two\"}\n").unwrap();
+        let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["one", "two"]);
+
+        let error = parse_taskwarrior("{\"description\":\"one\"}\n{broken}\n").unwrap_err();
+        assert!(error.starts_with("Line 2:"));
+    }
+
+    #[test]
+    fn matches_by_kairo_id_before_uuid() {
+        let imported = parse_one(r#"{"description":"x","uuid":"u-1","kairo_id":"task-009"}"#);
+        assert_eq!(imported.id.as_deref(), Some("task-009"));
+        assert!(imported.external_id.is_none());
+
+        let imported = parse_one(r#"{"description":"x","uuid":"u-1"}"#);
+        assert_eq!(imported.id, None);
+        let external = imported.external_id.unwrap();
+        assert_eq!(external.source, EXTERNAL_ID_SOURCE);
+        assert_eq!(external.id, "u-1");
+    }
+
+    #[test]
+    fn keeps_annotations_in_body() {
+        let imported = parse_one(
+            r#"{"description":"x","annotations":[{"entry":"20250102T030405Z","description":"first"},{"description":"second"}]}"#,
+        );
+        assert_eq!(
+            imported.body.as_deref(),
+            Some("### Annotations\n\n- 2025-01-02 03:04:05: first\n- second")
+        );
+
+        let section = "- 2025-01-02 03:04:05: first\n- 2025-01-03: legacy\n- second\n";
+        let annotations = markdown_to_annotations(section);
+        let entries: Vec<Option<&str>> = annotations.iter().map(|a| a.entry.as_deref()).collect();
+        assert_eq!(
+            entries,
+            vec![Some("20250102T030405Z"), Some("20250103T000000Z"), None]
+        );
+        assert_eq!(annotations[2].description, "second");
+    }
+
+    #[test]
+    fn exports_status_and_end_dates() {
+        let pending = export(sample_task());
+        assert_eq!(pending.status.as_deref(), Some("pending"));
+        assert_eq!(pending.end, None);
+        assert_eq!(pending.priority.as_deref(), Some("M"));
+        assert_eq!(pending.due.as_deref(), Some("20250331T000000Z"));
+        assert_eq!(pending.kairo_id.as_deref(), Some("task-001"));
+
+        let mut task = sample_task();
+        task.archived = true;
+        task.completed_at = Some(datetime("2025-03-05 12:00:00"));
+        let completed = export(task);
+        assert_eq!(completed.status.as_deref(), Some("completed"));
+        assert_eq!(completed.end.as_deref(), Some("20250305T120000Z"));
+
+        let mut task = sample_task();
+        task.deleted = true;
+        task.deleted_at = Some(datetime("2025-03-06 08:00:00"));
+        let deleted = export(task);
+        assert_eq!(deleted.status.as_deref(), Some("deleted"));
+        assert_eq!(deleted.end.as_deref(), Some("20250306T080000Z"));
+    }
+
+    #[test]
+    fn derives_stable_uuids_from_task_ids() {
+        let first = uuid_from_id("task-001");
+        assert_eq!(first, uuid_from_id("task-001"));
+        assert_ne!(first, uuid_from_id("task-002"));
+        assert_eq!(first.len(), 36);
+        assert_eq!(&first[14..15], "8");
+    }
+
+    #[test]
+    fn round_trips_through_parse() {
+        let mut task = sample_task();
+        task.archived = true;
+        let json = tasks_to_taskwarrior(
+            &[(task, vec!["review".to_string()])],
+            &[],
+            &HashMap::from([("task-001".to_string(), "orig-uuid".to_string())]),
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert!(json.contains("\"uuid\": \"orig-uuid\""));
+
+        let imported = parse_one(&json);
+        assert_eq!(imported.id.as_deref(), Some("task-001"));
+        assert_eq!(imported.title, "Review PR");
+        assert_eq!(imported.priority, Some(TaskPriority::Medium));
+        assert_eq!(imported.due, NaiveDate::from_ymd_opt(2025, 3, 31));
+        assert_eq!(imported.tags, vec!["review"]);
+        assert_eq!(imported.status, Some(ImportedStatus::Completed));
+    }
+}
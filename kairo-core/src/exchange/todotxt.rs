@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+
+use crate::{
+    exchange::{ImportedStatus, ImportedTask, slug_token},
+    model::{Project, Task, TaskPriority},
+};
+use chrono::NaiveDate;
+
+// ==============================
+// ▼ Export
+// ==============================
+
+// 1タスク1行の todo.txt 形式に変換する。
+// kairo のタスクIDは id: キーとして出力し、取り込み時に既存タスクと照合する。
+pub fn tasks_to_todotxt(tasks: &[(Task, Vec<String>)], projects: &[Project]) -> String {
+    let project_titles: HashMap<&str, &str> = projects
+        .iter()
+        .map(|p| (p.id.as_str(), p.title.as_str()))
+        .collect();
+
+    let mut output = String::new();
+    for (task, tags) in tasks {
+        let mut parts: Vec<String> = Vec::new();
+
+        let priority = task.priority.map(|p| match p {
+            TaskPriority::High => 'A',
+            TaskPriority::Medium => 'B',
+            TaskPriority::Low => 'C',
+        });
+
+        // 完了タスクは "x 完了日 作成日"、優先度は pri: キーで保持する
+        if task.archived {
+            parts.push("x".to_string());
+            parts.push(task.updated_at.format("%Y-%m-%d").to_string());
+        } else if let Some(p) = priority {
+            parts.push(format!("({})", p));
+        }
+        parts.push(task.created_at.format("%Y-%m-%d").to_string());
+
+        parts.push(task.title.clone());
+
+        if let Some(pid) = task.project_id.as_deref() {
+            let name = project_titles.get(pid).copied().unwrap_or(pid);
+            parts.push(format!("+{}", slug_token(name)));
+        }
+        for tag in tags {
+            parts.push(format!("@{}", slug_token(tag)));
+        }
+        if let Some(due) = task.due_date {
+            parts.push(format!("due:{}", due.format("%Y-%m-%d")));
+        }
+        if task.archived
+            && let Some(p) = priority
+        {
+            parts.push(format!("pri:{}", p));
+        }
+        parts.push(format!("id:{}", task.id));
+
+        output.push_str(&parts.join(" "));
+        output.push('\n');
+    }
+    output
+}
+
+// ==============================
+// ▼ Import
+// ==============================
+pub fn parse_todotxt(content: &str) -> Result<Vec<ImportedTask>, String> {
+    let mut tasks = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let task = parse_line(line).map_err(|e| format!("Line {}: {}", index + 1, e))?;
+        tasks.push(task);
+    }
+
+    Ok(tasks)
+}
+
+fn parse_line(line: &str) -> Result<ImportedTask, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let mut index = 0;
+
+    let completed = tokens.first() == Some(&"x");
+    let mut priority = None;
+
+    if completed {
+        index += 1;
+        // 完了日・作成日（いずれも省略可）
+        for _ in 0..2 {
+            if tokens.get(index).is_some_and(|t| parse_date(t).is_ok()) {
+                index += 1;
+            }
+        }
+    } else {
+        if let Some(p) = tokens.get(index).and_then(|t| parse_priority_marker(t)) {
+            priority = Some(p);
+            index += 1;
+        }
+        // 作成日（省略可）
+        if tokens.get(index).is_some_and(|t| parse_date(t).is_ok()) {
+            index += 1;
+        }
+    }
+
+    let mut words: Vec<&str> = Vec::new();
+    let mut item = ImportedTask::new(String::new());
+
+    for token in &tokens[index..] {
+        if let Some(project) = token.strip_prefix('+').filter(|p| !p.is_empty()) {
+            if item.project_name.is_none() {
+                item.project_name = Some(project.to_string());
+            }
+        } else if let Some(context) = token.strip_prefix('@').filter(|c| !c.is_empty()) {
+            item.tags.push(context.to_string());
+        } else if let Some((key, value)) = split_key_value(token) {
+            match key {
+                "id" => item.id = Some(value.to_string()),
+                "due" => item.due = Some(parse_date(value)?),
+                "pri" => priority = parse_priority_letter(value),
+                _ => words.push(token),
+            }
+        } else {
+            words.push(token);
+        }
+    }
+
+    if words.is_empty() {
+        return Err("Missing task description".to_string());
+    }
+
+    item.title = words.join(" ");
+    item.priority = priority;
+    item.status = Some(if completed {
+        ImportedStatus::Completed
+    } else {
+        ImportedStatus::Pending
+    });
+    Ok(item)
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| format!("Invalid date: {}", value))
+}
+
+fn parse_priority_marker(token: &str) -> Option<TaskPriority> {
+    token
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .and_then(parse_priority_letter)
+}
+
+// (A) を高、(B) を中、(C) 以降を低として扱う
+fn parse_priority_letter(letter: &str) -> Option<TaskPriority> {
+    match letter {
+        "A" => Some(TaskPriority::High),
+        "B" => Some(TaskPriority::Medium),
+        l if l.len() == 1 && l.chars().all(|c| c.is_ascii_uppercase()) => Some(TaskPriority::Low),
+        _ => None,
+    }
+}
+
+// "key:value" 形式のトークンを分割する（URL などは対象外）
+fn split_key_value(token: &str) -> Option<(&str, &str)> {
+    let (key, value) = token.split_once(':')?;
+    let valid_key = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid_key || value.is_empty() || value.contains(':') || value.starts_with("//") {
+        return None;
+    }
+    Some((key, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn datetime(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn sample_task() -> Task {
+        Task {
+            id: "task-001".to_string(),
+            title: "Call Mom".to_string(),
+            description: None,
+            priority: Some(TaskPriority::High),
+            due_date: Some(datetime("2025-03-31 00:00:00")),
+            created_at: datetime("2025-03-01 09:00:00"),
+            updated_at: datetime("2025-03-02 10:30:00"),
+            archived: false,
+            deleted: false,
+            deleted_at: None,
+            completed_at: None,
+            project_id: Some("p-001".to_string()),
+            file_path: None,
+        }
+    }
+
+    fn sample_project() -> Project {
+        Project {
+            id: "p-001".to_string(),
+            title: "Family Matters".to_string(),
+            description: None,
+            created_at: datetime("2025-01-01 00:00:00"),
+            updated_at: datetime("2025-01-01 00:00:00"),
+            archived: false,
+            deleted: false,
+            deleted_at: None,
+            status: Default::default(),
+            start_date: None,
+            target_date: None,
+            parent_id: None,
+            area_id: None,
+            file_path: None,
+        }
+    }
+
+    fn parse_one(line: &str) -> ImportedTask {
+        let mut tasks = parse_todotxt(line).unwrap();
+        assert_eq!(tasks.len(), 1);
+        tasks.remove(0)
+    }
+
+    #[test]
+    fn maps_priority_letters() {
+        assert_eq!(parse_one("(A) x").priority, Some(TaskPriority::High));
+        assert_eq!(parse_one("(B) x").priority, Some(TaskPriority::Medium));
+        assert_eq!(parse_one("(C) x").priority, Some(TaskPriority::Low));
+        assert_eq!(parse_one("(Z) x").priority, Some(TaskPriority::Low));
+        assert_eq!(parse_one("x (A) later").priority, None);
+        assert_eq!(parse_one("plain").priority, None);
+    }
+
+    #[test]
+    fn treats_invalid_priority_markers_as_title() {
+        let task = parse_one("(a) lower case");
+        assert_eq!(task.priority, None);
+        assert_eq!(task.title, "(a) lower case");
+    }
+
+    #[test]
+    fn parses_completed_tasks_with_dates() {
+        let task = parse_one("x 2025-03-05 2025-03-01 Call Mom pri:A");
+        assert_eq!(task.status, Some(ImportedStatus::Completed));
+        assert_eq!(task.title, "Call Mom");
+        assert_eq!(task.priority, Some(TaskPriority::High));
+
+        let task = parse_one("x Call Mom");
+        assert_eq!(task.status, Some(ImportedStatus::Completed));
+        assert_eq!(task.title, "Call Mom");
+    }
+
+    #[test]
+    fn does_not_treat_leading_x_word_as_completion() {
+        let task = parse_one("xylophone lessons");
+        assert_eq!(task.status, Some(ImportedStatus::Pending));
+        assert_eq!(task.title, "xylophone lessons");
+    }
+
+    #[test]
+    fn parses_projects_contexts_and_keys() {
+        let task = parse_one(
+            "(B) 2025-03-01 Call Mom +Family +Other @phone @home due:2025-03-31 id:task-007 see:http://example.com",
+        );
+        assert_eq!(task.title, "Call Mom see:http://example.com");
+        assert_eq!(task.project_name.as_deref(), Some("Family"));
+        assert_eq!(task.tags, vec!["phone", "home"]);
+        assert_eq!(task.due, NaiveDate::from_ymd_opt(2025, 3, 31));
+        assert_eq!(task.id.as_deref(), Some("task-007"));
+        assert_eq!(task.status, Some(ImportedStatus::Pending));
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        let error = parse_todotxt("ok\n\n+project @context").unwrap_err();
+        assert_eq!(error, "Line 3: Missing task description");
+
+        let error = parse_todotxt("Call due:2025-02-30").unwrap_err();
+        assert_eq!(error, "Line 1: Invalid date: 2025-02-30");
+    }
+
+    #[test]
+    fn serializes_open_tasks() {
+        let tags = vec!["phone".to_string(), "two words".to_string()];
+        let output = tasks_to_todotxt(&[(sample_task(), tags)], &[sample_project()]);
+        assert_eq!(
+            output,
+            "(A) 2025-03-01 Call Mom +Family-Matters @phone @two-words due:2025-03-31 id:task-001\n"
+        );
+    }
+
+    #[test]
+    fn serializes_completed_tasks_with_pri_key() {
+        let mut task = sample_task();
+        task.archived = true;
+        task.project_id = None;
+        let output = tasks_to_todotxt(&[(task, Vec::new())], &[]);
+        assert_eq!(
+            output,
+            "x 2025-03-02 2025-03-01 Call Mom due:2025-03-31 pri:A id:task-001\n"
+        );
+    }
+
+    #[test]
+    fn round_trips_through_parse() {
+        let mut completed = sample_task();
+        completed.id = "task-002".to_string();
+        completed.archived = true;
+        completed.priority = Some(TaskPriority::Low);
+
+        let output = tasks_to_todotxt(
+            &[
+                (sample_task(), vec!["phone".to_string()]),
+                (completed, Vec::new()),
+            ],
+            &[sample_project()],
+        );
+        let tasks = parse_todotxt(&output).unwrap();
+
+        assert_eq!(tasks[0].id.as_deref(), Some("task-001"));
+        assert_eq!(tasks[0].title, "Call Mom");
+        assert_eq!(tasks[0].priority, Some(TaskPriority::High));
+        assert_eq!(tasks[0].project_name.as_deref(), Some("Family-Matters"));
+        assert_eq!(tasks[0].tags, vec!["phone"]);
+        assert_eq!(tasks[0].due, NaiveDate::from_ymd_opt(2025, 3, 31));
+        assert_eq!(tasks[0].status, Some(ImportedStatus::Pending));
+
+        assert_eq!(tasks[1].id.as_deref(), Some("task-002"));
+        assert_eq!(tasks[1].priority, Some(TaskPriority::Low));
+        assert_eq!(tasks[1].status, Some(ImportedStatus::Completed));
+    }
+}
@@ -11,6 +11,7 @@ pub trait FrontMatterExportable<U> {
 pub trait HasItem {
     fn id(&self) -> &str;
     fn title(&self) -> &str;
+    fn file_path(&self) -> Option<&str>;
 }
 
 pub trait MarkdownParsable<U> {
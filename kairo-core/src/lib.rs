@@ -1,7 +1,9 @@
 pub mod config;
+pub mod exchange;
 pub mod interface;
 pub mod markdown;
 pub mod model;
+pub mod query;
 pub mod schema;
 pub mod store;
 pub mod usecase;
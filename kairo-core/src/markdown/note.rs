@@ -5,7 +5,8 @@ use crate::{
     interface::{FrontMatterExportable, HasItem, MarkdownExportable, MarkdownParsable},
     model::Note,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Mapping;
 
 #[derive(Debug, Serialize)]
 pub struct NoteContent {
@@ -13,11 +14,25 @@ pub struct NoteContent {
     pub body: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct NoteFrontMatter {
     #[serde(flatten)]
     pub item: Note,
+    #[serde(default)]
     pub tags: Vec<String>,
+    // このノートから出ているリンク。なければ front matter に出力しない
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<NoteLink>,
+    // ユーザーが追加した kairo の知らないキー。書き直すときもそのまま残す
+    #[serde(flatten)]
+    pub extra: Mapping,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NoteLink {
+    pub to: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub link_type: Option<String>,
 }
 
 impl MarkdownExportable<NoteFrontMatter> for NoteContent {
@@ -48,6 +63,10 @@ impl HasItem for Note {
     fn title(&self) -> &str {
         &self.title
     }
+
+    fn file_path(&self) -> Option<&str> {
+        self.file_path.as_deref()
+    }
 }
 
 impl MarkdownParsable<Note> for Note {
@@ -5,7 +5,8 @@ use crate::{
     interface::{FrontMatterExportable, HasItem, MarkdownExportable, MarkdownParsable},
     model::Project,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Mapping;
 
 #[derive(Debug, Serialize)]
 pub struct ProjectContent {
@@ -13,11 +14,15 @@ pub struct ProjectContent {
     pub body: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ProjectFrontMatter {
     #[serde(flatten)]
     pub item: Project,
+    #[serde(default)]
     pub tags: Vec<String>,
+    // ユーザーが追加した kairo の知らないキー。書き直すときもそのまま残す
+    #[serde(flatten)]
+    pub extra: Mapping,
 }
 
 impl MarkdownExportable<ProjectFrontMatter> for ProjectContent {
@@ -48,6 +53,10 @@ impl HasItem for Project {
     fn title(&self) -> &str {
         &self.title
     }
+
+    fn file_path(&self) -> Option<&str> {
+        self.file_path.as_deref()
+    }
 }
 
 impl MarkdownParsable<Project> for Project {
@@ -5,7 +5,8 @@ use crate::{
     interface::{FrontMatterExportable, HasItem, MarkdownExportable, MarkdownParsable},
     model::Task,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Mapping;
 
 #[derive(Debug, Serialize)]
 pub struct TaskContent {
@@ -13,11 +14,15 @@ pub struct TaskContent {
     pub body: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TaskFrontMatter {
     #[serde(flatten)]
     pub item: Task,
+    #[serde(default)]
     pub tags: Vec<String>,
+    // ユーザーが追加した kairo の知らないキー。書き直すときもそのまま残す
+    #[serde(flatten)]
+    pub extra: Mapping,
 }
 
 impl MarkdownExportable<TaskFrontMatter> for TaskContent {
@@ -48,6 +53,10 @@ impl HasItem for Task {
     fn title(&self) -> &str {
         &self.title
     }
+
+    fn file_path(&self) -> Option<&str> {
+        self.file_path.as_deref()
+    }
 }
 
 impl MarkdownParsable<Task> for Task {
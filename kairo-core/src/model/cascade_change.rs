@@ -0,0 +1,85 @@
+use crate::schema::cascade_changes;
+use chrono::NaiveDateTime;
+use diesel::{
+    backend::Backend,
+    deserialize::{FromSql, FromSqlRow},
+    expression::*,
+    prelude::*,
+    serialize::{Output, ToSql},
+    sql_types::Text,
+    sqlite::Sqlite,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow, Serialize, Deserialize)]
+#[diesel(sql_type = Text)]
+pub enum CascadeAction {
+    Archive,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow, Serialize, Deserialize)]
+#[diesel(sql_type = Text)]
+pub enum CascadeEntity {
+    Project,
+    Task,
+    Note,
+}
+
+// `--cascade` でプロジェクトと一緒に状態を変えた子要素の記録。
+// 元に戻すときは、ここに記録されたものだけを戻す。
+#[derive(Queryable, Selectable, Insertable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = cascade_changes)]
+pub struct CascadeChange {
+    pub project_id: String,
+    pub action: CascadeAction,
+    pub entity_type: CascadeEntity,
+    pub entity_id: String,
+    pub created_at: NaiveDateTime,
+}
+
+// --- ToSql<Text, Sqlite> 実装 ---
+impl ToSql<Text, Sqlite> for CascadeAction {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> diesel::serialize::Result {
+        let value = match self {
+            CascadeAction::Archive => "archive",
+            CascadeAction::Delete => "delete",
+        };
+        <str as ToSql<Text, Sqlite>>::to_sql(value, out)
+    }
+}
+
+impl ToSql<Text, Sqlite> for CascadeEntity {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> diesel::serialize::Result {
+        let value = match self {
+            CascadeEntity::Project => "project",
+            CascadeEntity::Task => "task",
+            CascadeEntity::Note => "note",
+        };
+        <str as ToSql<Text, Sqlite>>::to_sql(value, out)
+    }
+}
+
+// --- FromSql<Text, Sqlite> 実装 ---
+impl FromSql<Text, Sqlite> for CascadeAction {
+    fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        let s = <*const str as FromSql<Text, Sqlite>>::from_sql(bytes)?;
+        match unsafe { &*s } {
+            "archive" => Ok(CascadeAction::Archive),
+            "delete" => Ok(CascadeAction::Delete),
+            other => Err(format!("Unrecognized CascadeAction variant: {}", other).into()),
+        }
+    }
+}
+
+impl FromSql<Text, Sqlite> for CascadeEntity {
+    fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        let s = <*const str as FromSql<Text, Sqlite>>::from_sql(bytes)?;
+        match unsafe { &*s } {
+            "project" => Ok(CascadeEntity::Project),
+            "task" => Ok(CascadeEntity::Task),
+            "note" => Ok(CascadeEntity::Note),
+            other => Err(format!("Unrecognized CascadeEntity variant: {}", other).into()),
+        }
+    }
+}
@@ -32,17 +32,22 @@ pub struct LinkedNote {
     pub deleted: bool,
 }
 
-// --- ToSql<Text, Sqlite> 実装 ---
-impl ToSql<Text, Sqlite> for LinkType {
-    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> diesel::serialize::Result {
-        let value = match self {
+impl LinkType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
             LinkType::Structure => "structure",
             LinkType::Reference => "reference",
             LinkType::Support => "support",
             LinkType::Related => "related",
             LinkType::Refute => "refute",
-        };
-        <str as ToSql<Text, Sqlite>>::to_sql(value, out)
+        }
+    }
+}
+
+// --- ToSql<Text, Sqlite> 実装 ---
+impl ToSql<Text, Sqlite> for LinkType {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> diesel::serialize::Result {
+        <str as ToSql<Text, Sqlite>>::to_sql(self.as_str(), out)
     }
 }
 
@@ -42,8 +42,41 @@ pub struct Note {
     pub updated_at: NaiveDateTime,
     pub archived: bool,
     pub deleted: bool,
+    // 論理削除した日時。`kairo trash empty --older-than` で使う
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
     pub project_id: Option<String>,
     pub task_id: Option<String>,
+    // 各ディレクトリからの Markdown ファイルの相対パス。front matter には書かない
+    #[serde(skip)]
+    pub file_path: Option<String>,
+}
+
+// ノート作成時のタイトル以外の指定。note_type / sub_type は "fleeting" や "idea" などの名前で渡す。
+#[derive(Debug, Clone, Default)]
+pub struct NewNoteInput {
+    pub note_type: String,
+    pub sub_type: String,
+    pub project_id: Option<String>,
+    pub task_id: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+// ノート一覧の追加の絞り込み・並び替え・ページング条件。日付は YYYY-MM-DD で、範囲は両端を含む。
+#[derive(Debug, Clone, Default)]
+pub struct NoteFilter {
+    pub note_type: Option<String>,
+    pub sub_type: Option<String>,
+    pub project_id: Option<String>,
+    pub task_id: Option<String>,
+    pub created_from: Option<String>,
+    pub created_to: Option<String>,
+    pub updated_from: Option<String>,
+    pub updated_to: Option<String>,
+    pub title: Option<String>,
+    pub sort: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
 }
 
 // --- ToSql<Text, Sqlite> 実装 ---
@@ -2,9 +2,30 @@ use core::fmt;
 
 use crate::schema::projects;
 use chrono::NaiveDateTime;
-use diesel::prelude::*;
+use diesel::{
+    backend::Backend,
+    deserialize::{FromSql, FromSqlRow},
+    expression::*,
+    prelude::*,
+    serialize::{Output, ToSql},
+    sql_types::Text,
+    sqlite::Sqlite,
+};
 use serde::{Deserialize, Serialize};
 
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, AsExpression, FromSqlRow, Serialize, Deserialize,
+)]
+#[diesel(sql_type = Text)]
+pub enum ProjectStatus {
+    Planned,
+    // マイグレーションの既定値（'active'）に合わせる
+    #[default]
+    Active,
+    OnHold,
+    Completed,
+}
+
 #[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
 #[diesel(table_name = projects)]
 pub struct Project {
@@ -15,6 +36,80 @@ pub struct Project {
     pub updated_at: NaiveDateTime,
     pub archived: bool,
     pub deleted: bool,
+    // 論理削除した日時。`kairo trash empty --older-than` で使う
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
+    // status 以降は後から追加した列。古い Markdown の front matter にはないので既定値で読む
+    #[serde(default)]
+    pub status: ProjectStatus,
+    #[serde(default)]
+    pub start_date: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub target_date: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub area_id: Option<String>,
+    // 各ディレクトリからの Markdown ファイルの相対パス。front matter には書かない
+    #[serde(skip)]
+    pub file_path: Option<String>,
+}
+
+// プロジェクト作成時のタイトル以外の指定。日付は YYYY-MM-DD で、status を省略すると active になる。
+#[derive(Debug, Clone, Default)]
+pub struct NewProjectInput {
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub status: Option<String>,
+    pub start_date: Option<String>,
+    pub target_date: Option<String>,
+    pub parent_id: Option<String>,
+    pub area_id: Option<String>,
+}
+
+// プロジェクト更新時の指定。None の項目は変更しない。parent_id / area_id に "none" を指定すると紐付けを外す。
+#[derive(Debug, Clone, Default)]
+pub struct UpdateProjectInput {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub status: Option<String>,
+    pub start_date: Option<String>,
+    pub target_date: Option<String>,
+    pub parent_id: Option<String>,
+    pub area_id: Option<String>,
+}
+
+impl ProjectStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectStatus::Planned => "planned",
+            ProjectStatus::Active => "active",
+            ProjectStatus::OnHold => "on_hold",
+            ProjectStatus::Completed => "completed",
+        }
+    }
+}
+
+// --- ToSql<Text, Sqlite> 実装 ---
+impl ToSql<Text, Sqlite> for ProjectStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> diesel::serialize::Result {
+        <str as ToSql<Text, Sqlite>>::to_sql(self.as_str(), out)
+    }
+}
+
+// --- FromSql<Text, Sqlite> 実装 ---
+impl FromSql<Text, Sqlite> for ProjectStatus {
+    fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        let s = <*const str as FromSql<Text, Sqlite>>::from_sql(bytes)?;
+        match unsafe { &*s } {
+            "planned" => Ok(ProjectStatus::Planned),
+            "active" => Ok(ProjectStatus::Active),
+            "on_hold" => Ok(ProjectStatus::OnHold),
+            "completed" => Ok(ProjectStatus::Completed),
+            other => Err(format!("Unrecognized ProjectStatus variant: {}", other).into()),
+        }
+    }
 }
 
 impl fmt::Display for Project {
@@ -26,6 +121,19 @@ impl fmt::Display for Project {
         } else {
             writeln!(f, "Description: No description")?;
         }
+        writeln!(f, "Status: {:?}", self.status)?;
+        if let Some(start) = &self.start_date {
+            writeln!(f, "StartDate: {}", start.format("%Y/%m/%d"))?;
+        }
+        if let Some(target) = &self.target_date {
+            writeln!(f, "TargetDate: {}", target.format("%Y/%m/%d"))?;
+        }
+        if let Some(parent) = &self.parent_id {
+            writeln!(f, "Parent: {}", parent)?;
+        }
+        if let Some(area) = &self.area_id {
+            writeln!(f, "Area: {}", area)?;
+        }
         writeln!(
             f,
             "Created: {}",
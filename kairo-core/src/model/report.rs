@@ -0,0 +1,156 @@
+use crate::model::{Area, LinkedNote, Note, Project, ProjectStatus, Task};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum TimeReportGroup {
+    Task,
+    Project,
+    Tag,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeReportRow {
+    pub group: TimeReportGroup,
+    pub key: String,
+    pub name: String,
+    pub minutes: i64,
+}
+
+impl TimeReportRow {
+    pub fn hours(&self) -> f64 {
+        self.minutes as f64 / 60.0
+    }
+}
+
+// プロジェクトの進捗レポート。完了はアーカイブ済みのタスクとして数える。
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectReport {
+    pub project_id: String,
+    pub title: String,
+    pub status: ProjectStatus,
+    pub start_date: Option<NaiveDateTime>,
+    pub target_date: Option<NaiveDateTime>,
+    pub open: usize,
+    pub completed: usize,
+    pub deleted: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub no_priority: usize,
+    pub overdue: Vec<Task>,
+    pub linked_notes: i64,
+}
+
+impl ProjectReport {
+    pub fn percent_complete(&self) -> f64 {
+        let total = self.open + self.completed;
+        if total == 0 {
+            return 0.0;
+        }
+        self.completed as f64 / total as f64 * 100.0
+    }
+
+    // Markdown の生成セクションに書き込む要約
+    pub fn to_markdown(&self, generated_at: NaiveDate) -> String {
+        let mut lines = vec![
+            "## Report".to_string(),
+            String::new(),
+            format!("_Generated on {}_", generated_at.format("%Y-%m-%d")),
+            String::new(),
+            format!("- Status: {:?}", self.status),
+        ];
+        if let Some(start) = self.start_date {
+            lines.push(format!("- Start: {}", start.format("%Y-%m-%d")));
+        }
+        if let Some(target) = self.target_date {
+            lines.push(format!("- Target: {}", target.format("%Y-%m-%d")));
+        }
+        lines.push(format!(
+            "- Progress: {:.0}% ({} / {} tasks completed)",
+            self.percent_complete(),
+            self.completed,
+            self.open + self.completed
+        ));
+        lines.push(format!(
+            "- Tasks: {} open, {} completed, {} deleted",
+            self.open, self.completed, self.deleted
+        ));
+        lines.push(format!(
+            "- Open by priority: {} high, {} medium, {} low, {} none",
+            self.high, self.medium, self.low, self.no_priority
+        ));
+        lines.push(format!("- Linked notes: {}", self.linked_notes));
+
+        if !self.overdue.is_empty() {
+            lines.push(String::new());
+            lines.push("### Overdue".to_string());
+            lines.push(String::new());
+            for task in &self.overdue {
+                let due = task
+                    .due_date
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
+                lines.push(format!("- {} {} (due {})", task.id, task.title, due));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+// プロジェクトツリーの1ノード。件数はサブプロジェクトの分も合算する。
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectTreeNode {
+    pub project: Project,
+    pub open_tasks: i64,
+    pub completed_tasks: i64,
+    pub notes: i64,
+    pub children: Vec<ProjectTreeNode>,
+}
+
+impl ProjectTreeNode {
+    pub fn project_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(|child| child.project_count())
+            .sum::<usize>()
+    }
+}
+
+// エリアごとの集計。area が None のグループはエリア未設定のプロジェクト。
+#[derive(Debug, Clone, Serialize)]
+pub struct AreaRollup {
+    pub area: Option<Area>,
+    pub projects: Vec<ProjectTreeNode>,
+}
+
+impl AreaRollup {
+    pub fn project_count(&self) -> usize {
+        self.projects.iter().map(|p| p.project_count()).sum()
+    }
+
+    pub fn open_tasks(&self) -> i64 {
+        self.projects.iter().map(|p| p.open_tasks).sum()
+    }
+
+    pub fn completed_tasks(&self) -> i64 {
+        self.projects.iter().map(|p| p.completed_tasks).sum()
+    }
+
+    pub fn notes(&self) -> i64 {
+        self.projects.iter().map(|p| p.notes).sum()
+    }
+}
+
+// `project show` で1画面に表示する、プロジェクトと関連要素の一覧
+#[derive(Debug, Clone)]
+pub struct ProjectOverview {
+    pub project: Project,
+    pub tags: Vec<String>,
+    pub sub_projects: Vec<Project>,
+    pub tasks: Vec<Task>,
+    pub notes: Vec<Note>,
+    pub links: Vec<LinkedNote>,
+}
@@ -0,0 +1,12 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+// 名前を付けて保存した検索条件。条件は `kairo query` の文法で保持する
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::saved_views)]
+pub struct SavedView {
+    pub name: String,
+    pub query: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
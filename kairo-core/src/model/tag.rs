@@ -1,6 +1,7 @@
 use crate::schema::tags;
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
+use serde::Serialize;
 use std::fmt;
 
 #[derive(Queryable, Selectable, Debug, Clone)]
@@ -16,7 +17,94 @@ impl fmt::Display for Tag {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "TagID: {}", self.id)?;
         writeln!(f, "TagName: {}", self.tag_name)?;
+        writeln!(f, "Deleted: {}", self.deleted)?;
 
         Ok(())
     }
 }
+
+// 階層タグ（"lang/rust/async"）の1階層分。
+// tag_id が None のノードは、子タグから補われた中間の階層でタグ自体は存在しない。
+#[derive(Debug, Clone, Default)]
+pub struct TagTreeNode {
+    pub segment: String,
+    pub full_name: String,
+    pub tag_id: Option<String>,
+    pub count: i64,
+    pub children: Vec<TagTreeNode>,
+}
+
+impl TagTreeNode {
+    // 子孫タグの使用数を含めた合計
+    pub fn total(&self) -> i64 {
+        self.count + self.children.iter().map(|c| c.total()).sum::<i64>()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaggedEntity {
+    Note,
+    Task,
+    Project,
+}
+
+// タグと、それが付いている要素1件分の対応
+#[derive(Debug, Clone)]
+pub struct TagAssignment {
+    pub tag_id: String,
+    pub entity_type: TaggedEntity,
+    pub entity_id: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CoOccurringTag {
+    pub tag_name: String,
+    pub count: i64,
+}
+
+// タグごとの使用状況。first_used / last_used はタグの付いた要素の作成日時の最小・最大。
+#[derive(Debug, Clone, Serialize)]
+pub struct TagStats {
+    pub tag_id: String,
+    pub tag_name: String,
+    pub notes: i64,
+    pub tasks: i64,
+    pub projects: i64,
+    pub first_used: Option<NaiveDateTime>,
+    pub last_used: Option<NaiveDateTime>,
+    pub co_occurring: Vec<CoOccurringTag>,
+}
+
+impl TagStats {
+    pub fn total(&self) -> i64 {
+        self.notes + self.tasks + self.projects
+    }
+}
+
+// ノート・タスク・プロジェクトのタグ絞り込み条件。各タグは子孫タグも含めて判定する。
+#[derive(Debug, Clone, Default)]
+pub struct TagFilter {
+    pub all: Vec<String>,
+    pub any: Vec<String>,
+    pub not: Vec<String>,
+}
+
+impl TagFilter {
+    // 何も指定されていなければ None（絞り込みなし）
+    pub fn new(
+        all: Option<Vec<String>>,
+        any: Option<Vec<String>>,
+        not: Option<Vec<String>>,
+    ) -> Option<Self> {
+        let filter = TagFilter {
+            all: all.unwrap_or_default(),
+            any: any.unwrap_or_default(),
+            not: not.unwrap_or_default(),
+        };
+        if filter.all.is_empty() && filter.any.is_empty() && filter.not.is_empty() {
+            return None;
+        }
+        Some(filter)
+    }
+}
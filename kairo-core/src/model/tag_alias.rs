@@ -0,0 +1,11 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+// 入力時に正式なタグへ読み替える別名
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::tag_aliases)]
+pub struct TagAlias {
+    pub alias: String,
+    pub tag_id: String,
+    pub created_at: NaiveDateTime,
+}
@@ -33,7 +33,26 @@ pub struct Task {
     pub updated_at: NaiveDateTime,
     pub archived: bool,
     pub deleted: bool,
+    // 論理削除した日時。`kairo trash empty --older-than` で使う
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<NaiveDateTime>,
+    // 完了（アーカイブ）した日時。`kairo daily` で使う
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<NaiveDateTime>,
     pub project_id: Option<String>,
+    // 各ディレクトリからの Markdown ファイルの相対パス。front matter には書かない
+    #[serde(skip)]
+    pub file_path: Option<String>,
+}
+
+// タスク作成時のタイトル以外の指定。期限は YYYY-MM-DD で、priority を省略すると medium になる。
+#[derive(Debug, Clone, Default)]
+pub struct NewTaskInput {
+    pub description: Option<String>,
+    pub priority: Option<String>,
+    pub due_date: Option<String>,
+    pub project_id: Option<String>,
+    pub tags: Option<Vec<String>>,
 }
 
 // Optional: デフォルトを持たせたい場合
@@ -0,0 +1,13 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+// 外部ツールでのタスクの識別子（iCalendar の UID、Taskwarrior の uuid など）。
+// 取り込み直したときに同じタスクを更新するために使う
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::task_external_ids)]
+pub struct TaskExternalId {
+    pub source: String,
+    pub external_id: String,
+    pub task_id: String,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,52 @@
+use core::fmt;
+
+use crate::schema::time_entries;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = time_entries)]
+pub struct TimeEntry {
+    pub id: String,
+    pub task_id: String,
+    pub started_at: NaiveDateTime,
+    pub ended_at: Option<NaiveDateTime>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TimeEntry {
+    // 計測中（ended_at が未設定）の場合は現在時刻までの経過時間を返す
+    pub fn duration_minutes(&self) -> i64 {
+        let end = self.ended_at.unwrap_or_else(|| Utc::now().naive_utc());
+        (end - self.started_at).num_minutes()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.ended_at.is_none()
+    }
+}
+
+impl fmt::Display for TimeEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "ID: {}", self.id)?;
+        writeln!(f, "Task: {}", self.task_id)?;
+        writeln!(
+            f,
+            "Started: {}",
+            self.started_at.format("%Y/%m/%d %H:%M:%S")
+        )?;
+        if let Some(end) = &self.ended_at {
+            writeln!(f, "Ended: {}", end.format("%Y/%m/%d %H:%M:%S"))?;
+        } else {
+            writeln!(f, "Ended: Running")?;
+        }
+        let minutes = self.duration_minutes();
+        writeln!(f, "Duration: {}h{:02}m", minutes / 60, minutes % 60)?;
+        if let Some(note) = &self.note {
+            writeln!(f, "Note: {:?}", note)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+
+use crate::model::{NoteType, ProjectStatus, SubType, TaskPriority};
+use chrono::NaiveDate;
+
+// `kairo query` の検索対象
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryEntity {
+    Note,
+    Task,
+    Project,
+}
+
+// 日付の比較。日付は日単位で比較する（created>2025-01-01 は 1/2 以降）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOp {
+    On,
+    Before,
+    OnOrBefore,
+    After,
+    OnOrAfter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    Is(QueryEntity),
+    NoteType(NoteType),
+    SubType(Option<SubType>),
+    Tag(String),
+    Project(String),
+    Task(String),
+    Area(String),
+    Priority(Option<TaskPriority>),
+    Status(ProjectStatus),
+    Title(String),
+    Created(DateOp, NaiveDate),
+    Updated(DateOp, NaiveDate),
+    Due(DateOp, NaiveDate),
+    Archived(bool),
+    Deleted(bool),
+}
+
+impl Predicate {
+    // この条件を判定できる対象か。判定できない対象にはこの条件を使わない
+    pub fn applies_to(&self, entity: QueryEntity) -> bool {
+        match self {
+            Predicate::Is(_)
+            | Predicate::Tag(_)
+            | Predicate::Area(_)
+            | Predicate::Title(_)
+            | Predicate::Created(..)
+            | Predicate::Updated(..)
+            | Predicate::Archived(_)
+            | Predicate::Deleted(_) => true,
+            Predicate::NoteType(_) | Predicate::SubType(_) | Predicate::Task(_) => {
+                entity == QueryEntity::Note
+            }
+            Predicate::Project(_) => entity != QueryEntity::Project,
+            Predicate::Priority(_) | Predicate::Due(..) => entity == QueryEntity::Task,
+            Predicate::Status(_) => entity == QueryEntity::Project,
+        }
+    }
+}
+
+// 先頭に "-" を付けた条件は negated になる
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub negated: bool,
+    pub predicate: Predicate,
+}
+
+// 条件はすべて AND で結合する
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    pub terms: Vec<Term>,
+}
+
+impl Query {
+    // 検索する対象を決める。is: で指定した対象のうち、
+    // 特定の対象にしかない条件（type:, due: など）があれば、そのいずれかを判定できる対象に絞る。
+    // type:permanent due<today ならノートとタスクを検索し、type: はノートだけ、due: はタスクだけに使う
+    pub fn entities(&self) -> Vec<QueryEntity> {
+        const ALL: [QueryEntity; 3] = [QueryEntity::Note, QueryEntity::Task, QueryEntity::Project];
+
+        let requested: Vec<QueryEntity> = self
+            .terms
+            .iter()
+            .filter_map(|term| match term.predicate {
+                Predicate::Is(entity) if !term.negated => Some(entity),
+                _ => None,
+            })
+            .collect();
+
+        let specific: Vec<&Predicate> = self
+            .terms
+            .iter()
+            .map(|term| &term.predicate)
+            .filter(|predicate| {
+                !matches!(predicate, Predicate::Is(_))
+                    && !ALL.iter().all(|entity| predicate.applies_to(*entity))
+            })
+            .collect();
+
+        ALL.into_iter()
+            .filter(|entity| requested.is_empty() || requested.contains(entity))
+            .filter(|entity| {
+                !self.terms.iter().any(|term| {
+                    matches!(term.predicate, Predicate::Is(excluded) if term.negated && excluded == *entity)
+                })
+            })
+            .filter(|entity| {
+                specific.is_empty()
+                    || specific
+                        .iter()
+                        .any(|predicate| predicate.applies_to(*entity))
+            })
+            .collect()
+    }
+
+    // archived: / deleted: を指定しなければ、有効なものだけを対象にする
+    pub fn has_archived(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|term| matches!(term.predicate, Predicate::Archived(_)))
+    }
+
+    pub fn has_deleted(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|term| matches!(term.predicate, Predicate::Deleted(_)))
+    }
+}
+
+// column は入力文字列の何文字目か（1始まり）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryParseError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for QueryParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (column {})", self.message, self.column)
+    }
+}
+
+impl std::error::Error for QueryParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::parse_query;
+
+    fn entities(input: &str) -> Vec<QueryEntity> {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        parse_query(input, today).unwrap().entities()
+    }
+
+    #[test]
+    fn searches_every_entity_by_default() {
+        assert_eq!(
+            entities("tag:rust borrow"),
+            vec![QueryEntity::Note, QueryEntity::Task, QueryEntity::Project]
+        );
+    }
+
+    #[test]
+    fn limits_entities_with_is() {
+        assert_eq!(
+            entities("is:note is:project"),
+            vec![QueryEntity::Note, QueryEntity::Project]
+        );
+        assert_eq!(
+            entities("-is:task"),
+            vec![QueryEntity::Note, QueryEntity::Project]
+        );
+    }
+
+    #[test]
+    fn narrows_entities_to_those_with_specific_fields() {
+        assert_eq!(entities("type:permanent"), vec![QueryEntity::Note]);
+        assert_eq!(
+            entities("type:permanent due<today"),
+            vec![QueryEntity::Note, QueryEntity::Task]
+        );
+        assert_eq!(
+            entities("project:p-001"),
+            vec![QueryEntity::Note, QueryEntity::Task]
+        );
+    }
+
+    #[test]
+    fn narrows_requested_entities_with_specific_fields() {
+        assert_eq!(
+            entities("is:task type:permanent due<today"),
+            vec![QueryEntity::Task]
+        );
+    }
+
+    #[test]
+    fn detects_explicit_archived_and_deleted() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        let query = parse_query("archived:yes tag:rust", today).unwrap();
+        assert!(query.has_archived());
+        assert!(!query.has_deleted());
+
+        let query = parse_query("-deleted:true", today).unwrap();
+        assert!(!query.has_archived());
+        assert!(query.has_deleted());
+    }
+
+    #[test]
+    fn formats_errors_with_column() {
+        let error = QueryParseError {
+            column: 7,
+            message: "Unknown field 'x'".to_string(),
+        };
+        assert_eq!(error.to_string(), "Unknown field 'x' (column 7)");
+    }
+}
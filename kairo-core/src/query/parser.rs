@@ -0,0 +1,457 @@
+use crate::{
+    model::{NoteType, ProjectStatus, SubType, TaskPriority},
+    query::{DateOp, Predicate, Query, QueryEntity, QueryParseError, Term},
+};
+use chrono::{Duration, NaiveDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+// 空白区切りの1語。column は入力中の開始位置（1始まり）
+struct Token {
+    column: usize,
+    text: String,
+}
+
+// `type:permanent tag:rust -tag:draft created>=2025-01-01 due<today "free text"` を解析する。
+// today は相対日付（today / yesterday / tomorrow）の基準日。
+pub fn parse_query(input: &str, today: NaiveDate) -> Result<Query, QueryParseError> {
+    let mut terms = Vec::new();
+    for token in tokenize(input)? {
+        terms.push(parse_term(&token, today)?);
+    }
+    Ok(Query { terms })
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, QueryParseError> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Token> = None;
+    let mut quote_start: Option<usize> = None;
+
+    for (index, c) in input.chars().enumerate() {
+        let column = index + 1;
+        if c.is_whitespace() && quote_start.is_none() {
+            if let Some(token) = current.take() {
+                tokens.push(token);
+            }
+            continue;
+        }
+        if c == '"' {
+            quote_start = match quote_start {
+                Some(_) => None,
+                None => Some(column),
+            };
+        }
+        current
+            .get_or_insert_with(|| Token {
+                column,
+                text: String::new(),
+            })
+            .text
+            .push(c);
+    }
+
+    if let Some(column) = quote_start {
+        return Err(QueryParseError {
+            column,
+            message: "Unterminated quote".to_string(),
+        });
+    }
+    if let Some(token) = current {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn parse_term(token: &Token, today: NaiveDate) -> Result<Term, QueryParseError> {
+    let (negated, body, body_column) = match token.text.strip_prefix('-') {
+        Some(rest) => (true, rest, token.column + 1),
+        None => (false, token.text.as_str(), token.column),
+    };
+    if body.is_empty() {
+        return Err(QueryParseError {
+            column: token.column,
+            message: "Expected a condition after '-'".to_string(),
+        });
+    }
+
+    // 演算子のない語はタイトルの部分一致として扱う
+    let Some((field, op, value, value_column)) = split_condition(body, body_column) else {
+        return Ok(Term {
+            negated,
+            predicate: Predicate::Title(unquote(body)),
+        });
+    };
+
+    let error = |column: usize, message: String| QueryParseError { column, message };
+    let value = unquote(value);
+    if value.is_empty() {
+        return Err(error(
+            value_column,
+            format!("Missing value for '{}'", field),
+        ));
+    }
+
+    let field_name = field.to_lowercase();
+    let is_date_field = matches!(field_name.as_str(), "created" | "updated" | "due");
+    if !is_date_field && !matches!(op, Op::Eq | Op::Ne) {
+        return Err(error(
+            body_column,
+            format!("Comparison operators are not supported for '{}'", field),
+        ));
+    }
+    // "!=" は否定と同じ
+    let negated = negated ^ (op == Op::Ne);
+
+    let predicate = match field_name.as_str() {
+        "is" => Predicate::Is(match value.to_lowercase().as_str() {
+            "note" | "notes" => QueryEntity::Note,
+            "task" | "tasks" => QueryEntity::Task,
+            "project" | "projects" => QueryEntity::Project,
+            _ => {
+                return Err(error(
+                    value_column,
+                    format!(
+                        "Invalid value '{}' for 'is' (expected note, task or project)",
+                        value
+                    ),
+                ));
+            }
+        }),
+        "type" => Predicate::NoteType(match value.to_lowercase().as_str() {
+            "fleeting" => NoteType::Fleeting,
+            "permanent" => NoteType::Permanent,
+            _ => {
+                return Err(error(
+                    value_column,
+                    format!(
+                        "Invalid value '{}' for 'type' (expected fleeting or permanent)",
+                        value
+                    ),
+                ));
+            }
+        }),
+        "sub" | "subtype" => Predicate::SubType(match value.to_lowercase().as_str() {
+            "question" => Some(SubType::Question),
+            "investigation" => Some(SubType::Investigation),
+            "log" => Some(SubType::Log),
+            "idea" => Some(SubType::Idea),
+            "reference" => Some(SubType::Reference),
+            "literature" => Some(SubType::Literature),
+            "quote" => Some(SubType::Quote),
+            "none" | "_" => None,
+            _ => {
+                return Err(error(
+                    value_column,
+                    format!("Invalid value '{}' for '{}'", value, field),
+                ));
+            }
+        }),
+        "priority" | "pri" => Predicate::Priority(match value.to_lowercase().as_str() {
+            "high" => Some(TaskPriority::High),
+            "medium" => Some(TaskPriority::Medium),
+            "low" => Some(TaskPriority::Low),
+            "none" => None,
+            _ => {
+                return Err(error(
+                    value_column,
+                    format!(
+                        "Invalid value '{}' for '{}' (expected high, medium, low or none)",
+                        value, field
+                    ),
+                ));
+            }
+        }),
+        "status" => Predicate::Status(match value.to_lowercase().as_str() {
+            "planned" => ProjectStatus::Planned,
+            "active" => ProjectStatus::Active,
+            "on_hold" | "onhold" => ProjectStatus::OnHold,
+            "completed" => ProjectStatus::Completed,
+            _ => {
+                return Err(error(
+                    value_column,
+                    format!(
+                        "Invalid value '{}' for 'status' (expected planned, active, on_hold or completed)",
+                        value
+                    ),
+                ));
+            }
+        }),
+        "tag" => Predicate::Tag(value),
+        "project" | "pid" => Predicate::Project(value),
+        "task" | "tid" => Predicate::Task(value),
+        "area" => Predicate::Area(value),
+        "title" => Predicate::Title(value),
+        "archived" | "deleted" => {
+            let flag = match value.to_lowercase().as_str() {
+                "true" | "yes" => true,
+                "false" | "no" => false,
+                _ => {
+                    return Err(error(
+                        value_column,
+                        format!(
+                            "Invalid value '{}' for '{}' (expected true or false)",
+                            value, field
+                        ),
+                    ));
+                }
+            };
+            if field_name == "archived" {
+                Predicate::Archived(flag)
+            } else {
+                Predicate::Deleted(flag)
+            }
+        }
+        "created" | "updated" | "due" => {
+            let date = parse_date(&value, today).ok_or_else(|| {
+                error(
+                    value_column,
+                    format!(
+                        "Invalid date '{}' (expected YYYY-MM-DD, today, yesterday or tomorrow)",
+                        value
+                    ),
+                )
+            })?;
+            let date_op = match op {
+                Op::Eq | Op::Ne => DateOp::On,
+                Op::Lt => DateOp::Before,
+                Op::Le => DateOp::OnOrBefore,
+                Op::Gt => DateOp::After,
+                Op::Ge => DateOp::OnOrAfter,
+            };
+            match field_name.as_str() {
+                "created" => Predicate::Created(date_op, date),
+                "updated" => Predicate::Updated(date_op, date),
+                _ => Predicate::Due(date_op, date),
+            }
+        }
+        _ => {
+            return Err(error(body_column, format!("Unknown field '{}'", field)));
+        }
+    };
+
+    Ok(Term { negated, predicate })
+}
+
+// "field<op>value" に分ける。引用符の中の記号は演算子として扱わない
+fn split_condition(body: &str, body_column: usize) -> Option<(&str, Op, &str, usize)> {
+    let (index, _) = body
+        .char_indices()
+        .take_while(|(_, c)| *c != '"')
+        .find(|(_, c)| matches!(c, ':' | '=' | '!' | '<' | '>'))?;
+    let rest = &body[index..];
+
+    let (op, op_len) = if rest.starts_with("!=") {
+        (Op::Ne, 2)
+    } else if rest.starts_with("<=") {
+        (Op::Le, 2)
+    } else if rest.starts_with(">=") {
+        (Op::Ge, 2)
+    } else if rest.starts_with('<') {
+        (Op::Lt, 1)
+    } else if rest.starts_with('>') {
+        (Op::Gt, 1)
+    } else if rest.starts_with(':') || rest.starts_with('=') {
+        (Op::Eq, 1)
+    } else {
+        // 単独の "!" は演算子ではない
+        return None;
+    };
+
+    let field = &body[..index];
+    if field.is_empty() {
+        return None;
+    }
+    let value_column = body_column + body[..index + op_len].chars().count();
+    Some((field, op, &body[index + op_len..], value_column))
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_string()
+}
+
+fn parse_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match value.to_lowercase().as_str() {
+        "today" => Some(today),
+        "yesterday" => Some(today - Duration::days(1)),
+        "tomorrow" => Some(today + Duration::days(1)),
+        _ => NaiveDate::parse_from_str(value, "%Y-%m-%d").ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()
+    }
+
+    fn parse(input: &str) -> Result<Query, QueryParseError> {
+        parse_query(input, today())
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn term(negated: bool, predicate: Predicate) -> Term {
+        Term { negated, predicate }
+    }
+
+    #[test]
+    fn tokenizes_on_whitespace_and_keeps_columns() {
+        let tokens = tokenize("  tag:rust   -tag:draft\tfoo").unwrap();
+        let tokens: Vec<(usize, &str)> =
+            tokens.iter().map(|t| (t.column, t.text.as_str())).collect();
+        assert_eq!(
+            tokens,
+            vec![(3, "tag:rust"), (14, "-tag:draft"), (25, "foo")]
+        );
+    }
+
+    #[test]
+    fn keeps_whitespace_inside_quotes() {
+        let query = parse(r#"title:"rust async" "free text""#).unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                term(false, Predicate::Title("rust async".to_string())),
+                term(false, Predicate::Title("free text".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_treat_operators_inside_quotes_as_conditions() {
+        let query = parse(r#""a:b<c""#).unwrap();
+        assert_eq!(
+            query.terms,
+            vec![term(false, Predicate::Title("a:b<c".to_string()))]
+        );
+    }
+
+    #[test]
+    fn parses_fields_case_insensitively() {
+        let query = parse("TYPE:Permanent is:Tasks pri:none sub:_ status:on_hold").unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                term(false, Predicate::NoteType(NoteType::Permanent)),
+                term(false, Predicate::Is(QueryEntity::Task)),
+                term(false, Predicate::Priority(None)),
+                term(false, Predicate::SubType(None)),
+                term(false, Predicate::Status(ProjectStatus::OnHold)),
+            ]
+        );
+    }
+
+    #[test]
+    fn prefers_two_character_operators() {
+        let query =
+            parse("created<=2025-01-01 created>=2025-01-01 due<2025-01-01 due>2025-01-01").unwrap();
+        let day = date(2025, 1, 1);
+        assert_eq!(
+            query.terms,
+            vec![
+                term(false, Predicate::Created(DateOp::OnOrBefore, day)),
+                term(false, Predicate::Created(DateOp::OnOrAfter, day)),
+                term(false, Predicate::Due(DateOp::Before, day)),
+                term(false, Predicate::Due(DateOp::After, day)),
+            ]
+        );
+    }
+
+    #[test]
+    fn combines_minus_and_not_equal() {
+        let query = parse("-tag:draft tag!=wip -tag!=keep").unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                term(true, Predicate::Tag("draft".to_string())),
+                term(true, Predicate::Tag("wip".to_string())),
+                term(false, Predicate::Tag("keep".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_relative_date_keywords() {
+        let query = parse("due:today created>Yesterday updated<TOMORROW").unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                term(false, Predicate::Due(DateOp::On, date(2025, 6, 15))),
+                term(false, Predicate::Created(DateOp::After, date(2025, 6, 14))),
+                term(false, Predicate::Updated(DateOp::Before, date(2025, 6, 16))),
+            ]
+        );
+    }
+
+    #[test]
+    fn treats_words_without_operator_as_title() {
+        let query = parse("borrow -checker !important").unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                term(false, Predicate::Title("borrow".to_string())),
+                term(true, Predicate::Title("checker".to_string())),
+                term(false, Predicate::Title("!important".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unterminated_quote_at_its_start() {
+        let error = parse(r#"tag:rust title:"open"#).unwrap_err();
+        assert_eq!(error.column, 16);
+        assert_eq!(error.message, "Unterminated quote");
+    }
+
+    #[test]
+    fn reports_invalid_values_at_the_value() {
+        let error = parse("tag:rust type:draft").unwrap_err();
+        assert_eq!(error.column, 15);
+
+        let error = parse("due<2025-13-01").unwrap_err();
+        assert_eq!(error.column, 5);
+        assert!(error.message.starts_with("Invalid date '2025-13-01'"));
+    }
+
+    #[test]
+    fn reports_field_errors_at_the_field() {
+        let error = parse("tag:rust -colour:red").unwrap_err();
+        assert_eq!(error.column, 11);
+        assert_eq!(error.message, "Unknown field 'colour'");
+
+        let error = parse("tag>rust").unwrap_err();
+        assert_eq!(error.column, 1);
+    }
+
+    #[test]
+    fn reports_missing_values_and_conditions() {
+        let error = parse("tag:rust title:").unwrap_err();
+        assert_eq!(error.column, 16);
+        assert_eq!(error.message, "Missing value for 'title'");
+
+        let error = parse("tag:rust -").unwrap_err();
+        assert_eq!(error.column, 10);
+    }
+
+    #[test]
+    fn counts_columns_in_characters() {
+        let error = parse("タグ type:x").unwrap_err();
+        assert_eq!(error.column, 9);
+    }
+}
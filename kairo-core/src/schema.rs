@@ -1,5 +1,27 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    areas (id) {
+        id -> Text,
+        title -> Text,
+        description -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        archived -> Bool,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    cascade_changes (project_id, action, entity_type, entity_id) {
+        project_id -> Text,
+        action -> Text,
+        entity_type -> Text,
+        entity_id -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     linked_notes (id) {
         id -> Text,
@@ -30,6 +52,8 @@ diesel::table! {
         deleted -> Bool,
         project_id -> Nullable<Text>,
         task_id -> Nullable<Text>,
+        file_path -> Nullable<Text>,
+        deleted_at -> Nullable<Timestamp>,
     }
 }
 
@@ -49,6 +73,30 @@ diesel::table! {
         updated_at -> Timestamp,
         archived -> Bool,
         deleted -> Bool,
+        status -> Text,
+        start_date -> Nullable<Timestamp>,
+        target_date -> Nullable<Timestamp>,
+        parent_id -> Nullable<Text>,
+        area_id -> Nullable<Text>,
+        file_path -> Nullable<Text>,
+        deleted_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    saved_views (name) {
+        name -> Text,
+        query -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    tag_aliases (alias) {
+        alias -> Text,
+        tag_id -> Text,
+        created_at -> Timestamp,
     }
 }
 
@@ -61,6 +109,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    task_external_ids (source, external_id) {
+        source -> Text,
+        external_id -> Text,
+        task_id -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     task_tags (task_id, tag_id) {
         task_id -> Text,
@@ -80,23 +137,48 @@ diesel::table! {
         archived -> Bool,
         deleted -> Bool,
         project_id -> Nullable<Text>,
+        file_path -> Nullable<Text>,
+        deleted_at -> Nullable<Timestamp>,
+        completed_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    time_entries (id) {
+        id -> Text,
+        task_id -> Text,
+        started_at -> Timestamp,
+        ended_at -> Nullable<Timestamp>,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
     }
 }
 
+diesel::joinable!(cascade_changes -> projects (project_id));
 diesel::joinable!(note_tags -> notes (note_id));
 diesel::joinable!(note_tags -> tags (tag_id));
 diesel::joinable!(project_tags -> projects (project_id));
+diesel::joinable!(projects -> areas (area_id));
 diesel::joinable!(project_tags -> tags (tag_id));
+diesel::joinable!(tag_aliases -> tags (tag_id));
+diesel::joinable!(task_external_ids -> tasks (task_id));
 diesel::joinable!(task_tags -> tags (tag_id));
 diesel::joinable!(task_tags -> tasks (task_id));
+diesel::joinable!(time_entries -> tasks (task_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    areas,
+    cascade_changes,
     linked_notes,
     note_tags,
     notes,
     project_tags,
     projects,
+    saved_views,
+    tag_aliases,
     tags,
+    task_external_ids,
     task_tags,
     tasks,
+    time_entries,
 );
@@ -0,0 +1,244 @@
+use crate::{
+    model::{Area, Project},
+    schema::{
+        areas::{self, dsl::*},
+        projects,
+    },
+    store::expand_with_descendants,
+};
+use chrono::{NaiveDateTime, Utc};
+use diesel::{SqliteConnection, prelude::*, result::Error};
+
+// ==============================
+// ▼ Structs / Create
+// ==============================
+#[derive(Insertable)]
+#[diesel(table_name = areas)]
+pub struct NewArea {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub archived: bool,
+    pub deleted: bool,
+}
+
+// ==============================
+// ▼ Structs / Update
+// ==============================
+#[derive(AsChangeset)]
+#[diesel(table_name = areas)]
+pub struct UpdatedArea {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+// ==============================
+// ▼ Structs / Archive
+// ==============================
+#[derive(AsChangeset)]
+#[diesel(table_name = areas)]
+pub struct ArchivedArea {
+    pub archived: bool,
+}
+
+// ==============================
+// ▼ Structs / SoftDelete
+// ==============================
+#[derive(AsChangeset)]
+#[diesel(table_name = areas)]
+pub struct DeletedArea {
+    pub deleted: bool,
+}
+
+// ==============================
+// ▼ Create / Insert
+// ==============================
+fn generate_area_id(conn: &mut SqliteConnection) -> Result<String, diesel::result::Error> {
+    use regex::Regex;
+
+    // エリアIDの最大数値部分を取得
+    let all_ids: Vec<String> = areas.select(id).load::<String>(conn)?;
+
+    // 正規表現で "a-001" 形式の数字部分を抽出して最大値を見つける
+    let re = Regex::new(r"a-(\d{3,})").unwrap();
+    let max_num = all_ids
+        .iter()
+        .filter_map(|area_id| {
+            re.captures(area_id)
+                .and_then(|caps| caps.get(1)?.as_str().parse::<u32>().ok())
+        })
+        .max()
+        .unwrap_or(0);
+
+    // 次の番号を3桁ゼロ埋めで生成
+    let next_id = format!("a-{:03}", max_num + 1);
+    Ok(next_id)
+}
+
+pub fn create_area(
+    conn: &mut SqliteConnection,
+    input_title: String,
+    input_description: Option<String>,
+) -> Result<Area, Error> {
+    let new_area = NewArea {
+        id: generate_area_id(conn)?,
+        title: input_title,
+        description: input_description,
+        created_at: Utc::now().naive_utc(),
+        updated_at: Utc::now().naive_utc(),
+        archived: false,
+        deleted: false,
+    };
+
+    diesel::insert_into(areas::table)
+        .values(&new_area)
+        .returning(Area::as_select())
+        .get_result(conn)
+}
+
+// ==============================
+// ▼ Read / Select
+// ==============================
+pub fn list_areas(
+    conn: &mut SqliteConnection,
+    include_archived: Option<bool>,
+    include_deleted: Option<bool>,
+) -> Result<Vec<Area>, Error> {
+    let archived_flag = include_archived.unwrap_or(false);
+    let deleted_flag = include_deleted.unwrap_or(false);
+
+    if archived_flag && deleted_flag {
+        return Err(Error::QueryBuilderError(
+            "Invalid combination: archived=true AND deleted=true".into(),
+        ));
+    }
+
+    areas
+        .filter(archived.eq(archived_flag))
+        .filter(deleted.eq(deleted_flag))
+        .order(title.asc())
+        .select(Area::as_select())
+        .load::<Area>(conn)
+}
+
+pub fn get_area_by_id(conn: &mut SqliteConnection, area_id: &str) -> Result<Option<Area>, Error> {
+    let area = areas
+        .find(area_id)
+        .select(Area::as_select())
+        .first(conn)
+        .optional()?;
+
+    Ok(area)
+}
+
+// エリアに直接属するプロジェクトと、そのサブプロジェクトすべてのIDを返す
+pub fn get_project_ids_in_area(
+    conn: &mut SqliteConnection,
+    area_id: &str,
+) -> Result<Vec<String>, Error> {
+    ensure_area_exists(conn, area_id)?;
+
+    let all_projects = projects::table
+        .select(Project::as_select())
+        .load::<Project>(conn)?;
+
+    let project_ids: Vec<String> = all_projects
+        .iter()
+        .filter(|p| p.area_id.as_deref() == Some(area_id))
+        .map(|p| p.id.clone())
+        .collect();
+
+    Ok(expand_with_descendants(&all_projects, project_ids))
+}
+
+// ==============================
+// ▼ Update
+// ==============================
+pub fn update_area(
+    conn: &mut SqliteConnection,
+    area_id: &str,
+    updated_title: Option<String>,
+    updated_description: Option<String>,
+) -> Result<Area, Error> {
+    ensure_area_exists(conn, area_id)?;
+
+    diesel::update(areas.find(area_id))
+        .set(UpdatedArea {
+            title: updated_title,
+            description: updated_description,
+            updated_at: Utc::now().naive_utc(),
+        })
+        .returning(Area::as_select())
+        .get_result(conn)
+}
+
+pub fn archive_area(conn: &mut SqliteConnection, area_id: &str) -> Result<Area, Error> {
+    let exist_area = ensure_area_exists(conn, area_id)?;
+
+    if exist_area.archived {
+        return Err(Error::QueryBuilderError("Area is already archived".into()));
+    }
+
+    diesel::update(areas.find(area_id))
+        .set(ArchivedArea { archived: true })
+        .returning(Area::as_select())
+        .get_result(conn)
+}
+
+// ==============================
+// ▼ Delete
+// ==============================
+pub fn soft_delete_area(conn: &mut SqliteConnection, area_id: &str) -> Result<Area, Error> {
+    let exist_area = ensure_area_exists(conn, area_id)?;
+
+    if exist_area.deleted {
+        return Err(Error::QueryBuilderError("Area is already deleted".into()));
+    }
+
+    diesel::update(areas.find(area_id))
+        .set(DeletedArea { deleted: true })
+        .returning(Area::as_select())
+        .get_result(conn)
+}
+
+// ==============================
+// ▼ Unarchive / Restore
+// ==============================
+pub fn unarchive_area(conn: &mut SqliteConnection, area_id: &str) -> Result<Area, Error> {
+    let archived_area = ensure_area_exists(conn, area_id)?;
+
+    if !archived_area.archived {
+        return Err(Error::QueryBuilderError("Area is not archived".into()));
+    }
+
+    diesel::update(areas.find(area_id))
+        .set(ArchivedArea { archived: false })
+        .returning(Area::as_select())
+        .get_result(conn)
+}
+
+pub fn restore_area(conn: &mut SqliteConnection, area_id: &str) -> Result<Area, Error> {
+    let deleted_area = ensure_area_exists(conn, area_id)?;
+
+    if !deleted_area.deleted {
+        return Err(Error::QueryBuilderError("Area is not deleted".into()));
+    }
+
+    diesel::update(areas.find(area_id))
+        .set(DeletedArea { deleted: false })
+        .returning(Area::as_select())
+        .get_result(conn)
+}
+
+// ==============================
+// ▼ Internal Common Utils
+// ==============================
+pub fn ensure_area_exists(conn: &mut SqliteConnection, area_id: &str) -> Result<Area, Error> {
+    match get_area_by_id(conn, area_id)? {
+        Some(area) => Ok(area),
+        None => Err(Error::QueryBuilderError("Area not found".into())),
+    }
+}
@@ -0,0 +1,359 @@
+use crate::{
+    model::{CascadeAction, CascadeChange, CascadeEntity, Project},
+    schema::{
+        cascade_changes::{self, dsl::*},
+        notes, projects, tasks,
+    },
+    store::*,
+};
+use chrono::Utc;
+use diesel::{SqliteConnection, prelude::*, result::Error};
+
+// ==============================
+// ▼ Archive / Delete (cascade)
+// ==============================
+
+// プロジェクトをアーカイブし、サブプロジェクトと配下のタスク・ノートもまとめてアーカイブする。
+// 元々アーカイブ・削除済みだったものは対象外とし、変更したものだけを記録する。
+pub fn cascade_archive_project(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<(Project, Vec<CascadeChange>), Error> {
+    conn.transaction(|conn| {
+        let project = archive_project(conn, input_project_id)?;
+        let changes = apply_cascade(conn, input_project_id, CascadeAction::Archive)?;
+        Ok((project, changes))
+    })
+}
+
+// プロジェクトを論理削除し、サブプロジェクトと配下のタスク・ノートもまとめて論理削除する。
+pub fn cascade_delete_project(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<(Project, Vec<CascadeChange>), Error> {
+    conn.transaction(|conn| {
+        let project = soft_delete_project(conn, input_project_id)?;
+        let changes = apply_cascade(conn, input_project_id, CascadeAction::Delete)?;
+        Ok((project, changes))
+    })
+}
+
+// ==============================
+// ▼ Unarchive / Restore (cascade)
+// ==============================
+
+// アーカイブ時に一緒にアーカイブしたものだけを元に戻す
+pub fn cascade_unarchive_project(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<(Project, Vec<CascadeChange>), Error> {
+    conn.transaction(|conn| {
+        let project = unarchive_project(conn, input_project_id)?;
+        let changes = revert_cascade(conn, input_project_id, CascadeAction::Archive)?;
+        Ok((project, changes))
+    })
+}
+
+// 削除時に一緒に削除したものだけを元に戻す
+pub fn cascade_restore_project(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<(Project, Vec<CascadeChange>), Error> {
+    conn.transaction(|conn| {
+        let project = restore_project(conn, input_project_id)?;
+        let changes = revert_cascade(conn, input_project_id, CascadeAction::Delete)?;
+        Ok((project, changes))
+    })
+}
+
+// 親だけを戻し、子要素はそのままにする。記録も破棄し、
+// 次に cascade で戻したときに今回の子要素まで戻らないようにする
+pub fn unarchive_project_only(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<Project, Error> {
+    conn.transaction(|conn| {
+        let project = unarchive_project(conn, input_project_id)?;
+        clear_cascade_changes(conn, input_project_id, CascadeAction::Archive)?;
+        Ok(project)
+    })
+}
+
+pub fn restore_project_only(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<Project, Error> {
+    conn.transaction(|conn| {
+        let project = restore_project(conn, input_project_id)?;
+        clear_cascade_changes(conn, input_project_id, CascadeAction::Delete)?;
+        Ok(project)
+    })
+}
+
+// ==============================
+// ▼ Read / Select
+// ==============================
+pub fn get_cascade_changes(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+    input_action: CascadeAction,
+) -> Result<Vec<CascadeChange>, Error> {
+    cascade_changes
+        .filter(project_id.eq(input_project_id))
+        .filter(action.eq(input_action))
+        .order((entity_type.asc(), entity_id.asc()))
+        .select(CascadeChange::as_select())
+        .load::<CascadeChange>(conn)
+}
+
+// ==============================
+// ▼ Delete
+// ==============================
+
+// プロジェクトが記録した変更と、ほかのプロジェクトの記録に子要素として残っているものを削除する
+pub fn delete_cascade_changes_by_project_id(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<(), Error> {
+    diesel::delete(
+        cascade_changes.filter(
+            project_id.eq(input_project_id).or(entity_type
+                .eq(CascadeEntity::Project)
+                .and(entity_id.eq(input_project_id))),
+        ),
+    )
+    .execute(conn)?;
+    Ok(())
+}
+
+// ==============================
+// ▼ Internal Common Utils
+// ==============================
+fn apply_cascade(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+    input_action: CascadeAction,
+) -> Result<Vec<CascadeChange>, Error> {
+    prune_cascade_changes(conn, input_project_id, input_action)?;
+
+    let sub_project_ids = get_descendant_project_ids(conn, input_project_id)?;
+    let mut owner_ids = sub_project_ids.clone();
+    owner_ids.push(input_project_id.to_string());
+
+    // 状態を変える対象（アーカイブは有効なものだけ、削除は未削除のものすべて）
+    let (target_projects, target_tasks, target_notes): (Vec<String>, Vec<String>, Vec<String>) =
+        match input_action {
+            CascadeAction::Archive => (
+                projects::table
+                    .filter(projects::id.eq_any(&sub_project_ids))
+                    .filter(projects::archived.eq(false))
+                    .filter(projects::deleted.eq(false))
+                    .select(projects::id)
+                    .load(conn)?,
+                tasks::table
+                    .filter(tasks::project_id.eq_any(&owner_ids))
+                    .filter(tasks::archived.eq(false))
+                    .filter(tasks::deleted.eq(false))
+                    .select(tasks::id)
+                    .load(conn)?,
+                notes::table
+                    .filter(notes::project_id.eq_any(&owner_ids))
+                    .filter(notes::archived.eq(false))
+                    .filter(notes::deleted.eq(false))
+                    .select(notes::id)
+                    .load(conn)?,
+            ),
+            CascadeAction::Delete => (
+                projects::table
+                    .filter(projects::id.eq_any(&sub_project_ids))
+                    .filter(projects::deleted.eq(false))
+                    .select(projects::id)
+                    .load(conn)?,
+                tasks::table
+                    .filter(tasks::project_id.eq_any(&owner_ids))
+                    .filter(tasks::deleted.eq(false))
+                    .select(tasks::id)
+                    .load(conn)?,
+                notes::table
+                    .filter(notes::project_id.eq_any(&owner_ids))
+                    .filter(notes::deleted.eq(false))
+                    .select(notes::id)
+                    .load(conn)?,
+            ),
+        };
+
+    let targets = [
+        (CascadeEntity::Project, target_projects),
+        (CascadeEntity::Task, target_tasks),
+        (CascadeEntity::Note, target_notes),
+    ];
+
+    let now = Utc::now().naive_utc();
+    let mut changes = Vec::new();
+    for (input_entity_type, ids) in targets {
+        set_entity_state(conn, input_entity_type, input_action, &ids, true)?;
+        for target_id in ids {
+            changes.push(CascadeChange {
+                project_id: input_project_id.to_string(),
+                action: input_action,
+                entity_type: input_entity_type,
+                entity_id: target_id,
+                created_at: now,
+            });
+        }
+    }
+
+    diesel::insert_into(cascade_changes::table)
+        .values(&changes)
+        .execute(conn)?;
+
+    Ok(changes)
+}
+
+// 前回の記録が残っていれば、子要素がまだその状態のままのものだけを残す。
+// 個別に戻したり削除したりした子要素を、次の cascade で戻さないようにする
+fn prune_cascade_changes(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+    input_action: CascadeAction,
+) -> Result<(), Error> {
+    for change in get_cascade_changes(conn, input_project_id, input_action)? {
+        if is_in_state(conn, change.entity_type, input_action, &change.entity_id)? {
+            continue;
+        }
+        diesel::delete(
+            cascade_changes
+                .filter(project_id.eq(input_project_id))
+                .filter(action.eq(input_action))
+                .filter(entity_type.eq(change.entity_type))
+                .filter(entity_id.eq(&change.entity_id)),
+        )
+        .execute(conn)?;
+    }
+    Ok(())
+}
+
+// 要素がアーカイブ済み（削除済み）のままか。すでに存在しなければ false
+fn is_in_state(
+    conn: &mut SqliteConnection,
+    input_entity_type: CascadeEntity,
+    input_action: CascadeAction,
+    id: &str,
+) -> Result<bool, Error> {
+    let state = match (input_entity_type, input_action) {
+        (CascadeEntity::Project, CascadeAction::Archive) => projects::table
+            .find(id)
+            .select(projects::archived)
+            .first::<bool>(conn),
+        (CascadeEntity::Project, CascadeAction::Delete) => projects::table
+            .find(id)
+            .select(projects::deleted)
+            .first::<bool>(conn),
+        (CascadeEntity::Task, CascadeAction::Archive) => tasks::table
+            .find(id)
+            .select(tasks::archived)
+            .first::<bool>(conn),
+        (CascadeEntity::Task, CascadeAction::Delete) => tasks::table
+            .find(id)
+            .select(tasks::deleted)
+            .first::<bool>(conn),
+        (CascadeEntity::Note, CascadeAction::Archive) => notes::table
+            .find(id)
+            .select(notes::archived)
+            .first::<bool>(conn),
+        (CascadeEntity::Note, CascadeAction::Delete) => notes::table
+            .find(id)
+            .select(notes::deleted)
+            .first::<bool>(conn),
+    };
+    Ok(state.optional()?.unwrap_or(false))
+}
+
+fn revert_cascade(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+    input_action: CascadeAction,
+) -> Result<Vec<CascadeChange>, Error> {
+    let changes = get_cascade_changes(conn, input_project_id, input_action)?;
+
+    for input_entity_type in [
+        CascadeEntity::Project,
+        CascadeEntity::Task,
+        CascadeEntity::Note,
+    ] {
+        let ids: Vec<String> = changes
+            .iter()
+            .filter(|change| change.entity_type == input_entity_type)
+            .map(|change| change.entity_id.clone())
+            .collect();
+        set_entity_state(conn, input_entity_type, input_action, &ids, false)?;
+    }
+    clear_cascade_changes(conn, input_project_id, input_action)?;
+
+    Ok(changes)
+}
+
+fn clear_cascade_changes(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+    input_action: CascadeAction,
+) -> Result<(), Error> {
+    diesel::delete(
+        cascade_changes
+            .filter(project_id.eq(input_project_id))
+            .filter(action.eq(input_action)),
+    )
+    .execute(conn)?;
+    Ok(())
+}
+
+fn set_entity_state(
+    conn: &mut SqliteConnection,
+    input_entity_type: CascadeEntity,
+    input_action: CascadeAction,
+    ids: &[String],
+    value: bool,
+) -> Result<usize, Error> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let deleted_at = value.then(|| Utc::now().naive_utc());
+
+    match (input_entity_type, input_action) {
+        (CascadeEntity::Project, CascadeAction::Archive) => {
+            diesel::update(projects::table.filter(projects::id.eq_any(ids)))
+                .set(projects::archived.eq(value))
+                .execute(conn)
+        }
+        (CascadeEntity::Project, CascadeAction::Delete) => {
+            diesel::update(projects::table.filter(projects::id.eq_any(ids)))
+                .set((
+                    projects::deleted.eq(value),
+                    projects::deleted_at.eq(deleted_at),
+                ))
+                .execute(conn)
+        }
+        // プロジェクトに合わせて片付けただけで完了ではないので、completed_at には触れない
+        (CascadeEntity::Task, CascadeAction::Archive) => {
+            diesel::update(tasks::table.filter(tasks::id.eq_any(ids)))
+                .set(tasks::archived.eq(value))
+                .execute(conn)
+        }
+        (CascadeEntity::Task, CascadeAction::Delete) => {
+            diesel::update(tasks::table.filter(tasks::id.eq_any(ids)))
+                .set((tasks::deleted.eq(value), tasks::deleted_at.eq(deleted_at)))
+                .execute(conn)
+        }
+        (CascadeEntity::Note, CascadeAction::Archive) => {
+            diesel::update(notes::table.filter(notes::id.eq_any(ids)))
+                .set(notes::archived.eq(value))
+                .execute(conn)
+        }
+        (CascadeEntity::Note, CascadeAction::Delete) => {
+            diesel::update(notes::table.filter(notes::id.eq_any(ids)))
+                .set((notes::deleted.eq(value), notes::deleted_at.eq(deleted_at)))
+                .execute(conn)
+        }
+    }
+}
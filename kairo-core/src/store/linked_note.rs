@@ -123,6 +123,20 @@ pub fn get_link_by_id(
     Ok(link)
 }
 
+// 指定したノート同士を結ぶリンク（両端がともに含まれるもの）を返す
+pub fn get_links_among_notes(
+    conn: &mut SqliteConnection,
+    note_ids: &[String],
+) -> Result<Vec<LinkedNote>, Error> {
+    linked_notes
+        .filter(from_id.eq_any(note_ids))
+        .filter(to_id.eq_any(note_ids))
+        .filter(deleted.eq(false))
+        .order(created_at.asc())
+        .select(LinkedNote::as_select())
+        .load(conn)
+}
+
 // ==============================
 // ▼ Delete
 // ==============================
@@ -139,6 +153,16 @@ pub fn soft_delete_link(conn: &mut SqliteConnection, link_id: &str) -> Result<Li
         .get_result(conn)
 }
 
+// ノートから出る・ノートへ入るリンクをすべて削除し、削除したリンクを返す
+pub fn delete_links_by_note_id(
+    conn: &mut SqliteConnection,
+    note_id: &str,
+) -> Result<Vec<LinkedNote>, Error> {
+    diesel::delete(linked_notes.filter(from_id.eq(note_id).or(to_id.eq(note_id))))
+        .returning(LinkedNote::as_select())
+        .get_results(conn)
+}
+
 // ==============================
 // ▼ Internal Common Utils
 // ==============================
@@ -1,19 +1,33 @@
+pub mod area;
+pub mod cascade;
 pub mod db;
 pub mod linked_note;
 pub mod note;
 pub mod note_tag;
 pub mod project;
 pub mod project_tag;
+pub mod query;
+pub mod saved_view;
 pub mod tag;
+pub mod tag_alias;
 pub mod task;
+pub mod task_external_id;
 pub mod task_tag;
+pub mod time_entry;
 
+pub use area::*;
+pub use cascade::*;
 pub use db::*;
 pub use linked_note::*;
 pub use note::*;
 pub use note_tag::*;
 pub use project::*;
 pub use project_tag::*;
+pub use query::*;
+pub use saved_view::*;
 pub use tag::*;
+pub use tag_alias::*;
 pub use task::*;
+pub use task_external_id::*;
 pub use task_tag::*;
+pub use time_entry::*;
@@ -1,5 +1,7 @@
+use std::collections::HashMap;
+
 use crate::{
-    model::{Note, NoteType, SubType},
+    model::{LinkedNote, NewNoteInput, Note, NoteFilter, NoteType, SubType, TagFilter},
     schema::{
         note_tags,
         notes::{self, dsl::*},
@@ -7,7 +9,7 @@ use crate::{
     },
     store::*,
 };
-use chrono::{NaiveDateTime, Utc};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use diesel::{SqliteConnection, prelude::*, result::Error};
 
 use super::get_tag_by_name;
@@ -57,35 +59,76 @@ pub struct ArchivedNote {
 // ▼ Structs / SoftDelete
 // ==============================
 #[derive(AsChangeset)]
-#[diesel(table_name = notes)]
+#[diesel(table_name = notes, treat_none_as_null = true)]
 pub struct SoftDeletedNote {
     pub deleted: bool,
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 // ==============================
 // ▼ Create / Insert
 // ==============================
-fn generate_note_id() -> String {
-    Utc::now().format("%Y%m%dT%H%M%S").to_string()
+// 作成日時（秒まで）を ID にする。同じ秒にノートがすでにあれば -2, -3 ... を付ける
+fn generate_note_id(conn: &mut SqliteConnection) -> Result<String, diesel::result::Error> {
+    let base_id = Utc::now().format("%Y%m%dT%H%M%S").to_string();
+
+    let taken_ids: Vec<String> = notes
+        .filter(id.eq(&base_id).or(id.like(format!("{}-%", base_id))))
+        .select(id)
+        .load::<String>(conn)?;
+
+    let next_id = std::iter::once(base_id.clone())
+        .chain((2..).map(|n| format!("{}-{}", base_id, n)))
+        .find(|candidate| !taken_ids.contains(candidate))
+        .unwrap();
+    Ok(next_id)
+}
+
+const DAILY_NOTE_PREFIX: &str = "daily-";
+
+// デイリーノートは日付から決まる ID を使う（例：daily-2025-06-24）
+pub fn daily_note_id(date: NaiveDate) -> String {
+    format!("{}{}", DAILY_NOTE_PREFIX, date.format("%Y-%m-%d"))
+}
+
+pub fn create_daily_note(
+    conn: &mut SqliteConnection,
+    date: NaiveDate,
+    input_title: String,
+) -> Result<Note, Error> {
+    let now = Utc::now().naive_utc();
+    let new_note = NewNote {
+        id: daily_note_id(date),
+        title: input_title,
+        note_type: NoteType::Permanent,
+        sub_type: Some(SubType::Log),
+        created_at: now,
+        updated_at: now,
+        archived: false,
+        deleted: false,
+        project_id: None,
+        task_id: None,
+    };
+
+    diesel::insert_into(notes::table)
+        .values(&new_note)
+        .returning(Note::as_select())
+        .get_result(conn)
 }
 
 pub fn create_note(
     conn: &mut SqliteConnection,
     input_title: String,
-    input_note_type: &str,
-    input_sub_type: &str,
-    input_project_id: Option<String>,
-    input_task_id: Option<String>,
-    input_tag_names: Option<Vec<String>>,
+    input: NewNoteInput,
 ) -> Result<Note, Error> {
-    let validated_note_type = parse_note_type(&input_note_type)?;
-    let validated_sub_type = parse_sub_type(&input_sub_type)?;
-    if let Some(ref pid) = input_project_id {
+    let validated_note_type = parse_note_type(&input.note_type)?;
+    let validated_sub_type = parse_sub_type(&input.sub_type)?;
+    if let Some(ref pid) = input.project_id {
         ensure_project_exists(conn, pid)?;
     }
 
     let new_note = NewNote {
-        id: generate_note_id(),
+        id: generate_note_id(conn)?,
         title: input_title,
         note_type: validated_note_type,
         sub_type: validated_sub_type,
@@ -93,18 +136,22 @@ pub fn create_note(
         updated_at: Utc::now().naive_utc(),
         archived: false,
         deleted: false,
-        project_id: input_project_id,
-        task_id: input_task_id,
+        project_id: input.project_id,
+        task_id: input.task_id,
     };
 
     // Note を保存
+    if let Some(ref tag_names) = input.tags {
+        ensure_tag_names_usable(conn, tag_names)?;
+    }
+
     let note = diesel::insert_into(notes::table)
         .values(&new_note)
         .returning(Note::as_select())
         .get_result(conn)?;
 
     // Tag と NoteTag の保存処理
-    if let Some(tag_names) = input_tag_names {
+    if let Some(tag_names) = input.tags {
         for name in tag_names {
             // タグ取得または作成
             let tag = match get_tag_by_name(conn, name.clone()) {
@@ -128,8 +175,10 @@ pub fn list_notes(
     conn: &mut SqliteConnection,
     include_archived: Option<bool>,
     include_deleted: Option<bool>,
-    include_tags: Option<Vec<String>>,
+    include_tags: Option<TagFilter>,
     include_order: Option<String>,
+    include_area_id: Option<String>,
+    include_filter: NoteFilter,
 ) -> Result<Vec<Note>, Error> {
     let archived_flag = include_archived.unwrap_or(false);
     let deleted_flag = include_deleted.unwrap_or(false);
@@ -140,39 +189,129 @@ pub fn list_notes(
         ));
     }
 
-    // タグフィルターがある場合：JOIN込みクエリで返す
-    if let Some(tags_filter) = include_tags {
-        let tag_filtered_query = notes
-            .inner_join(note_tags::table.on(notes::id.eq(note_tags::note_id)))
-            .inner_join(tags::table.on(tags::id.eq(note_tags::tag_id)))
-            .filter(tags::tag_name.eq_any(tags_filter))
-            .filter(archived.eq(archived_flag))
-            .filter(deleted.eq(deleted_flag))
-            .select(Note::as_select())
-            .distinct();
-
-        let ordered_query = match include_order.as_deref() {
-            Some("asc") => tag_filtered_query.order(created_at.asc()).into_boxed(),
-            Some("desc") => tag_filtered_query.order(created_at.desc()).into_boxed(),
-            _ => tag_filtered_query.order(created_at.desc()).into_boxed(), // デフォルトは降順
+    // エリアフィルター（エリア配下のプロジェクトとサブプロジェクトのノート）
+    let area_project_ids = match include_area_id.as_deref() {
+        Some(aid) => Some(get_project_ids_in_area(conn, aid)?),
+        None => None,
+    };
+
+    let mut query = notes
+        .filter(archived.eq(archived_flag))
+        .filter(deleted.eq(deleted_flag))
+        .into_boxed();
+
+    // タグフィルター（all はすべて、any はいずれか、not はどれも付いていないもの）
+    if let Some(filter) = include_tags {
+        for tag in filter.all {
+            let names = expand_tag_names(conn, vec![tag])?;
+            query = query.filter(
+                notes::id.eq_any(
+                    note_tags::table
+                        .inner_join(tags::table)
+                        .filter(tags::tag_name.eq_any(names))
+                        .select(note_tags::note_id),
+                ),
+            );
+        }
+        if !filter.any.is_empty() {
+            let names = expand_tag_names(conn, filter.any)?;
+            query = query.filter(
+                notes::id.eq_any(
+                    note_tags::table
+                        .inner_join(tags::table)
+                        .filter(tags::tag_name.eq_any(names))
+                        .select(note_tags::note_id),
+                ),
+            );
+        }
+        if !filter.not.is_empty() {
+            let names = expand_tag_names(conn, filter.not)?;
+            query = query.filter(
+                notes::id.ne_all(
+                    note_tags::table
+                        .inner_join(tags::table)
+                        .filter(tags::tag_name.eq_any(names))
+                        .select(note_tags::note_id),
+                ),
+            );
+        }
+    }
+
+    if let Some(ids) = area_project_ids {
+        query = query.filter(notes::project_id.eq_any(ids));
+    }
+
+    // 種別・関連フィルター
+    if let Some(ref input) = include_filter.note_type {
+        query = query.filter(note_type.eq(parse_note_type(input)?));
+    }
+    if let Some(ref input) = include_filter.sub_type {
+        // "_" を指定するとサブタイプなしのノート
+        query = match parse_sub_type(input)? {
+            Some(parsed) => query.filter(sub_type.eq(parsed)),
+            None => query.filter(sub_type.is_null()),
         };
+    }
+    if let Some(pid) = include_filter.project_id {
+        query = query.filter(project_id.eq(pid));
+    }
+    if let Some(tid) = include_filter.task_id {
+        query = query.filter(task_id.eq(tid));
+    }
 
-        return Ok(ordered_query.load::<Note>(conn)?);
+    // 日付範囲フィルター（終了日はその日の終わりまで含める）
+    if let Some(ref from) = include_filter.created_from {
+        query = query.filter(created_at.ge(parse_date_bound(from)?));
+    }
+    if let Some(ref to) = include_filter.created_to {
+        query = query.filter(created_at.lt(parse_date_bound(to)? + Duration::days(1)));
+    }
+    if let Some(ref from) = include_filter.updated_from {
+        query = query.filter(updated_at.ge(parse_date_bound(from)?));
+    }
+    if let Some(ref to) = include_filter.updated_to {
+        query = query.filter(updated_at.lt(parse_date_bound(to)? + Duration::days(1)));
     }
 
-    // タグフィルターがない場合
-    let base_query = notes
-        .filter(archived.eq(archived_flag))
-        .filter(deleted.eq(deleted_flag))
-        .select(Note::as_select());
+    // タイトルの部分一致（% と _ は文字として扱う）
+    if let Some(ref keyword) = include_filter.title {
+        let escaped = keyword
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        query = query.filter(title.like(format!("%{}%", escaped)).escape('\\'));
+    }
 
-    let ordered_query = match include_order.as_deref() {
-        Some("asc") => base_query.order(created_at.asc()).into_boxed(),
-        Some("desc") => base_query.order(created_at.desc()).into_boxed(),
-        _ => base_query.order(created_at.desc()).into_boxed(), // デフォルト: desc
+    // ソート順指定（タイトル順は昇順、日付順は降順が既定）
+    let sort_key = include_filter.sort.as_deref().unwrap_or("created");
+    let ascending = match include_order.as_deref() {
+        Some("asc") => true,
+        Some("desc") => false,
+        _ => sort_key == "title",
+    };
+    query = match (sort_key, ascending) {
+        ("created", true) => query.order((created_at.asc(), notes::id.asc())),
+        ("created", false) => query.order((created_at.desc(), notes::id.desc())),
+        ("updated", true) => query.order((updated_at.asc(), notes::id.asc())),
+        ("updated", false) => query.order((updated_at.desc(), notes::id.desc())),
+        ("title", true) => query.order((title.asc(), notes::id.asc())),
+        ("title", false) => query.order((title.desc(), notes::id.desc())),
+        (other, _) => {
+            return Err(Error::QueryBuilderError(
+                format!("Invalid sort: {}", other).into(),
+            ));
+        }
     };
 
-    Ok(ordered_query.load::<Note>(conn)?)
+    // ページング
+    if let Some(limit) = include_filter.limit {
+        query = query.limit(limit);
+    }
+    if let Some(offset) = include_filter.offset {
+        query = query.offset(offset);
+    }
+
+    Ok(query.select(Note::as_select()).load::<Note>(conn)?)
 }
 
 pub fn get_note_by_id(conn: &mut SqliteConnection, note_id: &str) -> Result<Option<Note>, Error> {
@@ -185,6 +324,106 @@ pub fn get_note_by_id(conn: &mut SqliteConnection, note_id: &str) -> Result<Opti
     Ok(note)
 }
 
+// タイトルが完全一致するノート（削除済みを除く、古いものを優先）
+pub fn get_note_by_title(
+    conn: &mut SqliteConnection,
+    input_title: &str,
+) -> Result<Option<Note>, Error> {
+    notes
+        .filter(title.eq(input_title))
+        .filter(deleted.eq(false))
+        .order(created_at.asc())
+        .select(Note::as_select())
+        .first(conn)
+        .optional()
+}
+
+// 指定日より前で最も新しいデイリーノート（削除済みを除く）
+pub fn get_previous_daily_note(
+    conn: &mut SqliteConnection,
+    date: NaiveDate,
+) -> Result<Option<Note>, Error> {
+    notes
+        .filter(id.like(format!("{}%", DAILY_NOTE_PREFIX)))
+        .filter(id.lt(daily_note_id(date)))
+        .filter(deleted.eq(false))
+        .order(id.desc())
+        .select(Note::as_select())
+        .first(conn)
+        .optional()
+}
+
+// 削除済みを除いた、指定日（ローカル時刻）に作成されたノート（デイリーノートを除く）
+pub fn get_notes_created_on(
+    conn: &mut SqliteConnection,
+    date: NaiveDate,
+) -> Result<Vec<Note>, Error> {
+    let (start, end) = local_day_range(date);
+    notes
+        .filter(created_at.ge(start))
+        .filter(created_at.lt(end))
+        .filter(deleted.eq(false))
+        .filter(id.not_like(format!("{}%", DAILY_NOTE_PREFIX)))
+        .order(created_at.asc())
+        .select(Note::as_select())
+        .load(conn)
+}
+
+// 削除済みを除いた、プロジェクトに紐づくノート
+pub fn get_notes_by_project_id(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<Vec<Note>, Error> {
+    notes
+        .filter(project_id.eq(input_project_id))
+        .filter(deleted.eq(false))
+        .order(created_at.asc())
+        .select(Note::as_select())
+        .load::<Note>(conn)
+}
+
+// 削除済みを除いた、プロジェクトに紐づくノートの件数
+pub fn count_notes_by_project_id(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<i64, Error> {
+    notes
+        .filter(project_id.eq(input_project_id))
+        .filter(deleted.eq(false))
+        .count()
+        .get_result(conn)
+}
+
+// 削除済みを除き、プロジェクトごとのノート数を返す
+pub fn count_notes_by_project(conn: &mut SqliteConnection) -> Result<HashMap<String, i64>, Error> {
+    let rows: Vec<(Option<String>, i64)> = notes
+        .filter(deleted.eq(false))
+        .filter(project_id.is_not_null())
+        .group_by(project_id)
+        .select((project_id, diesel::dsl::count_star()))
+        .load(conn)?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(pid, count)| pid.map(|pid| (pid, count)))
+        .collect())
+}
+
+// 論理削除済みのノート。before を指定すると、それより前に削除したものだけを返す
+pub fn get_deleted_notes(
+    conn: &mut SqliteConnection,
+    before: Option<NaiveDateTime>,
+) -> Result<Vec<Note>, Error> {
+    let mut query = notes.filter(deleted.eq(true)).into_boxed();
+    if let Some(before) = before {
+        query = query.filter(deleted_at.lt(before));
+    }
+    query
+        .order(deleted_at.asc())
+        .select(Note::as_select())
+        .load(conn)
+}
+
 // ==============================
 // ▼ Update
 // ==============================
@@ -199,6 +438,9 @@ pub fn update_note(
     updated_tags: Option<Vec<String>>,
 ) -> Result<Note, Error> {
     let exist_note = ensure_note_exists(conn, note_id)?;
+    if let Some(ref tags) = updated_tags {
+        ensure_tag_names_usable(conn, tags)?;
+    }
 
     let validated_note_type = match updated_note_type {
         Some(ref exist) => parse_note_type(exist)?,
@@ -265,6 +507,18 @@ pub fn archive_note(conn: &mut SqliteConnection, note_id: &str) -> Result<Note,
         .get_result(conn)
 }
 
+// Markdown ファイルを移したときに、保存先のパスを記録する。updated_at は変えない
+pub fn set_note_file_path(
+    conn: &mut SqliteConnection,
+    note_id: &str,
+    path: &str,
+) -> Result<Note, Error> {
+    diesel::update(notes.find(note_id))
+        .set(file_path.eq(Some(path)))
+        .returning(Note::as_select())
+        .get_result(conn)
+}
+
 // ==============================
 // ▼ Delete
 // ==============================
@@ -276,18 +530,26 @@ pub fn soft_delete_note(conn: &mut SqliteConnection, note_id: &str) -> Result<No
     }
 
     diesel::update(notes.find(note_id))
-        .set(SoftDeletedNote { deleted: true })
+        .set(SoftDeletedNote {
+            deleted: true,
+            deleted_at: Some(Utc::now().naive_utc()),
+        })
         .returning(Note::as_select())
         .get_result(conn)
 }
 
-pub fn delete_note(conn: &mut SqliteConnection, note_id: &str) -> Result<(), Error> {
-    let _exist_note = ensure_note_exists(conn, note_id)?;
-    diesel::delete(notes.find(note_id))
-        .returning(Note::as_select())
-        .get_result(conn)?;
-
-    Ok(())
+// ノートを参照する行（タグ・リンク）も同じトランザクションで削除し、削除したリンクを返す
+pub fn delete_note(conn: &mut SqliteConnection, note_id: &str) -> Result<Vec<LinkedNote>, Error> {
+    conn.transaction(|conn| {
+        let _exist_note = ensure_note_exists(conn, note_id)?;
+        diesel::delete(note_tags::table.filter(note_tags::note_id.eq(note_id))).execute(conn)?;
+        let links = delete_links_by_note_id(conn, note_id)?;
+        diesel::delete(notes.find(note_id))
+            .returning(Note::as_select())
+            .get_result(conn)?;
+
+        Ok(links)
+    })
 }
 
 // ==============================
@@ -314,14 +576,99 @@ pub fn restore_note(conn: &mut SqliteConnection, note_id: &str) -> Result<Note,
     }
 
     diesel::update(notes.find(note_id))
-        .set(SoftDeletedNote { deleted: false })
+        .set(SoftDeletedNote {
+            deleted: false,
+            deleted_at: None,
+        })
+        .returning(Note::as_select())
+        .get_result(conn)
+}
+
+// ==============================
+// ▼ Promote
+// ==============================
+// fleeting ノートをそのまま permanent にする
+pub fn promote_note(conn: &mut SqliteConnection, note_id: &str) -> Result<Note, Error> {
+    let source = ensure_promotable(conn, note_id)?;
+
+    diesel::update(notes.find(&source.id))
+        .set((
+            note_type.eq(NoteType::Permanent),
+            updated_at.eq(Utc::now().naive_utc()),
+        ))
         .returning(Note::as_select())
         .get_result(conn)
 }
 
+// fleeting ノートから新しい permanent ノートを作り、元のノートへのリンクを張る。
+// タグ・サブタイプ・関連プロジェクト/タスクは元のノートを引き継ぐ。元のノートは変更しない。
+pub fn split_note(
+    conn: &mut SqliteConnection,
+    note_id: &str,
+    input_title: Option<String>,
+    input_link_type: &str,
+) -> Result<(Note, LinkedNote), Error> {
+    if !matches!(input_link_type, "structure" | "reference") {
+        return Err(Error::QueryBuilderError(
+            format!(
+                "Invalid link_type for promotion: {} (use structure or reference)",
+                input_link_type
+            )
+            .into(),
+        ));
+    }
+
+    conn.transaction(|conn| {
+        let source = ensure_promotable(conn, note_id)?;
+        let tag_names: Vec<String> = get_tags_by_note_id(conn, &source.id)?
+            .into_iter()
+            .map(|t| t.tag_name)
+            .collect();
+        let source_sub_type = source
+            .sub_type
+            .map(|s| format!("{:?}", s).to_lowercase())
+            .unwrap_or_default();
+
+        // 元のノートと同じ秒に作られても、create_note が別の ID（-2 など）を振る
+        let note = create_note(
+            conn,
+            input_title.unwrap_or_else(|| source.title.clone()),
+            NewNoteInput {
+                note_type: "permanent".to_string(),
+                sub_type: source_sub_type,
+                project_id: source.project_id.clone(),
+                task_id: source.task_id.clone(),
+                tags: Some(tag_names),
+            },
+        )?;
+
+        let link = create_link(
+            conn,
+            note.id.clone(),
+            source.id.clone(),
+            input_link_type.to_string(),
+        )?;
+        Ok((note, link))
+    })
+}
+
 // ==============================
 // ▼ Internal Common Utils
 // ==============================
+fn ensure_promotable(conn: &mut SqliteConnection, note_id: &str) -> Result<Note, Error> {
+    let source = ensure_note_exists(conn, note_id)?;
+
+    if source.deleted {
+        return Err(Error::QueryBuilderError("Note is deleted".into()));
+    }
+    if source.note_type != NoteType::Fleeting {
+        return Err(Error::QueryBuilderError(
+            "Only fleeting notes can be promoted".into(),
+        ));
+    }
+    Ok(source)
+}
+
 fn ensure_note_exists(conn: &mut SqliteConnection, note_id: &str) -> Result<Note, Error> {
     match get_note_by_id(conn, note_id)? {
         Some(note) => Ok(note),
@@ -329,6 +676,12 @@ fn ensure_note_exists(conn: &mut SqliteConnection, note_id: &str) -> Result<Note
     }
 }
 
+fn parse_date_bound(input: &str) -> Result<NaiveDateTime, Error> {
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map(|date| date.and_time(NaiveTime::MIN))
+        .map_err(|_| Error::QueryBuilderError(format!("Invalid date: {}", input).into()))
+}
+
 fn parse_note_type(input: &str) -> Result<NoteType, Error> {
     match input {
         "fleeting" => Ok(NoteType::Fleeting),
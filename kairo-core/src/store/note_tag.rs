@@ -49,6 +49,18 @@ pub fn get_note_tags_by_note_id(
     Ok(note_tag)
 }
 
+// いずれかのタグが付いているノートのID
+pub fn get_note_ids_by_tag_ids(
+    conn: &mut SqliteConnection,
+    input_tag_ids: &[String],
+) -> Result<Vec<String>, Error> {
+    note_tags::table
+        .filter(note_tags::tag_id.eq_any(input_tag_ids))
+        .select(note_tags::note_id)
+        .distinct()
+        .load::<String>(conn)
+}
+
 // ==============================
 // ▼ Delete
 // ==============================
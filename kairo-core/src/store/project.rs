@@ -1,5 +1,5 @@
 use crate::{
-    model::Project,
+    model::{NewProjectInput, Project, ProjectStatus, TagFilter, UpdateProjectInput},
     schema::{
         project_tags,
         projects::{self, dsl::*},
@@ -7,7 +7,7 @@ use crate::{
     },
     store::*,
 };
-use chrono::{NaiveDateTime, Utc};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use diesel::{SqliteConnection, prelude::*, result::Error};
 
 // ==============================
@@ -23,6 +23,11 @@ pub struct NewProject {
     pub updated_at: NaiveDateTime,
     pub archived: bool,
     pub deleted: bool,
+    pub status: ProjectStatus,
+    pub start_date: Option<NaiveDateTime>,
+    pub target_date: Option<NaiveDateTime>,
+    pub parent_id: Option<String>,
+    pub area_id: Option<String>,
 }
 
 // ==============================
@@ -34,6 +39,12 @@ pub struct UpdatedProject {
     pub title: Option<String>,
     pub description: Option<String>,
     pub updated_at: NaiveDateTime,
+    pub status: Option<ProjectStatus>,
+    pub start_date: Option<NaiveDateTime>,
+    pub target_date: Option<NaiveDateTime>,
+    // Some(None) の場合は親・エリアの紐付けを外す
+    pub parent_id: Option<Option<String>>,
+    pub area_id: Option<Option<String>>,
 }
 
 // ==============================
@@ -49,9 +60,10 @@ pub struct ArchivedProject {
 // ▼ Structs / SoftDelete
 // ==============================
 #[derive(AsChangeset)]
-#[diesel(table_name = projects)]
+#[diesel(table_name = projects, treat_none_as_null = true)]
 pub struct DeletedProject {
     pub deleted: bool,
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 // ==============================
@@ -82,28 +94,51 @@ fn generate_project_id(conn: &mut SqliteConnection) -> Result<String, diesel::re
 pub fn create_project(
     conn: &mut SqliteConnection,
     input_title: String,
-    input_description: Option<String>,
-    input_tag_names: Option<Vec<String>>,
+    input: NewProjectInput,
 ) -> Result<Project, Error> {
     let project_id = generate_project_id(conn)?;
+    let validated_status = parse_project_status(input.status.as_deref().unwrap_or("active"))?;
+    let parsed_start_date = parse_project_date(input.start_date)?;
+    let parsed_target_date = parse_project_date(input.target_date)?;
+
+    // サブプロジェクトはエリアの指定がなければ親のエリアを引き継ぐ
+    let parent = match input.parent_id.as_deref() {
+        Some(pid) => Some(ensure_project_exists(conn, pid)?),
+        None => None,
+    };
+    if let Some(aid) = input.area_id.as_deref() {
+        ensure_area_exists(conn, aid)?;
+    }
+    let validated_area_id = input
+        .area_id
+        .or_else(|| parent.as_ref().and_then(|p| p.area_id.clone()));
 
     let new_project = NewProject {
         id: project_id,
         title: input_title,
-        description: input_description,
+        description: input.description,
         created_at: Utc::now().naive_utc(),
         updated_at: Utc::now().naive_utc(),
         archived: false,
         deleted: false,
+        status: validated_status,
+        start_date: parsed_start_date,
+        target_date: parsed_target_date,
+        parent_id: parent.map(|p| p.id),
+        area_id: validated_area_id,
     };
 
+    if let Some(ref tag_names) = input.tags {
+        ensure_tag_names_usable(conn, tag_names)?;
+    }
+
     let project = diesel::insert_into(projects::table)
         .values(&new_project)
         .returning(Project::as_select())
         .get_result(conn)?;
 
     // Tag と ProjectTag の保存処理
-    if let Some(tag_names) = input_tag_names {
+    if let Some(tag_names) = input.tags {
         for name in tag_names {
             // タグ取得または作成
             let tag = match get_tag_by_name(conn, name.clone()) {
@@ -127,8 +162,9 @@ pub fn list_projects(
     conn: &mut SqliteConnection,
     include_archived: Option<bool>,
     include_deleted: Option<bool>,
-    include_tags: Option<Vec<String>>,
+    include_tags: Option<TagFilter>,
     include_order: Option<String>,
+    include_area_id: Option<String>,
 ) -> Result<Vec<Project>, Error> {
     let archived_flag = include_archived.unwrap_or(false);
     let deleted_flag = include_deleted.unwrap_or(false);
@@ -139,39 +175,65 @@ pub fn list_projects(
         ));
     }
 
-    // タグフィルターがある場合：JOIN込みクエリで返す
-    if let Some(tags_filter) = include_tags {
-        let tag_filtered_query = projects
-            .inner_join(project_tags::table.on(projects::id.eq(project_tags::project_id)))
-            .inner_join(tags::table.on(tags::id.eq(project_tags::tag_id)))
-            .filter(tags::tag_name.eq_any(tags_filter))
-            .filter(archived.eq(archived_flag))
-            .filter(deleted.eq(deleted_flag))
-            .select(Project::as_select())
-            .distinct();
-
-        let ordered_query = match include_order.as_deref() {
-            Some("asc") => tag_filtered_query.order(created_at.asc()).into_boxed(),
-            Some("desc") => tag_filtered_query.order(created_at.desc()).into_boxed(),
-            _ => tag_filtered_query.order(created_at.desc()).into_boxed(), // デフォルトは降順
-        };
-
-        return Ok(ordered_query.load::<Project>(conn)?);
-    }
+    // エリアフィルター（サブプロジェクトを含む）
+    let area_project_ids = match include_area_id.as_deref() {
+        Some(aid) => Some(get_project_ids_in_area(conn, aid)?),
+        None => None,
+    };
 
-    // タグフィルターがない場合
-    let base_query = projects
+    let mut query = projects
         .filter(archived.eq(archived_flag))
         .filter(deleted.eq(deleted_flag))
-        .select(Project::as_select());
+        .into_boxed();
+
+    // タグフィルター（all はすべて、any はいずれか、not はどれも付いていないもの）
+    if let Some(filter) = include_tags {
+        for tag in filter.all {
+            let names = expand_tag_names(conn, vec![tag])?;
+            query = query.filter(
+                projects::id.eq_any(
+                    project_tags::table
+                        .inner_join(tags::table)
+                        .filter(tags::tag_name.eq_any(names))
+                        .select(project_tags::project_id),
+                ),
+            );
+        }
+        if !filter.any.is_empty() {
+            let names = expand_tag_names(conn, filter.any)?;
+            query = query.filter(
+                projects::id.eq_any(
+                    project_tags::table
+                        .inner_join(tags::table)
+                        .filter(tags::tag_name.eq_any(names))
+                        .select(project_tags::project_id),
+                ),
+            );
+        }
+        if !filter.not.is_empty() {
+            let names = expand_tag_names(conn, filter.not)?;
+            query = query.filter(
+                projects::id.ne_all(
+                    project_tags::table
+                        .inner_join(tags::table)
+                        .filter(tags::tag_name.eq_any(names))
+                        .select(project_tags::project_id),
+                ),
+            );
+        }
+    }
+
+    if let Some(ids) = area_project_ids {
+        query = query.filter(projects::id.eq_any(ids));
+    }
 
-    let ordered_query = match include_order.as_deref() {
-        Some("asc") => base_query.order(created_at.asc()).into_boxed(),
-        Some("desc") => base_query.order(created_at.desc()).into_boxed(),
-        _ => base_query.order(created_at.desc()).into_boxed(), // デフォルト: desc
+    query = match include_order.as_deref() {
+        Some("asc") => query.order(created_at.asc()),
+        Some("desc") => query.order(created_at.desc()),
+        _ => query.order(created_at.desc()), // デフォルト: desc
     };
 
-    Ok(ordered_query.load::<Project>(conn)?)
+    Ok(query.select(Project::as_select()).load::<Project>(conn)?)
 }
 
 pub fn get_project_by_id(
@@ -187,25 +249,81 @@ pub fn get_project_by_id(
     Ok(project)
 }
 
+// 削除済みを除いた直下のサブプロジェクト
+pub fn get_sub_projects(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<Vec<Project>, Error> {
+    projects
+        .filter(parent_id.eq(input_project_id))
+        .filter(deleted.eq(false))
+        .order(created_at.asc())
+        .select(Project::as_select())
+        .load::<Project>(conn)
+}
+
+// 論理削除済みのプロジェクト。before を指定すると、それより前に削除したものだけを返す
+pub fn get_deleted_projects(
+    conn: &mut SqliteConnection,
+    before: Option<NaiveDateTime>,
+) -> Result<Vec<Project>, Error> {
+    let mut query = projects.filter(deleted.eq(true)).into_boxed();
+    if let Some(before) = before {
+        query = query.filter(deleted_at.lt(before));
+    }
+    query
+        .order(deleted_at.asc())
+        .select(Project::as_select())
+        .load(conn)
+}
+
 // ==============================
 // ▼ Update
 // ==============================
 pub fn update_project(
     conn: &mut SqliteConnection,
     project_id: &str,
-    updated_title: Option<String>,
-    updated_description: Option<String>,
-    updated_tags: Option<Vec<String>>,
+    input: UpdateProjectInput,
 ) -> Result<Project, Error> {
     let _exist_project = ensure_project_exists(conn, project_id)?;
+    if let Some(ref tags) = input.tags {
+        ensure_tag_names_usable(conn, tags)?;
+    }
+    let validated_status = match input.status {
+        Some(ref exist) => Some(parse_project_status(exist)?),
+        None => None,
+    };
+
+    // "none" を指定すると紐付けを外す
+    let validated_parent_id = match input.parent_id.as_deref() {
+        Some("none") => Some(None),
+        Some(pid) => {
+            ensure_valid_parent(conn, project_id, pid)?;
+            Some(Some(pid.to_string()))
+        }
+        None => None,
+    };
+    let validated_area_id = match input.area_id.as_deref() {
+        Some("none") => Some(None),
+        Some(aid) => {
+            ensure_area_exists(conn, aid)?;
+            Some(Some(aid.to_string()))
+        }
+        None => None,
+    };
 
     let updated_project = UpdatedProject {
-        title: updated_title,
-        description: updated_description,
+        title: input.title,
+        description: input.description,
         updated_at: Utc::now().naive_utc(),
+        status: validated_status,
+        start_date: parse_project_date(input.start_date)?,
+        target_date: parse_project_date(input.target_date)?,
+        parent_id: validated_parent_id,
+        area_id: validated_area_id,
     };
 
-    match updated_tags {
+    match input.tags {
         None => {
             // 何もしない
         }
@@ -249,6 +367,18 @@ pub fn archive_project(conn: &mut SqliteConnection, project_id: &str) -> Result<
         .get_result(conn)
 }
 
+// Markdown ファイルを移したときに、保存先のパスを記録する。updated_at は変えない
+pub fn set_project_file_path(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+    path: &str,
+) -> Result<Project, Error> {
+    diesel::update(projects.find(project_id))
+        .set(file_path.eq(Some(path)))
+        .returning(Project::as_select())
+        .get_result(conn)
+}
+
 // ==============================
 // ▼ Delete
 // ==============================
@@ -265,18 +395,35 @@ pub fn soft_delete_project(
     }
 
     diesel::update(projects.find(project_id))
-        .set(DeletedProject { deleted: true })
+        .set(DeletedProject {
+            deleted: true,
+            deleted_at: Some(Utc::now().naive_utc()),
+        })
         .returning(Project::as_select())
         .get_result(conn)
 }
 
-pub fn delete_project(conn: &mut SqliteConnection, project_id: &str) -> Result<(), Error> {
-    let _exist_project = ensure_project_exists(conn, project_id)?;
-    diesel::delete(projects.find(project_id))
-        .returning(Project::as_select())
-        .get_result(conn)?;
-
-    Ok(())
+// プロジェクトを参照する行（タグ・cascade の記録）も同じトランザクションで削除する。
+// サブプロジェクトは最上位に移し、移したものを返す
+pub fn delete_project(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+) -> Result<Vec<Project>, Error> {
+    conn.transaction(|conn| {
+        let _exist_project = ensure_project_exists(conn, project_id)?;
+        diesel::delete(project_tags::table.filter(project_tags::project_id.eq(project_id)))
+            .execute(conn)?;
+        delete_cascade_changes_by_project_id(conn, project_id)?;
+        let detached = diesel::update(projects.filter(parent_id.eq(project_id)))
+            .set(parent_id.eq(None::<String>))
+            .returning(Project::as_select())
+            .get_results(conn)?;
+        diesel::delete(projects.find(project_id))
+            .returning(Project::as_select())
+            .get_result(conn)?;
+
+        Ok(detached)
+    })
 }
 
 // ==============================
@@ -303,7 +450,10 @@ pub fn restore_project(conn: &mut SqliteConnection, project_id: &str) -> Result<
     }
 
     diesel::update(projects.find(project_id))
-        .set(DeletedProject { deleted: false })
+        .set(DeletedProject {
+            deleted: false,
+            deleted_at: None,
+        })
         .returning(Project::as_select())
         .get_result(conn)
 }
@@ -320,3 +470,89 @@ pub fn ensure_project_exists(
         None => Err(Error::QueryBuilderError("Project not found".into())),
     }
 }
+
+// 指定したプロジェクトのIDに、そのサブプロジェクト（孫以下も含む）のIDを加えて返す
+pub fn get_descendant_project_ids(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+) -> Result<Vec<String>, Error> {
+    let all_projects = projects
+        .select(Project::as_select())
+        .load::<Project>(conn)?;
+
+    let mut project_ids = expand_with_descendants(&all_projects, vec![project_id.to_string()]);
+    project_ids.retain(|pid| pid != project_id);
+    Ok(project_ids)
+}
+
+// 親が含まれているプロジェクトを、増えなくなるまで追加する
+pub(crate) fn expand_with_descendants(
+    all_projects: &[Project],
+    mut ids: Vec<String>,
+) -> Vec<String> {
+    let mut index = 0;
+    while index < ids.len() {
+        let current = ids[index].clone();
+        for child in all_projects
+            .iter()
+            .filter(|p| p.parent_id.as_deref() == Some(current.as_str()))
+        {
+            if !ids.contains(&child.id) {
+                ids.push(child.id.clone());
+            }
+        }
+        index += 1;
+    }
+    ids
+}
+
+// 親に自分自身や子孫を指定すると階層が循環するため拒否する
+fn ensure_valid_parent(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+    parent_id_input: &str,
+) -> Result<(), Error> {
+    let mut current = Some(ensure_project_exists(conn, parent_id_input)?);
+
+    while let Some(ancestor) = current {
+        if ancestor.id == project_id {
+            return Err(Error::QueryBuilderError(
+                format!(
+                    "Cannot set {} as parent of {}: it would create a cycle",
+                    parent_id_input, project_id
+                )
+                .into(),
+            ));
+        }
+        current = match ancestor.parent_id.as_deref() {
+            Some(pid) => get_project_by_id(conn, pid)?,
+            None => None,
+        };
+    }
+
+    Ok(())
+}
+
+fn parse_project_status(input: &str) -> Result<ProjectStatus, Error> {
+    match input {
+        "planned" => Ok(ProjectStatus::Planned),
+        "active" => Ok(ProjectStatus::Active),
+        "on_hold" | "on-hold" => Ok(ProjectStatus::OnHold),
+        "completed" => Ok(ProjectStatus::Completed),
+        other => Err(Error::QueryBuilderError(
+            format!("Invalid project status: {}", other).into(),
+        )),
+    }
+}
+
+fn parse_project_date(input: Option<String>) -> Result<Option<NaiveDateTime>, Error> {
+    match input {
+        Some(date_str) => {
+            let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").map_err(|e| {
+                Error::QueryBuilderError(format!("Invalid date {}: {}", date_str, e).into())
+            })?;
+            Ok(date.and_hms_opt(0, 0, 0))
+        }
+        None => Ok(None),
+    }
+}
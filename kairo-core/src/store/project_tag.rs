@@ -49,6 +49,18 @@ pub fn get_project_tags_by_project_id(
     Ok(project_tag)
 }
 
+// いずれかのタグが付いているプロジェクトのID
+pub fn get_project_ids_by_tag_ids(
+    conn: &mut SqliteConnection,
+    input_tag_ids: &[String],
+) -> Result<Vec<String>, Error> {
+    project_tags::table
+        .filter(project_tags::tag_id.eq_any(input_tag_ids))
+        .select(project_tags::project_id)
+        .distinct()
+        .load::<String>(conn)
+}
+
 // ==============================
 // ▼ Delete
 // ==============================
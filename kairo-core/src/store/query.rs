@@ -0,0 +1,369 @@
+use crate::{
+    model::{Note, Project, Task},
+    query::{DateOp, Predicate, Query},
+    schema::{note_tags, notes, project_tags, projects, tags, task_tags, tasks},
+    store::*,
+};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use diesel::{SqliteConnection, prelude::*, result::Error};
+
+// 条件を絞り込みに加える。否定は「条件に合うもの以外」として扱い、値が NULL のものも残す。
+// 同じテーブルの副問い合わせは Diesel で組めないため、条件に合うIDを先に取得して除外する。
+macro_rules! apply {
+    ($conn:ident, $query:ident, $table:ident, $negated:expr, $condition:expr) => {
+        $query = if $negated {
+            let matched: Vec<String> = $table::table
+                .filter($condition)
+                .select($table::id)
+                .load($conn)?;
+            $query.filter($table::id.ne_all(matched))
+        } else {
+            $query.filter($condition)
+        }
+    };
+}
+
+// 日付の範囲は、UTC の日時の列なら local_day_range、日付だけの列なら day_range で求める
+macro_rules! apply_date {
+    ($conn:ident, $query:ident, $table:ident, $negated:expr, $column:expr, $op:expr, $range:expr) => {{
+        let (start, end) = $range;
+        match $op {
+            DateOp::On => apply!(
+                $conn,
+                $query,
+                $table,
+                $negated,
+                $column.ge(start).and($column.lt(end))
+            ),
+            DateOp::Before => apply!($conn, $query, $table, $negated, $column.lt(start)),
+            DateOp::OnOrBefore => apply!($conn, $query, $table, $negated, $column.lt(end)),
+            DateOp::After => apply!($conn, $query, $table, $negated, $column.ge(end)),
+            DateOp::OnOrAfter => apply!($conn, $query, $table, $negated, $column.ge(start)),
+        }
+    }};
+}
+
+// ==============================
+// ▼ Read / Select
+// ==============================
+
+// 対象外の条件（例：ノートに対する due:）は使わない。どの対象を検索するかは Query::entities() で決める
+pub fn query_notes(conn: &mut SqliteConnection, input_query: &Query) -> Result<Vec<Note>, Error> {
+    let mut query = notes::table.into_boxed();
+    if !input_query.has_archived() {
+        query = query.filter(notes::archived.eq(false));
+    }
+    if !input_query.has_deleted() {
+        query = query.filter(notes::deleted.eq(false));
+    }
+
+    for term in &input_query.terms {
+        let negated = term.negated;
+        match &term.predicate {
+            Predicate::NoteType(value) => {
+                apply!(conn, query, notes, negated, notes::note_type.eq(*value))
+            }
+            Predicate::SubType(Some(value)) => {
+                apply!(conn, query, notes, negated, notes::sub_type.eq(*value))
+            }
+            Predicate::SubType(None) => {
+                apply!(conn, query, notes, negated, notes::sub_type.is_null())
+            }
+            Predicate::Tag(name) => {
+                let names = expand_tag_names(conn, vec![name.clone()])?;
+                apply!(
+                    conn,
+                    query,
+                    notes,
+                    negated,
+                    notes::id.eq_any(
+                        note_tags::table
+                            .inner_join(tags::table)
+                            .filter(tags::tag_name.eq_any(names))
+                            .select(note_tags::note_id)
+                    )
+                )
+            }
+            Predicate::Project(pid) => {
+                apply!(
+                    conn,
+                    query,
+                    notes,
+                    negated,
+                    notes::project_id.eq(pid.clone())
+                )
+            }
+            Predicate::Task(tid) => {
+                apply!(conn, query, notes, negated, notes::task_id.eq(tid.clone()))
+            }
+            Predicate::Area(aid) => {
+                let ids = get_project_ids_in_area(conn, aid)?;
+                apply!(conn, query, notes, negated, notes::project_id.eq_any(ids))
+            }
+            Predicate::Title(text) => apply!(
+                conn,
+                query,
+                notes,
+                negated,
+                notes::title.like(like_pattern(text)).escape('\\')
+            ),
+            Predicate::Created(op, date) => {
+                apply_date!(
+                    conn,
+                    query,
+                    notes,
+                    negated,
+                    notes::created_at,
+                    *op,
+                    local_day_range(*date)
+                )
+            }
+            Predicate::Updated(op, date) => {
+                apply_date!(
+                    conn,
+                    query,
+                    notes,
+                    negated,
+                    notes::updated_at,
+                    *op,
+                    local_day_range(*date)
+                )
+            }
+            Predicate::Archived(flag) => {
+                apply!(conn, query, notes, negated, notes::archived.eq(*flag))
+            }
+            Predicate::Deleted(flag) => {
+                apply!(conn, query, notes, negated, notes::deleted.eq(*flag))
+            }
+            _ => {}
+        }
+    }
+
+    query
+        .order(notes::created_at.desc())
+        .select(Note::as_select())
+        .load::<Note>(conn)
+}
+
+pub fn query_tasks(conn: &mut SqliteConnection, input_query: &Query) -> Result<Vec<Task>, Error> {
+    let mut query = tasks::table.into_boxed();
+    if !input_query.has_archived() {
+        query = query.filter(tasks::archived.eq(false));
+    }
+    if !input_query.has_deleted() {
+        query = query.filter(tasks::deleted.eq(false));
+    }
+
+    for term in &input_query.terms {
+        let negated = term.negated;
+        match &term.predicate {
+            Predicate::Priority(Some(value)) => {
+                apply!(conn, query, tasks, negated, tasks::priority.eq(*value))
+            }
+            Predicate::Priority(None) => {
+                apply!(conn, query, tasks, negated, tasks::priority.is_null())
+            }
+            Predicate::Tag(name) => {
+                let names = expand_tag_names(conn, vec![name.clone()])?;
+                apply!(
+                    conn,
+                    query,
+                    tasks,
+                    negated,
+                    tasks::id.eq_any(
+                        task_tags::table
+                            .inner_join(tags::table)
+                            .filter(tags::tag_name.eq_any(names))
+                            .select(task_tags::task_id)
+                    )
+                )
+            }
+            Predicate::Project(pid) => {
+                apply!(
+                    conn,
+                    query,
+                    tasks,
+                    negated,
+                    tasks::project_id.eq(pid.clone())
+                )
+            }
+            Predicate::Area(aid) => {
+                let ids = get_project_ids_in_area(conn, aid)?;
+                apply!(conn, query, tasks, negated, tasks::project_id.eq_any(ids))
+            }
+            Predicate::Title(text) => apply!(
+                conn,
+                query,
+                tasks,
+                negated,
+                tasks::title.like(like_pattern(text)).escape('\\')
+            ),
+            Predicate::Created(op, date) => {
+                apply_date!(
+                    conn,
+                    query,
+                    tasks,
+                    negated,
+                    tasks::created_at,
+                    *op,
+                    local_day_range(*date)
+                )
+            }
+            Predicate::Updated(op, date) => {
+                apply_date!(
+                    conn,
+                    query,
+                    tasks,
+                    negated,
+                    tasks::updated_at,
+                    *op,
+                    local_day_range(*date)
+                )
+            }
+            Predicate::Due(op, date) => {
+                apply_date!(
+                    conn,
+                    query,
+                    tasks,
+                    negated,
+                    tasks::due_date,
+                    *op,
+                    day_range(*date)
+                )
+            }
+            Predicate::Archived(flag) => {
+                apply!(conn, query, tasks, negated, tasks::archived.eq(*flag))
+            }
+            Predicate::Deleted(flag) => {
+                apply!(conn, query, tasks, negated, tasks::deleted.eq(*flag))
+            }
+            _ => {}
+        }
+    }
+
+    query
+        .order(tasks::created_at.desc())
+        .select(Task::as_select())
+        .load::<Task>(conn)
+}
+
+pub fn query_projects(
+    conn: &mut SqliteConnection,
+    input_query: &Query,
+) -> Result<Vec<Project>, Error> {
+    let mut query = projects::table.into_boxed();
+    if !input_query.has_archived() {
+        query = query.filter(projects::archived.eq(false));
+    }
+    if !input_query.has_deleted() {
+        query = query.filter(projects::deleted.eq(false));
+    }
+
+    for term in &input_query.terms {
+        let negated = term.negated;
+        match &term.predicate {
+            Predicate::Status(value) => {
+                apply!(conn, query, projects, negated, projects::status.eq(*value))
+            }
+            Predicate::Tag(name) => {
+                let names = expand_tag_names(conn, vec![name.clone()])?;
+                apply!(
+                    conn,
+                    query,
+                    projects,
+                    negated,
+                    projects::id.eq_any(
+                        project_tags::table
+                            .inner_join(tags::table)
+                            .filter(tags::tag_name.eq_any(names))
+                            .select(project_tags::project_id)
+                    )
+                )
+            }
+            Predicate::Area(aid) => {
+                let ids = get_project_ids_in_area(conn, aid)?;
+                apply!(conn, query, projects, negated, projects::id.eq_any(ids))
+            }
+            Predicate::Title(text) => apply!(
+                conn,
+                query,
+                projects,
+                negated,
+                projects::title.like(like_pattern(text)).escape('\\')
+            ),
+            Predicate::Created(op, date) => {
+                apply_date!(
+                    conn,
+                    query,
+                    projects,
+                    negated,
+                    projects::created_at,
+                    *op,
+                    local_day_range(*date)
+                )
+            }
+            Predicate::Updated(op, date) => {
+                apply_date!(
+                    conn,
+                    query,
+                    projects,
+                    negated,
+                    projects::updated_at,
+                    *op,
+                    local_day_range(*date)
+                )
+            }
+            Predicate::Archived(flag) => {
+                apply!(conn, query, projects, negated, projects::archived.eq(*flag))
+            }
+            Predicate::Deleted(flag) => {
+                apply!(conn, query, projects, negated, projects::deleted.eq(*flag))
+            }
+            _ => {}
+        }
+    }
+
+    query
+        .order(projects::created_at.desc())
+        .select(Project::as_select())
+        .load::<Project>(conn)
+}
+
+// ==============================
+// ▼ Internal Common Utils
+// ==============================
+
+// その日の 0:00 と翌日の 0:00。期限のように日付だけを持つ列に使う
+pub fn day_range(date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    let start = date.and_time(NaiveTime::MIN);
+    (start, start + Duration::days(1))
+}
+
+// ローカル時刻でのその日の範囲を UTC で返す。created_at など UTC で保存した日時の列に使う
+pub fn local_day_range(date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    let (start, end) = day_range(date);
+    (local_to_utc(start), local_to_utc(end))
+}
+
+// 夏時間の切り替えで 0:00 が存在しない日は、その日の最初の時刻（1時間後）を使う
+fn local_to_utc(datetime: NaiveDateTime) -> NaiveDateTime {
+    Local
+        .from_local_datetime(&datetime)
+        .earliest()
+        .or_else(|| {
+            Local
+                .from_local_datetime(&(datetime + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|local| local.naive_utc())
+        .unwrap_or(datetime)
+}
+
+// 部分一致の LIKE パターン（% と _ は文字として扱う）
+fn like_pattern(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
@@ -0,0 +1,105 @@
+use crate::{
+    model::SavedView,
+    schema::saved_views::{self, dsl::*},
+};
+use chrono::{NaiveDateTime, Utc};
+use diesel::{SqliteConnection, prelude::*, result::Error};
+
+// ==============================
+// ▼ Structs / Create
+// ==============================
+#[derive(Insertable)]
+#[diesel(table_name = saved_views)]
+pub struct NewSavedView {
+    pub name: String,
+    pub query: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+// ==============================
+// ▼ Create / Update
+// ==============================
+// 同名のビューがあれば条件を上書きする
+pub fn save_view(
+    conn: &mut SqliteConnection,
+    input_name: &str,
+    input_query: &str,
+) -> Result<SavedView, Error> {
+    let normalized = normalize_view_name(input_name)?;
+    let now = Utc::now().naive_utc();
+
+    if get_view(conn, &normalized)?.is_some() {
+        return diesel::update(saved_views.find(&normalized))
+            .set((query.eq(input_query), updated_at.eq(now)))
+            .returning(SavedView::as_select())
+            .get_result(conn);
+    }
+
+    let new_view = NewSavedView {
+        name: normalized,
+        query: input_query.to_string(),
+        created_at: now,
+        updated_at: now,
+    };
+
+    diesel::insert_into(saved_views::table)
+        .values(&new_view)
+        .returning(SavedView::as_select())
+        .get_result(conn)
+}
+
+// ==============================
+// ▼ Read / Select
+// ==============================
+pub fn list_views(conn: &mut SqliteConnection) -> Result<Vec<SavedView>, Error> {
+    saved_views
+        .order(name.asc())
+        .select(SavedView::as_select())
+        .load(conn)
+}
+
+pub fn get_view(conn: &mut SqliteConnection, input_name: &str) -> Result<Option<SavedView>, Error> {
+    saved_views
+        .find(input_name.trim().to_lowercase())
+        .select(SavedView::as_select())
+        .first(conn)
+        .optional()
+}
+
+// ==============================
+// ▼ Delete
+// ==============================
+pub fn delete_view(conn: &mut SqliteConnection, input_name: &str) -> Result<(), Error> {
+    let normalized = input_name.trim().to_lowercase();
+    let deleted_rows = diesel::delete(saved_views.find(&normalized)).execute(conn)?;
+
+    if deleted_rows == 0 {
+        return Err(Error::QueryBuilderError(
+            format!("View not found: {}", normalized).into(),
+        ));
+    }
+    Ok(())
+}
+
+// ==============================
+// ▼ Helpers
+// ==============================
+// ビュー名は小文字で保持し、シェルで扱いやすい文字だけを許す
+fn normalize_view_name(input_name: &str) -> Result<String, Error> {
+    let normalized = input_name.trim().to_lowercase();
+    if normalized.is_empty()
+        || !normalized
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(Error::QueryBuilderError(
+            format!(
+                "Invalid view name: {} (use letters, digits, - and _)",
+                input_name
+            )
+            .into(),
+        ));
+    }
+    Ok(normalized)
+}
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+
 use crate::{
-    model::Tag,
-    schema::{note_tags, project_tags, tags, tags::dsl::*, task_tags},
+    model::{Tag, TagAssignment, TaggedEntity},
+    schema::{
+        note_tags, notes, project_tags, projects, tag_aliases, tags, tags::dsl::*, task_tags, tasks,
+    },
+    store::{create_tag_alias, get_tag_alias, get_tag_by_alias},
 };
 use chrono::{NaiveDateTime, Utc};
 use diesel::{SqliteConnection, prelude::*, result::Error};
@@ -61,10 +66,17 @@ fn generate_tag_id(conn: &mut SqliteConnection) -> Result<String, diesel::result
 }
 
 pub fn create_tag(conn: &mut SqliteConnection, input_tag_name: String) -> Result<Tag, Error> {
+    let normalized = normalize_tag_name(&input_tag_name)?;
+    if let Some(existing) = get_tag_alias(conn, &normalized)? {
+        return Err(Error::QueryBuilderError(
+            format!("{} is an alias of tag {}", existing.alias, existing.tag_id).into(),
+        ));
+    }
+
     let tag_id = generate_tag_id(conn)?;
     let new_tag = NewTag {
         id: tag_id,
-        tag_name: input_tag_name,
+        tag_name: normalized,
         created_at: Utc::now().naive_utc(),
         deleted: false,
     };
@@ -105,23 +117,29 @@ pub fn get_tag_by_id(conn: &mut SqliteConnection, tag_id: &str) -> Result<Option
     Ok(tag)
 }
 
+// タグ名が見つからなければ別名として解決する
 pub fn get_tag_by_name(
     conn: &mut SqliteConnection,
     input_tag_name: String,
 ) -> Result<Option<Tag>, Error> {
+    let normalized = normalize_tag_name(&input_tag_name)?;
     let tag = tags
-        .filter(tag_name.eq(input_tag_name))
+        .filter(tag_name.eq(&normalized))
         .select(Tag::as_select())
         .first(conn)
         .optional()?;
 
-    Ok(tag)
+    match tag {
+        Some(tag) => Ok(Some(tag)),
+        None => get_tag_by_alias(conn, &normalized),
+    }
 }
 
 pub fn get_tags_by_note_id(conn: &mut SqliteConnection, note_id: &str) -> Result<Vec<Tag>, Error> {
     let note_tags = note_tags::table
         .inner_join(tags::table.on(note_tags::tag_id.eq(tags::id)))
         .filter(note_tags::note_id.eq(note_id))
+        .filter(tags::deleted.eq(false))
         .select(tags::all_columns)
         .load::<Tag>(conn)?;
 
@@ -135,6 +153,7 @@ pub fn get_tags_by_project_id(
     let project_tags = project_tags::table
         .inner_join(tags::table.on(project_tags::tag_id.eq(tags::id)))
         .filter(project_tags::project_id.eq(project_id))
+        .filter(tags::deleted.eq(false))
         .select(tags::all_columns)
         .load::<Tag>(conn)?;
 
@@ -145,30 +164,247 @@ pub fn get_tags_by_task_id(conn: &mut SqliteConnection, task_id: &str) -> Result
     let task_tags = task_tags::table
         .inner_join(tags::table.on(task_tags::tag_id.eq(tags::id)))
         .filter(task_tags::task_id.eq(task_id))
+        .filter(tags::deleted.eq(false))
         .select(tags::all_columns)
         .load::<Tag>(conn)?;
 
     Ok(task_tags)
 }
 
+// "lang/rust" を指定すると "lang/rust" 自身と "lang/rust/async" などの子孫タグ名を返す。
+// 削除済みの子孫タグは含めないが、削除済みのタグを直接指定した場合はそのまま条件に残す。
+pub fn expand_tag_names(
+    conn: &mut SqliteConnection,
+    input_tag_names: Vec<String>,
+) -> Result<Vec<String>, Error> {
+    let all_names: Vec<String> = tags
+        .filter(deleted.eq(false))
+        .select(tag_name)
+        .load::<String>(conn)?;
+    let mut expanded = Vec::new();
+
+    for input in input_tag_names {
+        let parent = match get_tag_by_name(conn, input.clone())? {
+            Some(tag) => tag.tag_name,
+            None => normalize_tag_name(&input)?,
+        };
+        for name in &all_names {
+            if is_same_or_descendant(name, &parent) && !expanded.contains(name) {
+                expanded.push(name.clone());
+            }
+        }
+        // 存在しないタグ名も、そのまま条件に残しておく
+        if !expanded.contains(&parent) {
+            expanded.push(parent);
+        }
+    }
+
+    Ok(expanded)
+}
+
+// 指定したタグと、その子孫タグのID
+pub fn get_subtree_tag_ids(
+    conn: &mut SqliteConnection,
+    input_tag_id: &str,
+) -> Result<Vec<String>, Error> {
+    let parent = ensure_tag_exists(conn, input_tag_id)?;
+
+    Ok(tags
+        .select(Tag::as_select())
+        .load::<Tag>(conn)?
+        .into_iter()
+        .filter(|tag| is_same_or_descendant(&tag.tag_name, &parent.tag_name))
+        .map(|tag| tag.id)
+        .collect())
+}
+
+// 削除済みでないノート・タスク・プロジェクトから参照されている件数をタグIDごとに返す
+pub fn count_tag_usage(conn: &mut SqliteConnection) -> Result<HashMap<String, i64>, Error> {
+    let mut counts: HashMap<String, i64> = HashMap::new();
+
+    let note_counts: Vec<(String, i64)> = note_tags::table
+        .inner_join(notes::table)
+        .filter(notes::deleted.eq(false))
+        .group_by(note_tags::tag_id)
+        .select((note_tags::tag_id, diesel::dsl::count_star()))
+        .load(conn)?;
+    let task_counts: Vec<(String, i64)> = task_tags::table
+        .inner_join(tasks::table)
+        .filter(tasks::deleted.eq(false))
+        .group_by(task_tags::tag_id)
+        .select((task_tags::tag_id, diesel::dsl::count_star()))
+        .load(conn)?;
+    let project_counts: Vec<(String, i64)> = project_tags::table
+        .inner_join(projects::table)
+        .filter(projects::deleted.eq(false))
+        .group_by(project_tags::tag_id)
+        .select((project_tags::tag_id, diesel::dsl::count_star()))
+        .load(conn)?;
+
+    for (tid, count) in note_counts
+        .into_iter()
+        .chain(task_counts)
+        .chain(project_counts)
+    {
+        *counts.entry(tid).or_default() += count;
+    }
+    Ok(counts)
+}
+
+// 削除済みでないノート・タスク・プロジェクトに付いているタグをすべて返す
+pub fn list_tag_assignments(conn: &mut SqliteConnection) -> Result<Vec<TagAssignment>, Error> {
+    let note_rows: Vec<(String, String, NaiveDateTime)> = note_tags::table
+        .inner_join(notes::table)
+        .filter(notes::deleted.eq(false))
+        .select((note_tags::tag_id, notes::id, notes::created_at))
+        .load(conn)?;
+    let task_rows: Vec<(String, String, NaiveDateTime)> = task_tags::table
+        .inner_join(tasks::table)
+        .filter(tasks::deleted.eq(false))
+        .select((task_tags::tag_id, tasks::id, tasks::created_at))
+        .load(conn)?;
+    let project_rows: Vec<(String, String, NaiveDateTime)> = project_tags::table
+        .inner_join(projects::table)
+        .filter(projects::deleted.eq(false))
+        .select((project_tags::tag_id, projects::id, projects::created_at))
+        .load(conn)?;
+
+    let rows = [
+        (TaggedEntity::Note, note_rows),
+        (TaggedEntity::Task, task_rows),
+        (TaggedEntity::Project, project_rows),
+    ];
+    Ok(rows
+        .into_iter()
+        .flat_map(|(entity_type, rows)| {
+            rows.into_iter()
+                .map(move |(tid, entity_id, entity_created_at)| TagAssignment {
+                    tag_id: tid,
+                    entity_type,
+                    entity_id,
+                    created_at: entity_created_at,
+                })
+        })
+        .collect())
+}
+
 // ==============================
 // ▼ Update
 // ==============================
+
+// 親タグの名前を変えると、子孫タグ（"親/..."）の名前も同じように付け替える
 pub fn rename_tag(
     conn: &mut SqliteConnection,
     tag_id: &str,
     updated_tag_name: String,
 ) -> Result<Tag, Error> {
-    let _exist_tag = ensure_tag_exists(conn, tag_id)?;
+    let exist_tag = ensure_tag_exists(conn, tag_id)?;
+    let new_name = normalize_tag_name(&updated_tag_name)?;
+    let old_name = exist_tag.tag_name.clone();
 
-    let updated_tag = UpdatedTag {
-        tag_name: updated_tag_name,
-    };
+    if is_same_or_descendant(&new_name, &old_name) && new_name != old_name {
+        return Err(Error::QueryBuilderError(
+            format!("Cannot move tag {} under itself ({})", old_name, new_name).into(),
+        ));
+    }
 
-    diesel::update(tags.find(tag_id))
-        .set(updated_tag)
-        .returning(Tag::as_select())
-        .get_result(conn)
+    conn.transaction(|conn| {
+        let subtree: Vec<Tag> = tags
+            .select(Tag::as_select())
+            .load::<Tag>(conn)?
+            .into_iter()
+            .filter(|tag| is_same_or_descendant(&tag.tag_name, &old_name))
+            .collect();
+
+        for tag in &subtree {
+            let renamed = format!("{}{}", new_name, &tag.tag_name[old_name.len()..]);
+            if let Some(conflict) = get_tag_by_name(conn, renamed.clone())?
+                && conflict.id != tag.id
+            {
+                return Err(Error::QueryBuilderError(
+                    format!("Tag already exists: {}", renamed).into(),
+                ));
+            }
+            diesel::update(tags.find(&tag.id))
+                .set(UpdatedTag { tag_name: renamed })
+                .execute(conn)?;
+        }
+
+        ensure_tag_exists(conn, tag_id)
+    })
+}
+
+// from のタグを into に統合する。同じ要素に両方付いている場合は1つにまとめ、
+// from の名前は into の別名として残す。
+pub fn merge_tags(
+    conn: &mut SqliteConnection,
+    from_tag_id: &str,
+    into_tag_id: &str,
+) -> Result<Tag, Error> {
+    let from_tag = ensure_tag_exists(conn, from_tag_id)?;
+    let into_tag = ensure_tag_exists(conn, into_tag_id)?;
+
+    if from_tag.id == into_tag.id {
+        return Err(Error::QueryBuilderError(
+            "Cannot merge a tag into itself".into(),
+        ));
+    }
+
+    conn.transaction(|conn| {
+        // note_tags
+        let notes_with_into: Vec<String> = note_tags::table
+            .filter(note_tags::tag_id.eq(&into_tag.id))
+            .select(note_tags::note_id)
+            .load(conn)?;
+        diesel::delete(
+            note_tags::table
+                .filter(note_tags::tag_id.eq(&from_tag.id))
+                .filter(note_tags::note_id.eq_any(&notes_with_into)),
+        )
+        .execute(conn)?;
+        diesel::update(note_tags::table.filter(note_tags::tag_id.eq(&from_tag.id)))
+            .set(note_tags::tag_id.eq(&into_tag.id))
+            .execute(conn)?;
+
+        // task_tags
+        let tasks_with_into: Vec<String> = task_tags::table
+            .filter(task_tags::tag_id.eq(&into_tag.id))
+            .select(task_tags::task_id)
+            .load(conn)?;
+        diesel::delete(
+            task_tags::table
+                .filter(task_tags::tag_id.eq(&from_tag.id))
+                .filter(task_tags::task_id.eq_any(&tasks_with_into)),
+        )
+        .execute(conn)?;
+        diesel::update(task_tags::table.filter(task_tags::tag_id.eq(&from_tag.id)))
+            .set(task_tags::tag_id.eq(&into_tag.id))
+            .execute(conn)?;
+
+        // project_tags
+        let projects_with_into: Vec<String> = project_tags::table
+            .filter(project_tags::tag_id.eq(&into_tag.id))
+            .select(project_tags::project_id)
+            .load(conn)?;
+        diesel::delete(
+            project_tags::table
+                .filter(project_tags::tag_id.eq(&from_tag.id))
+                .filter(project_tags::project_id.eq_any(&projects_with_into)),
+        )
+        .execute(conn)?;
+        diesel::update(project_tags::table.filter(project_tags::tag_id.eq(&from_tag.id)))
+            .set(project_tags::tag_id.eq(&into_tag.id))
+            .execute(conn)?;
+
+        // from を指していた別名は into に付け替え、from 自身の名前も別名にする
+        diesel::update(tag_aliases::table.filter(tag_aliases::tag_id.eq(&from_tag.id)))
+            .set(tag_aliases::tag_id.eq(&into_tag.id))
+            .execute(conn)?;
+        diesel::delete(tags.find(&from_tag.id)).execute(conn)?;
+        create_tag_alias(conn, &from_tag.tag_name, &into_tag.id)?;
+
+        Ok(into_tag.clone())
+    })
 }
 
 // ==============================
@@ -187,6 +423,38 @@ pub fn soft_delete_tag(conn: &mut SqliteConnection, tag_id: &str) -> Result<Tag,
         .get_result(conn)
 }
 
+// タグを物理削除する。ノート・タスク・プロジェクトとの関連と別名もまとめて削除する
+pub fn purge_tag(conn: &mut SqliteConnection, tag_id: &str) -> Result<Tag, Error> {
+    let exist_tag = ensure_tag_exists(conn, tag_id)?;
+
+    conn.transaction(|conn| {
+        diesel::delete(note_tags::table.filter(note_tags::tag_id.eq(tag_id))).execute(conn)?;
+        diesel::delete(task_tags::table.filter(task_tags::tag_id.eq(tag_id))).execute(conn)?;
+        diesel::delete(project_tags::table.filter(project_tags::tag_id.eq(tag_id)))
+            .execute(conn)?;
+        diesel::delete(tag_aliases::table.filter(tag_aliases::tag_id.eq(tag_id))).execute(conn)?;
+        diesel::delete(tags.find(tag_id)).execute(conn)?;
+
+        Ok(exist_tag.clone())
+    })
+}
+
+// ==============================
+// ▼ Restore
+// ==============================
+pub fn restore_tag(conn: &mut SqliteConnection, tag_id: &str) -> Result<Tag, Error> {
+    let deleted_tag = ensure_tag_exists(conn, tag_id)?;
+
+    if !deleted_tag.deleted {
+        return Err(Error::QueryBuilderError("Tag is not deleted".into()));
+    }
+
+    diesel::update(tags.find(tag_id))
+        .set(SoftDeletedTag { deleted: false })
+        .returning(Tag::as_select())
+        .get_result(conn)
+}
+
 // ==============================
 // ▼ Internal Common Utils
 // ==============================
@@ -196,3 +464,49 @@ fn ensure_tag_exists(conn: &mut SqliteConnection, tag_id: &str) -> Result<Tag, E
         None => Err(Error::QueryBuilderError("Tag not found".into())),
     }
 }
+
+// 削除済みのタグは付け直せないようにする（復元してから使う）。
+// 途中まで保存されないよう、ノート・タスク・プロジェクトを書き込む前に確認する。
+pub fn ensure_tag_names_usable(
+    conn: &mut SqliteConnection,
+    input_tag_names: &[String],
+) -> Result<(), Error> {
+    for name in input_tag_names {
+        if let Some(tag) = get_tag_by_name(conn, name.clone())?
+            && tag.deleted
+        {
+            return Err(Error::QueryBuilderError(
+                format!(
+                    "Tag {} ({}) is deleted; restore it before using it",
+                    tag.tag_name, tag.id
+                )
+                .into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// 前後の空白とスラッシュを取り除き、空の階層（"lang//rust"）は拒否する
+pub fn normalize_tag_name(input: &str) -> Result<String, Error> {
+    let segments: Vec<&str> = input
+        .trim()
+        .trim_matches('/')
+        .split('/')
+        .map(str::trim)
+        .collect();
+
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return Err(Error::QueryBuilderError(
+            format!("Invalid tag name: {:?}", input).into(),
+        ));
+    }
+    Ok(segments.join("/"))
+}
+
+fn is_same_or_descendant(name: &str, parent: &str) -> bool {
+    name == parent
+        || name
+            .strip_prefix(parent)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
@@ -0,0 +1,115 @@
+use crate::{
+    model::{Tag, TagAlias},
+    schema::{
+        tag_aliases::{self, dsl::*},
+        tags,
+    },
+    store::*,
+};
+use chrono::{NaiveDateTime, Utc};
+use diesel::{SqliteConnection, prelude::*, result::Error};
+
+// ==============================
+// ▼ Structs / Create
+// ==============================
+#[derive(Insertable)]
+#[diesel(table_name = tag_aliases)]
+pub struct NewTagAlias {
+    pub alias: String,
+    pub tag_id: String,
+    pub created_at: NaiveDateTime,
+}
+
+// ==============================
+// ▼ Create / Insert
+// ==============================
+pub fn create_tag_alias(
+    conn: &mut SqliteConnection,
+    input_alias: &str,
+    input_tag_id: &str,
+) -> Result<TagAlias, Error> {
+    let normalized = normalize_tag_name(input_alias)?;
+
+    // 別名が既存のタグ名や別名と重なると読み替え先が曖昧になるため拒否する
+    if tags::table
+        .filter(tags::tag_name.eq(&normalized))
+        .select(tags::id)
+        .first::<String>(conn)
+        .optional()?
+        .is_some()
+    {
+        return Err(Error::QueryBuilderError(
+            format!("A tag named {} already exists", normalized).into(),
+        ));
+    }
+    if let Some(existing) = get_tag_alias(conn, &normalized)? {
+        return Err(Error::QueryBuilderError(
+            format!(
+                "Alias {} already points to {}",
+                existing.alias, existing.tag_id
+            )
+            .into(),
+        ));
+    }
+
+    let new_alias = NewTagAlias {
+        alias: normalized,
+        tag_id: input_tag_id.to_string(),
+        created_at: Utc::now().naive_utc(),
+    };
+
+    diesel::insert_into(tag_aliases::table)
+        .values(&new_alias)
+        .returning(TagAlias::as_select())
+        .get_result(conn)
+}
+
+// ==============================
+// ▼ Read / Select
+// ==============================
+pub fn list_tag_aliases(conn: &mut SqliteConnection) -> Result<Vec<(TagAlias, Tag)>, Error> {
+    tag_aliases
+        .inner_join(tags::table)
+        .order(alias.asc())
+        .select((TagAlias::as_select(), Tag::as_select()))
+        .load(conn)
+}
+
+pub fn get_tag_alias(
+    conn: &mut SqliteConnection,
+    input_alias: &str,
+) -> Result<Option<TagAlias>, Error> {
+    tag_aliases
+        .find(input_alias)
+        .select(TagAlias::as_select())
+        .first(conn)
+        .optional()
+}
+
+// 別名から正式なタグを引く
+pub fn get_tag_by_alias(
+    conn: &mut SqliteConnection,
+    input_alias: &str,
+) -> Result<Option<Tag>, Error> {
+    tag_aliases
+        .inner_join(tags::table)
+        .filter(alias.eq(input_alias))
+        .select(Tag::as_select())
+        .first(conn)
+        .optional()
+}
+
+// ==============================
+// ▼ Delete
+// ==============================
+pub fn delete_tag_alias(conn: &mut SqliteConnection, input_alias: &str) -> Result<(), Error> {
+    let normalized = normalize_tag_name(input_alias)?;
+    let deleted_rows = diesel::delete(tag_aliases.find(&normalized)).execute(conn)?;
+
+    if deleted_rows == 0 {
+        return Err(Error::QueryBuilderError(
+            format!("Alias not found: {}", normalized).into(),
+        ));
+    }
+    Ok(())
+}
@@ -1,5 +1,7 @@
+use std::collections::HashMap;
+
 use crate::{
-    model::{Task, TaskPriority},
+    model::{NewTaskInput, TagFilter, Task, TaskPriority},
     schema::{
         tags, task_tags,
         tasks::{self, dsl::*},
@@ -41,22 +43,39 @@ pub struct UpdatedTask {
     pub project_id: Option<String>,
 }
 
+// ==============================
+// ▼ Structs / Replace
+// ==============================
+// 取り込み時に使う。None の項目は「変更なし」ではなく NULL で上書きする
+#[derive(AsChangeset)]
+#[diesel(table_name = tasks, treat_none_as_null = true)]
+pub struct ReplacedTask {
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<NaiveDateTime>,
+    pub updated_at: NaiveDateTime,
+    pub project_id: Option<String>,
+}
+
 // ==============================
 // ▼ Structs / Archive
 // ==============================
 #[derive(AsChangeset)]
-#[diesel(table_name = tasks)]
+#[diesel(table_name = tasks, treat_none_as_null = true)]
 pub struct ArchivedTask {
     pub archived: bool,
+    pub completed_at: Option<NaiveDateTime>,
 }
 
 // ==============================
 // ▼ Structs / SoftDelete
 // ==============================
 #[derive(AsChangeset)]
-#[diesel(table_name = tasks)]
+#[diesel(table_name = tasks, treat_none_as_null = true)]
 pub struct DeletedTask {
     pub deleted: bool,
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 // ==============================
@@ -84,40 +103,68 @@ fn generate_task_id(conn: &mut SqliteConnection) -> Result<String, diesel::resul
     Ok(next_id)
 }
 
+// "task-001" 形式のIDかどうか
+pub fn is_task_id(input: &str) -> bool {
+    input
+        .strip_prefix("task-")
+        .is_some_and(|num| num.len() >= 3 && num.chars().all(|c| c.is_ascii_digit()))
+}
+
 pub fn create_task(
     conn: &mut SqliteConnection,
     input_title: String,
-    input_description: Option<String>,
-    input_task_priority: Option<String>,
-    input_due_date: Option<String>,
-    input_project_id: Option<String>,
-    input_tag_names: Option<Vec<String>>,
+    input: NewTaskInput,
 ) -> Result<Task, Error> {
     let task_id = generate_task_id(conn)?;
-    let validated_task_priority = parse_task_priority(input_task_priority)?;
-    let parsed_due_date = parse_due_date(input_due_date)
+    create_task_with_id(conn, task_id, input_title, input)
+}
+
+// ID を指定して作成する（取り込み元のIDを引き継ぐときに使う）
+pub fn create_task_with_id(
+    conn: &mut SqliteConnection,
+    task_id: String,
+    input_title: String,
+    input: NewTaskInput,
+) -> Result<Task, Error> {
+    if !is_task_id(&task_id) {
+        return Err(Error::QueryBuilderError(
+            format!("Invalid task ID: {} (expected task-001 form)", task_id).into(),
+        ));
+    }
+    if get_task_by_id(conn, &task_id)?.is_some() {
+        return Err(Error::QueryBuilderError(
+            format!("Task ID already in use: {}", task_id).into(),
+        ));
+    }
+
+    let validated_task_priority = parse_task_priority(input.priority)?;
+    let parsed_due_date = parse_due_date(input.due_date)
         .map_err(|e| Error::QueryBuilderError(format!("Invalid due_date: {}", e).into()))?;
 
     let new_task = NewTask {
         id: task_id,
         title: input_title,
-        description: input_description,
+        description: input.description,
         priority: validated_task_priority,
         due_date: parsed_due_date,
         created_at: Utc::now().naive_utc(),
         updated_at: Utc::now().naive_utc(),
         archived: false,
         deleted: false,
-        project_id: input_project_id,
+        project_id: input.project_id,
     };
 
+    if let Some(ref tag_names) = input.tags {
+        ensure_tag_names_usable(conn, tag_names)?;
+    }
+
     let task = diesel::insert_into(tasks::table)
         .values(&new_task)
         .returning(Task::as_select())
         .get_result(conn)?;
 
     // Tag と TaskTag の保存処理
-    if let Some(tag_names) = input_tag_names {
+    if let Some(tag_names) = input.tags {
         for name in tag_names {
             // タグ取得または作成
             let tag = match get_tag_by_name(conn, name.clone()) {
@@ -141,10 +188,11 @@ pub fn list_tasks(
     conn: &mut SqliteConnection,
     include_archived: Option<bool>,
     include_deleted: Option<bool>,
-    include_tags: Option<Vec<String>>,
+    include_tags: Option<TagFilter>,
     include_order: Option<String>,
     include_priority: Option<String>,
     include_project_id: Option<String>,
+    include_area_id: Option<String>,
 ) -> Result<Vec<Task>, Error> {
     let archived_flag = include_archived.unwrap_or(false);
     let deleted_flag = include_deleted.unwrap_or(false);
@@ -155,31 +203,54 @@ pub fn list_tasks(
         ));
     }
 
-    // タグフィルターがある場合：JOIN込みクエリで返す
-    if let Some(tags_filter) = include_tags {
-        let tag_filtered_query = tasks
-            .inner_join(task_tags::table.on(tasks::id.eq(task_tags::task_id)))
-            .inner_join(tags::table.on(tags::id.eq(task_tags::tag_id)))
-            .filter(tags::tag_name.eq_any(tags_filter))
-            .filter(archived.eq(archived_flag))
-            .filter(deleted.eq(deleted_flag))
-            .select(Task::as_select())
-            .distinct();
-
-        let ordered_query = match include_order.as_deref() {
-            Some("asc") => tag_filtered_query.order(due_date.asc()).into_boxed(),
-            Some("desc") => tag_filtered_query.order(due_date.desc()).into_boxed(),
-            _ => tag_filtered_query.order(due_date.desc()).into_boxed(), // デフォルトは降順
-        };
-
-        return Ok(ordered_query.load::<Task>(conn)?);
-    }
+    // エリアフィルター（エリア配下のプロジェクトとサブプロジェクトのタスク）
+    let area_project_ids = match include_area_id.as_deref() {
+        Some(aid) => Some(get_project_ids_in_area(conn, aid)?),
+        None => None,
+    };
 
     let mut query = tasks
         .filter(archived.eq(archived_flag))
         .filter(deleted.eq(deleted_flag))
         .into_boxed(); // 最初に into_boxed() しておく
 
+    // タグフィルター（all はすべて、any はいずれか、not はどれも付いていないもの）
+    if let Some(filter) = include_tags {
+        for tag in filter.all {
+            let names = expand_tag_names(conn, vec![tag])?;
+            query = query.filter(
+                tasks::id.eq_any(
+                    task_tags::table
+                        .inner_join(tags::table)
+                        .filter(tags::tag_name.eq_any(names))
+                        .select(task_tags::task_id),
+                ),
+            );
+        }
+        if !filter.any.is_empty() {
+            let names = expand_tag_names(conn, filter.any)?;
+            query = query.filter(
+                tasks::id.eq_any(
+                    task_tags::table
+                        .inner_join(tags::table)
+                        .filter(tags::tag_name.eq_any(names))
+                        .select(task_tags::task_id),
+                ),
+            );
+        }
+        if !filter.not.is_empty() {
+            let names = expand_tag_names(conn, filter.not)?;
+            query = query.filter(
+                tasks::id.ne_all(
+                    task_tags::table
+                        .inner_join(tags::table)
+                        .filter(tags::tag_name.eq_any(names))
+                        .select(task_tags::task_id),
+                ),
+            );
+        }
+    }
+
     // 優先度フィルター
     if let Some(priority_str) = include_priority.as_deref() {
         match priority_str {
@@ -199,6 +270,10 @@ pub fn list_tasks(
         query = query.filter(project_id.eq(pid));
     }
 
+    if let Some(ids) = area_project_ids {
+        query = query.filter(project_id.eq_any(ids));
+    }
+
     // ソート順指定
     query = match include_order.as_deref() {
         Some("asc") => query.order(created_at.asc()),
@@ -220,6 +295,86 @@ pub fn get_task_by_id(conn: &mut SqliteConnection, task_id: &str) -> Result<Opti
     Ok(task)
 }
 
+// アーカイブ済み・削除済みも含めて、プロジェクトに属するすべてのタスクを返す
+pub fn get_tasks_by_project_id(
+    conn: &mut SqliteConnection,
+    input_project_id: &str,
+) -> Result<Vec<Task>, Error> {
+    tasks
+        .filter(project_id.eq(input_project_id))
+        .order(created_at.asc())
+        .select(Task::as_select())
+        .load::<Task>(conn)
+}
+
+// 削除済みを除き、プロジェクトごとの (未完了, 完了) タスク数を返す
+pub fn count_tasks_by_project(
+    conn: &mut SqliteConnection,
+) -> Result<HashMap<String, (i64, i64)>, Error> {
+    let rows: Vec<(Option<String>, bool, i64)> = tasks
+        .filter(deleted.eq(false))
+        .filter(project_id.is_not_null())
+        .group_by((project_id, archived))
+        .select((project_id, archived, diesel::dsl::count_star()))
+        .load(conn)?;
+
+    let mut counts: HashMap<String, (i64, i64)> = HashMap::new();
+    for (pid, is_archived, count) in rows {
+        let Some(pid) = pid else { continue };
+        let entry = counts.entry(pid).or_default();
+        if is_archived {
+            entry.1 += count;
+        } else {
+            entry.0 += count;
+        }
+    }
+    Ok(counts)
+}
+
+// 削除済みを除いた、指定日が期限の未完了タスク
+pub fn get_tasks_due_on(conn: &mut SqliteConnection, date: NaiveDate) -> Result<Vec<Task>, Error> {
+    let (start, end) = day_range(date);
+    tasks
+        .filter(due_date.ge(start))
+        .filter(due_date.lt(end))
+        .filter(archived.eq(false))
+        .filter(deleted.eq(false))
+        .order(id.asc())
+        .select(Task::as_select())
+        .load(conn)
+}
+
+// 指定日（ローカル時刻）に完了（アーカイブ）したタスク
+pub fn get_tasks_completed_on(
+    conn: &mut SqliteConnection,
+    date: NaiveDate,
+) -> Result<Vec<Task>, Error> {
+    let (start, end) = local_day_range(date);
+    tasks
+        .filter(archived.eq(true))
+        .filter(deleted.eq(false))
+        .filter(completed_at.ge(start))
+        .filter(completed_at.lt(end))
+        .order(id.asc())
+        .select(Task::as_select())
+        .load(conn)
+}
+
+// 論理削除済みのタスク。before を指定すると、それより前に削除したものだけを返す
+pub fn get_deleted_tasks(
+    conn: &mut SqliteConnection,
+    before: Option<NaiveDateTime>,
+) -> Result<Vec<Task>, Error> {
+    let mut query = tasks.filter(deleted.eq(true)).into_boxed();
+    if let Some(before) = before {
+        query = query.filter(deleted_at.lt(before));
+    }
+    query
+        .order(deleted_at.asc())
+        .select(Task::as_select())
+        .load(conn)
+}
+
 // ==============================
 // ▼ Update
 // ==============================
@@ -234,6 +389,9 @@ pub fn update_task(
     updated_tags: Option<Vec<String>>,
 ) -> Result<Task, Error> {
     let _exist_task = ensure_task_exists(conn, task_id)?;
+    if let Some(ref tags) = updated_tags {
+        ensure_tag_names_usable(conn, tags)?;
+    }
     let validated_task_priority = parse_task_priority(updated_task_priority)?;
     let parsed_due_date = parse_due_date(updated_due_date)
         .map_err(|e| Error::QueryBuilderError(format!("Invalid due_date: {}", e).into()))?;
@@ -280,6 +438,36 @@ pub fn update_task(
         .get_result(conn)
 }
 
+// 項目とタグをすべて指定した内容で置き換える
+pub fn replace_task(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    replaced_task: ReplacedTask,
+    replaced_tags: Vec<String>,
+) -> Result<Task, Error> {
+    let _exist_task = ensure_task_exists(conn, task_id)?;
+    ensure_tag_names_usable(conn, &replaced_tags)?;
+
+    if let Some(ref pid) = replaced_task.project_id {
+        ensure_project_exists(conn, pid)?;
+    }
+
+    delete_task_tag_by_task_id(conn, task_id)?;
+    for tag_name in replaced_tags {
+        let tag = match get_tag_by_name(conn, tag_name.clone()) {
+            Ok(Some(existing)) => existing,
+            Ok(None) => create_tag(conn, tag_name)?,
+            Err(e) => return Err(e),
+        };
+        create_task_tag(conn, task_id, &tag.id)?;
+    }
+
+    diesel::update(tasks.find(task_id))
+        .set(replaced_task)
+        .returning(Task::as_select())
+        .get_result(conn)
+}
+
 pub fn archive_task(conn: &mut SqliteConnection, task_id: &str) -> Result<Task, Error> {
     let exist_task = ensure_task_exists(conn, task_id)?;
 
@@ -288,7 +476,22 @@ pub fn archive_task(conn: &mut SqliteConnection, task_id: &str) -> Result<Task,
     }
 
     diesel::update(tasks.find(task_id))
-        .set(ArchivedTask { archived: true })
+        .set(ArchivedTask {
+            archived: true,
+            completed_at: Some(Utc::now().naive_utc()),
+        })
+        .returning(Task::as_select())
+        .get_result(conn)
+}
+
+// Markdown ファイルを移したときに、保存先のパスを記録する。updated_at は変えない
+pub fn set_task_file_path(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    path: &str,
+) -> Result<Task, Error> {
+    diesel::update(tasks.find(task_id))
+        .set(file_path.eq(Some(path)))
         .returning(Task::as_select())
         .get_result(conn)
 }
@@ -304,18 +507,27 @@ pub fn soft_delete_task(conn: &mut SqliteConnection, task_id: &str) -> Result<Ta
     }
 
     diesel::update(tasks.find(task_id))
-        .set(DeletedTask { deleted: true })
+        .set(DeletedTask {
+            deleted: true,
+            deleted_at: Some(Utc::now().naive_utc()),
+        })
         .returning(Task::as_select())
         .get_result(conn)
 }
 
+// タスクを参照する行（タグ・時間の記録など）も同じトランザクションで削除する
 pub fn delete_task(conn: &mut SqliteConnection, task_id: &str) -> Result<(), Error> {
-    let _exist_task = ensure_task_exists(conn, task_id)?;
-    diesel::delete(tasks.find(task_id))
-        .returning(Task::as_select())
-        .get_result(conn)?;
-
-    Ok(())
+    conn.transaction(|conn| {
+        let _exist_task = ensure_task_exists(conn, task_id)?;
+        diesel::delete(task_tags::table.filter(task_tags::task_id.eq(task_id))).execute(conn)?;
+        delete_time_entries_by_task_id(conn, task_id)?;
+        delete_external_ids_by_task_id(conn, task_id)?;
+        diesel::delete(tasks.find(task_id))
+            .returning(Task::as_select())
+            .get_result(conn)?;
+
+        Ok(())
+    })
 }
 
 // ==============================
@@ -329,7 +541,10 @@ pub fn unarchive_task(conn: &mut SqliteConnection, task_id: &str) -> Result<Task
     }
 
     diesel::update(tasks.find(task_id))
-        .set(ArchivedTask { archived: false })
+        .set(ArchivedTask {
+            archived: false,
+            completed_at: None,
+        })
         .returning(Task::as_select())
         .get_result(conn)
 }
@@ -342,7 +557,10 @@ pub fn restore_task(conn: &mut SqliteConnection, task_id: &str) -> Result<Task,
     }
 
     diesel::update(tasks.find(task_id))
-        .set(DeletedTask { deleted: false })
+        .set(DeletedTask {
+            deleted: false,
+            deleted_at: None,
+        })
         .returning(Task::as_select())
         .get_result(conn)
 }
@@ -350,7 +568,7 @@ pub fn restore_task(conn: &mut SqliteConnection, task_id: &str) -> Result<Task,
 // ==============================
 // ▼ Internal Common Utils
 // ==============================
-fn ensure_task_exists(conn: &mut SqliteConnection, task_id: &str) -> Result<Task, Error> {
+pub fn ensure_task_exists(conn: &mut SqliteConnection, task_id: &str) -> Result<Task, Error> {
     match get_task_by_id(conn, task_id)? {
         Some(task) => Ok(task),
         None => Err(Error::QueryBuilderError("Task not found".into())),
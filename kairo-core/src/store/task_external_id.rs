@@ -0,0 +1,85 @@
+use crate::{
+    model::{Task, TaskExternalId},
+    schema::{
+        task_external_ids::{self, dsl::*},
+        tasks,
+    },
+};
+use chrono::{NaiveDateTime, Utc};
+use diesel::{SqliteConnection, prelude::*, result::Error};
+
+// ==============================
+// ▼ Structs / Create
+// ==============================
+#[derive(Insertable)]
+#[diesel(table_name = task_external_ids)]
+pub struct NewTaskExternalId {
+    pub source: String,
+    pub external_id: String,
+    pub task_id: String,
+    pub created_at: NaiveDateTime,
+}
+
+// ==============================
+// ▼ Create / Update
+// ==============================
+// 外部の識別子をタスクに結び付ける。すでにほかのタスクに結び付いていれば付け替える
+pub fn link_task_external_id(
+    conn: &mut SqliteConnection,
+    input_source: &str,
+    input_external_id: &str,
+    input_task_id: &str,
+) -> Result<(), Error> {
+    let new_link = NewTaskExternalId {
+        source: input_source.to_string(),
+        external_id: input_external_id.to_string(),
+        task_id: input_task_id.to_string(),
+        created_at: Utc::now().naive_utc(),
+    };
+
+    diesel::insert_into(task_external_ids::table)
+        .values(&new_link)
+        .on_conflict((source, external_id))
+        .do_update()
+        .set(task_id.eq(input_task_id))
+        .execute(conn)?;
+    Ok(())
+}
+
+// ==============================
+// ▼ Read / Select
+// ==============================
+pub fn list_task_external_ids(
+    conn: &mut SqliteConnection,
+    input_source: &str,
+) -> Result<Vec<TaskExternalId>, Error> {
+    task_external_ids
+        .filter(source.eq(input_source))
+        .select(TaskExternalId::as_select())
+        .load(conn)
+}
+
+pub fn get_task_by_external_id(
+    conn: &mut SqliteConnection,
+    input_source: &str,
+    input_external_id: &str,
+) -> Result<Option<Task>, Error> {
+    task_external_ids
+        .inner_join(tasks::table)
+        .filter(source.eq(input_source))
+        .filter(external_id.eq(input_external_id))
+        .select(Task::as_select())
+        .first(conn)
+        .optional()
+}
+
+// ==============================
+// ▼ Delete
+// ==============================
+pub fn delete_external_ids_by_task_id(
+    conn: &mut SqliteConnection,
+    input_task_id: &str,
+) -> Result<(), Error> {
+    diesel::delete(task_external_ids.filter(task_id.eq(input_task_id))).execute(conn)?;
+    Ok(())
+}
@@ -49,6 +49,18 @@ pub fn get_task_tags_by_task_id(
     Ok(task_tag)
 }
 
+// いずれかのタグが付いているタスクのID
+pub fn get_task_ids_by_tag_ids(
+    conn: &mut SqliteConnection,
+    input_tag_ids: &[String],
+) -> Result<Vec<String>, Error> {
+    task_tags::table
+        .filter(task_tags::tag_id.eq_any(input_tag_ids))
+        .select(task_tags::task_id)
+        .distinct()
+        .load::<String>(conn)
+}
+
 // ==============================
 // ▼ Delete
 // ==============================
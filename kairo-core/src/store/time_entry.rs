@@ -0,0 +1,291 @@
+use crate::{
+    model::TimeEntry,
+    schema::{
+        tasks,
+        time_entries::{self, dsl::*},
+    },
+    store::*,
+};
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use diesel::{SqliteConnection, prelude::*, result::Error};
+
+// ==============================
+// ▼ Structs / Create
+// ==============================
+#[derive(Insertable)]
+#[diesel(table_name = time_entries)]
+pub struct NewTimeEntry {
+    pub id: String,
+    pub task_id: String,
+    pub started_at: NaiveDateTime,
+    pub ended_at: Option<NaiveDateTime>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+// ==============================
+// ▼ Structs / Stop
+// ==============================
+#[derive(AsChangeset)]
+#[diesel(table_name = time_entries)]
+pub struct StoppedTimeEntry {
+    pub ended_at: Option<NaiveDateTime>,
+}
+
+// ==============================
+// ▼ Create / Insert
+// ==============================
+fn generate_time_entry_id(conn: &mut SqliteConnection) -> Result<String, diesel::result::Error> {
+    use regex::Regex;
+
+    // 時間記録IDの最大数値部分を取得
+    let all_ids: Vec<String> = time_entries.select(id).load::<String>(conn)?;
+
+    // 正規表現で "te-001" 形式の数字部分を抽出して最大値を見つける
+    let re = Regex::new(r"te-(\d{3,})").unwrap();
+    let max_num = all_ids
+        .iter()
+        .filter_map(|entry_id| {
+            re.captures(entry_id)
+                .and_then(|caps| caps.get(1)?.as_str().parse::<u32>().ok())
+        })
+        .max()
+        .unwrap_or(0);
+
+    // 次の番号を3桁ゼロ埋めで生成
+    let next_id = format!("te-{:03}", max_num + 1);
+    Ok(next_id)
+}
+
+pub fn start_timer(
+    conn: &mut SqliteConnection,
+    input_task_id: &str,
+    input_note: Option<String>,
+) -> Result<TimeEntry, Error> {
+    ensure_task_exists(conn, input_task_id)?;
+
+    // 同時に計測できるタイマーは1つだけ
+    if let Some(running) = get_running_time_entry(conn)? {
+        return Err(Error::QueryBuilderError(
+            format!(
+                "Timer is already running for task {} ({})",
+                running.task_id, running.id
+            )
+            .into(),
+        ));
+    }
+
+    let now = Utc::now().naive_utc();
+    let new_entry = NewTimeEntry {
+        id: generate_time_entry_id(conn)?,
+        task_id: input_task_id.to_string(),
+        started_at: now,
+        ended_at: None,
+        note: input_note,
+        created_at: now,
+    };
+
+    diesel::insert_into(time_entries::table)
+        .values(&new_entry)
+        .returning(TimeEntry::as_select())
+        .get_result(conn)
+}
+
+pub fn log_time(
+    conn: &mut SqliteConnection,
+    input_task_id: &str,
+    input_duration: &str,
+    input_note: Option<String>,
+) -> Result<TimeEntry, Error> {
+    ensure_task_exists(conn, input_task_id)?;
+    let minutes = parse_duration(input_duration)?;
+
+    // 記録時刻を終了時刻とみなし、そこから遡って開始時刻を算出する
+    let now = Utc::now().naive_utc();
+    let start = Duration::try_minutes(minutes)
+        .and_then(|duration| now.checked_sub_signed(duration))
+        .ok_or_else(|| {
+            Error::QueryBuilderError(format!("Duration is too long: {}", input_duration).into())
+        })?;
+    let new_entry = NewTimeEntry {
+        id: generate_time_entry_id(conn)?,
+        task_id: input_task_id.to_string(),
+        started_at: start,
+        ended_at: Some(now),
+        note: input_note,
+        created_at: now,
+    };
+
+    diesel::insert_into(time_entries::table)
+        .values(&new_entry)
+        .returning(TimeEntry::as_select())
+        .get_result(conn)
+}
+
+// ==============================
+// ▼ Read / Select
+// ==============================
+pub fn list_time_entries(
+    conn: &mut SqliteConnection,
+    include_from: Option<String>,
+    include_to: Option<String>,
+    include_project_id: Option<String>,
+) -> Result<Vec<TimeEntry>, Error> {
+    let mut query = time_entries
+        .inner_join(tasks::table)
+        .select(TimeEntry::as_select())
+        .into_boxed();
+
+    // 期間フィルター（開始日時で判定、日付はローカル時刻の日、to は当日を含む）
+    if let Some(from) = include_from.as_deref() {
+        let (from_start, _) = local_day_range(parse_report_date(from)?);
+        query = query.filter(started_at.ge(from_start));
+    }
+    if let Some(to) = include_to.as_deref() {
+        let (_, to_end) = local_day_range(parse_report_date(to)?);
+        query = query.filter(started_at.lt(to_end));
+    }
+
+    // プロジェクトIDフィルター
+    if let Some(pid) = include_project_id.as_deref() {
+        query = query.filter(tasks::project_id.eq(pid));
+    }
+
+    query.order(started_at.asc()).load::<TimeEntry>(conn)
+}
+
+pub fn get_time_entry_by_id(
+    conn: &mut SqliteConnection,
+    entry_id: &str,
+) -> Result<Option<TimeEntry>, Error> {
+    let entry = time_entries
+        .find(entry_id)
+        .select(TimeEntry::as_select())
+        .first(conn)
+        .optional()?;
+
+    Ok(entry)
+}
+
+pub fn get_running_time_entry(conn: &mut SqliteConnection) -> Result<Option<TimeEntry>, Error> {
+    let entry = time_entries
+        .filter(ended_at.is_null())
+        .select(TimeEntry::as_select())
+        .first(conn)
+        .optional()?;
+
+    Ok(entry)
+}
+
+// ==============================
+// ▼ Update
+// ==============================
+pub fn stop_timer(conn: &mut SqliteConnection) -> Result<TimeEntry, Error> {
+    let running = match get_running_time_entry(conn)? {
+        Some(entry) => entry,
+        None => return Err(Error::QueryBuilderError("No timer is running".into())),
+    };
+
+    diesel::update(time_entries.find(&running.id))
+        .set(StoppedTimeEntry {
+            ended_at: Some(Utc::now().naive_utc()),
+        })
+        .returning(TimeEntry::as_select())
+        .get_result(conn)
+}
+
+// ==============================
+// ▼ Delete
+// ==============================
+pub fn delete_time_entries_by_task_id(
+    conn: &mut SqliteConnection,
+    input_task_id: &str,
+) -> Result<(), Error> {
+    diesel::delete(time_entries.filter(task_id.eq(input_task_id))).execute(conn)?;
+    Ok(())
+}
+
+// ==============================
+// ▼ Internal Common Utils
+// ==============================
+
+// "1h30m", "2h", "45m" 形式の文字列を分に変換する
+pub fn parse_duration(input: &str) -> Result<i64, Error> {
+    use regex::Regex;
+
+    let re = Regex::new(r"^(?:(\d+)h)?(?:(\d+)m)?$").unwrap();
+    let invalid = || Error::QueryBuilderError(format!("Invalid duration: {}", input).into());
+
+    let caps = re.captures(input.trim()).ok_or_else(invalid)?;
+    if caps.get(1).is_none() && caps.get(2).is_none() {
+        return Err(invalid());
+    }
+    // 桁が多すぎる値は、0 として扱わずにエラーにする
+    let number = |index: usize| match caps.get(index) {
+        Some(m) => m.as_str().parse::<i64>().map_err(|_| invalid()),
+        None => Ok(0),
+    };
+
+    let (hours, minutes) = (number(1)?, number(2)?);
+
+    let total = hours
+        .checked_mul(60)
+        .and_then(|h| h.checked_add(minutes))
+        .ok_or_else(invalid)?;
+    if total <= 0 {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+fn parse_report_date(input: &str) -> Result<NaiveDate, Error> {
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map_err(|e| Error::QueryBuilderError(format!("Invalid date {}: {}", input, e).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hours_and_minutes() {
+        assert_eq!(parse_duration("1h30m").unwrap(), 90);
+        assert_eq!(parse_duration("2h").unwrap(), 120);
+        assert_eq!(parse_duration("45m").unwrap(), 45);
+        assert_eq!(parse_duration(" 90m ").unwrap(), 90);
+    }
+
+    #[test]
+    fn rejects_empty_and_zero_durations() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("0h0m").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        assert!(parse_duration("90").is_err());
+        assert!(parse_duration("30m1h").is_err());
+        assert!(parse_duration("1.5h").is_err());
+        assert!(parse_duration("-5m").is_err());
+    }
+
+    #[test]
+    fn rejects_overflowing_durations() {
+        // i64 に収まらない桁数
+        assert!(parse_duration("99999999999999999999m").is_err());
+        // 分に直すと i64 を超える
+        assert!(parse_duration(&format!("{}h", i64::MAX / 60 + 1)).is_err());
+        assert!(parse_duration(&format!("{}h59m", i64::MAX / 60)).is_err());
+    }
+
+    #[test]
+    fn parses_report_dates() {
+        assert_eq!(
+            parse_report_date("2025-01-31").unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()
+        );
+        assert!(parse_report_date("2025-02-30").is_err());
+        assert!(parse_report_date("today").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use crate::{
+    model::{Area, AreaRollup, Project, ProjectTreeNode},
+    store::*,
+    util::{print_areas_as_table, print_project_tree},
+};
+use anyhow::Ok;
+use diesel::SqliteConnection;
+
+pub fn handle_create_area(
+    conn: &mut SqliteConnection,
+    title: String,
+    description: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let area = create_area(conn, title, description)?;
+
+    println!("Created area: {:?}", area.id);
+    Ok(())
+}
+
+pub fn handle_list_areas(
+    conn: &mut SqliteConnection,
+    include_archived: Option<bool>,
+    include_deleted: Option<bool>,
+) -> Result<(), anyhow::Error> {
+    let areas = list_areas(conn, include_archived, include_deleted)?;
+    let rollups: Vec<AreaRollup> = build_area_rollups(conn, areas)?
+        .into_iter()
+        .filter(|rollup| rollup.area.is_some())
+        .collect();
+
+    print_areas_as_table(&rollups);
+    Ok(())
+}
+
+pub fn handle_get_area(conn: &mut SqliteConnection, area_id: String) -> Result<(), anyhow::Error> {
+    let area = get_area_by_id(conn, &area_id)?;
+    match area {
+        Some(exist) => {
+            println!("{exist}");
+            let rollups: Vec<AreaRollup> = build_area_rollups(conn, vec![exist])?
+                .into_iter()
+                .filter(|rollup| rollup.area.is_some())
+                .collect();
+            print_project_tree(&rollups);
+        }
+        None => {
+            println!("Area not found");
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_update_area(
+    conn: &mut SqliteConnection,
+    area_id: String,
+    title: Option<String>,
+    description: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let area = update_area(conn, &area_id, title, description)?;
+
+    println!("Updated area: {:?}", area.id);
+    Ok(())
+}
+
+pub fn handle_archive_area(
+    conn: &mut SqliteConnection,
+    area_id: String,
+) -> Result<(), anyhow::Error> {
+    let area = archive_area(conn, &area_id)?;
+
+    println!("Archived area: {:?}", area.id);
+    Ok(())
+}
+
+pub fn handle_delete_area(
+    conn: &mut SqliteConnection,
+    area_id: String,
+) -> Result<(), anyhow::Error> {
+    let area = soft_delete_area(conn, &area_id)?;
+
+    println!("Deleted area: {:?}", area.id);
+    Ok(())
+}
+
+pub fn handle_unarchive_area(
+    conn: &mut SqliteConnection,
+    area_id: String,
+) -> Result<(), anyhow::Error> {
+    let area = unarchive_area(conn, &area_id)?;
+
+    println!("Unarchived area: {:?}", area.id);
+    Ok(())
+}
+
+pub fn handle_restore_area(
+    conn: &mut SqliteConnection,
+    area_id: String,
+) -> Result<(), anyhow::Error> {
+    let area = restore_area(conn, &area_id)?;
+
+    println!("Restored area: {:?}", area.id);
+    Ok(())
+}
+
+// アクティブなプロジェクトをエリアごとにツリーへ組み立て、タスク・ノート数を集計する。
+// 渡されたエリアに属さないルートプロジェクトは、area が None のグループにまとめる。
+pub fn build_area_rollups(
+    conn: &mut SqliteConnection,
+    areas: Vec<Area>,
+) -> Result<Vec<AreaRollup>, anyhow::Error> {
+    let projects = list_projects(
+        conn,
+        Some(false),
+        Some(false),
+        None,
+        Some("asc".into()),
+        None,
+    )?;
+    let task_counts = count_tasks_by_project(conn)?;
+    let note_counts = count_notes_by_project(conn)?;
+
+    let project_ids: Vec<&str> = projects.iter().map(|p| p.id.as_str()).collect();
+    let mut children: HashMap<String, Vec<Project>> = HashMap::new();
+    let mut roots: Vec<Project> = Vec::new();
+    for project in &projects {
+        // 親がアーカイブ・削除済みのプロジェクトはルートとして扱う
+        match project.parent_id.as_deref() {
+            Some(pid) if project_ids.contains(&pid) => children
+                .entry(pid.to_string())
+                .or_default()
+                .push(project.clone()),
+            _ => roots.push(project.clone()),
+        }
+    }
+
+    let build = |project: Project| build_tree_node(project, &children, &task_counts, &note_counts);
+
+    let mut rollups: Vec<AreaRollup> = areas
+        .into_iter()
+        .map(|area| AreaRollup {
+            area: Some(area),
+            projects: Vec::new(),
+        })
+        .collect();
+    let mut unassigned = AreaRollup {
+        area: None,
+        projects: Vec::new(),
+    };
+
+    for root in roots {
+        let position = rollups.iter().position(|rollup| {
+            rollup.area.as_ref().map(|a| a.id.as_str()) == root.area_id.as_deref()
+        });
+        match position {
+            Some(index) => rollups[index].projects.push(build(root)),
+            None => unassigned.projects.push(build(root)),
+        }
+    }
+
+    if !unassigned.projects.is_empty() {
+        rollups.push(unassigned);
+    }
+    Ok(rollups)
+}
+
+fn build_tree_node(
+    project: Project,
+    children: &HashMap<String, Vec<Project>>,
+    task_counts: &HashMap<String, (i64, i64)>,
+    note_counts: &HashMap<String, i64>,
+) -> ProjectTreeNode {
+    let child_nodes: Vec<ProjectTreeNode> = children
+        .get(&project.id)
+        .map(|list| {
+            list.iter()
+                .map(|child| build_tree_node(child.clone(), children, task_counts, note_counts))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (open, completed) = task_counts.get(&project.id).copied().unwrap_or((0, 0));
+    let notes = note_counts.get(&project.id).copied().unwrap_or(0);
+
+    ProjectTreeNode {
+        open_tasks: open + child_nodes.iter().map(|c| c.open_tasks).sum::<i64>(),
+        completed_tasks: completed + child_nodes.iter().map(|c| c.completed_tasks).sum::<i64>(),
+        notes: notes + child_nodes.iter().map(|c| c.notes).sum::<i64>(),
+        project,
+        children: child_nodes,
+    }
+}
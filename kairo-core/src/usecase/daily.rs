@@ -0,0 +1,149 @@
+use crate::{
+    config::AppConfig,
+    model::{Note, Task},
+    store::*,
+    usecase::write_note_markdown,
+    util::{find_template, markdown_path, parse_markdown, template_body, upsert_generated_section},
+};
+use anyhow::Ok;
+use chrono::{Duration, Local, NaiveDate};
+use diesel::SqliteConnection;
+
+const DAILY_PLACEHOLDERS: &[&str] = &["id", "title", "date", "weekday", "previous"];
+
+// その日のデイリーノートを開く（なければ作る）。
+// 完了・期限のタスクとその日に作ったノートの一覧は、実行のたびに生成セクションへ書き直す。
+pub fn handle_daily(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    date: Option<String>,
+    template: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let date = parse_daily_date(date.as_deref())?;
+    let note_id = daily_note_id(date);
+    let dir = &config.paths.notes_dir;
+
+    let (note, body) = match get_note_by_id(conn, &note_id)? {
+        Some(note) if note.deleted => {
+            return Err(anyhow::anyhow!(
+                "Daily note {} is deleted; restore it before opening it",
+                note.id
+            ));
+        }
+        Some(note) => {
+            let body = parse_markdown(&note, dir)
+                .map(|contents| contents.1)
+                .unwrap_or_else(|_| format!("\n\n## {}\n", note.title));
+            println!("Opened daily note {}", note.id);
+            (note, body)
+        }
+        None => create_daily(config, conn, date, template)?,
+    };
+
+    let section = build_daily_section(conn, date)?;
+    let body = upsert_generated_section(&body, "daily", &section);
+    write_note_markdown(config, conn, &note, Some(body.clone()))?;
+
+    // 書き込みでレイアウトに合わせて移動している場合があるので、保存先を読み直す
+    let note = get_note_by_id(conn, &note.id)?.unwrap_or(note);
+    println!("{}", markdown_path(dir, &note).display());
+    println!("{}", body.trim());
+    Ok(())
+}
+
+fn create_daily(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    date: NaiveDate,
+    template: Option<String>,
+) -> Result<(Note, String), anyhow::Error> {
+    let template = find_template(config, template.as_deref(), &["daily.md".to_string()])?;
+    if let Some(template) = &template {
+        template.check(DAILY_PLACEHOLDERS)?;
+    }
+
+    let title = format!("{} ({})", date.format("%Y-%m-%d"), date.format("%a"));
+    let previous = get_previous_daily_note(conn, date)?;
+    let note = create_daily_note(conn, date, title)?;
+
+    // 前のデイリーノート（間の日が空いていれば直近のもの）へリンクする
+    if let Some(previous) = &previous {
+        create_link(
+            conn,
+            note.id.clone(),
+            previous.id.clone(),
+            "related".to_string(),
+        )?;
+    }
+
+    let body = match template {
+        Some(template) => template_body(&template.render(&[
+            ("id", note.id.clone()),
+            ("title", note.title.clone()),
+            ("date", date.format("%Y-%m-%d").to_string()),
+            ("weekday", date.format("%A").to_string()),
+            (
+                "previous",
+                previous.as_ref().map(|p| p.id.clone()).unwrap_or_default(),
+            ),
+        ])),
+        None => {
+            let mut body = format!("\n\n## {}\n", note.title);
+            if let Some(previous) = &previous {
+                body.push_str(&format!("\nPrevious: {}\n", previous.id));
+            }
+            body
+        }
+    };
+
+    println!("Created daily note {}", note.id);
+    Ok((note, body))
+}
+
+fn build_daily_section(
+    conn: &mut SqliteConnection,
+    date: NaiveDate,
+) -> Result<String, anyhow::Error> {
+    let completed = get_tasks_completed_on(conn, date)?;
+    let due = get_tasks_due_on(conn, date)?;
+    let created = get_notes_created_on(conn, date)?;
+
+    let mut lines = vec!["### Tasks completed".to_string(), String::new()];
+    lines.extend(task_lines(&completed, "x"));
+    lines.extend(["".to_string(), "### Tasks due".to_string(), String::new()]);
+    lines.extend(task_lines(&due, " "));
+    lines.extend([
+        "".to_string(),
+        "### Notes created".to_string(),
+        String::new(),
+    ]);
+    if created.is_empty() {
+        lines.push("- (none)".to_string());
+    }
+    for note in created {
+        lines.push(format!("- {} {}", note.id, note.title));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn task_lines(tasks: &[Task], mark: &str) -> Vec<String> {
+    if tasks.is_empty() {
+        return vec!["- (none)".to_string()];
+    }
+    tasks
+        .iter()
+        .map(|task| format!("- [{}] {} {}", mark, task.id, task.title))
+        .collect()
+}
+
+// YYYY-MM-DD のほか today / yesterday / tomorrow を受け付ける
+fn parse_daily_date(input: Option<&str>) -> Result<NaiveDate, anyhow::Error> {
+    let today = Local::now().date_naive();
+    match input {
+        None | Some("today") => Ok(today),
+        Some("yesterday") => Ok(today - Duration::days(1)),
+        Some("tomorrow") => Ok(today + Duration::days(1)),
+        Some(other) => NaiveDate::parse_from_str(other, "%Y-%m-%d")
+            .map_err(|_| anyhow::anyhow!("Invalid date: {} (use YYYY-MM-DD)", other)),
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    config::AppConfig,
+    exchange::{ImportedStatus, ImportedTask, slug_token},
+    model::{NewProjectInput, NewTaskInput, Task},
+    store::*,
+    usecase::{write_project_markdown, write_task_markdown},
+    util::markdown_path,
+};
+use anyhow::Ok;
+use chrono::Utc;
+use diesel::SqliteConnection;
+
+// エクスポート対象（削除済みを除く全タスク）をタグ名付きで取得する
+pub fn load_exportable_tasks(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<(Task, Vec<String>)>, anyhow::Error> {
+    let mut tasks = list_tasks(conn, Some(false), Some(false), None, None, None, None, None)?;
+    tasks.extend(list_tasks(
+        conn,
+        Some(true),
+        Some(false),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?);
+
+    let mut items = Vec::new();
+    for task in tasks {
+        let tags = get_tags_by_task_id(conn, &task.id)?;
+        let tag_names = tags.into_iter().map(|t| t.tag_name).collect();
+        items.push((task, tag_names));
+    }
+    Ok(items)
+}
+
+// 取り込んだタスクを ID または外部の識別子が一致すれば更新、なければ新規作成する。
+// 取り込み元にない項目（説明・期限・優先度など）は空にして、取り込み元の内容に揃える。
+// 戻り値の bool は新規作成したかどうか。
+pub fn upsert_imported_task(
+    conn: &mut SqliteConnection,
+    imported: &ImportedTask,
+) -> Result<(Task, bool), anyhow::Error> {
+    let project_id = match (&imported.project_id, &imported.project_name) {
+        (Some(pid), _) => get_project_by_id(conn, pid)?.map(|p| p.id),
+        (None, Some(name)) => Some(find_or_create_project(conn, name)?),
+        (None, None) => None,
+    };
+
+    let existing = match (&imported.id, &imported.external_id) {
+        (Some(tid), _) => get_task_by_id(conn, tid)?,
+        (None, Some(external)) => get_task_by_external_id(conn, external.source, &external.id)?,
+        (None, None) => None,
+    };
+    let created = existing.is_none();
+
+    // 新規の場合はまず空のタスクを作り、以降は既存のタスクと同じく丸ごと置き換える
+    let task_id = match existing {
+        Some(exist) => exist.id,
+        // 取り込み元のIDが空いていればそのまま使い、ファイル間で同じIDを保つ
+        None => match imported.id.as_deref().filter(|tid| is_task_id(tid)) {
+            Some(tid) => {
+                create_task_with_id(
+                    conn,
+                    tid.to_string(),
+                    imported.title.clone(),
+                    NewTaskInput::default(),
+                )?
+                .id
+            }
+            None => create_task(conn, imported.title.clone(), NewTaskInput::default())?.id,
+        },
+    };
+
+    let replaced_task = ReplacedTask {
+        title: imported.title.clone(),
+        description: imported.description.clone(),
+        priority: imported.priority,
+        due_date: imported.due.and_then(|d| d.and_hms_opt(0, 0, 0)),
+        updated_at: Utc::now().naive_utc(),
+        project_id,
+    };
+    let mut task = replace_task(conn, &task_id, replaced_task, imported.tags.clone())?;
+
+    if let Some(external) = &imported.external_id {
+        link_task_external_id(conn, external.source, &external.id, &task.id)?;
+    }
+
+    // ステータスを archived / deleted に反映する。
+    // 完了・未完了のタスクは削除済みのままにせず、ゴミ箱から戻してから揃える
+    match imported.status {
+        Some(ImportedStatus::Completed) => {
+            if task.deleted {
+                task = restore_task(conn, &task.id)?;
+            }
+            if !task.archived {
+                task = archive_task(conn, &task.id)?;
+            }
+        }
+        Some(ImportedStatus::Pending) => {
+            if task.deleted {
+                task = restore_task(conn, &task.id)?;
+            }
+            if task.archived {
+                task = unarchive_task(conn, &task.id)?;
+            }
+        }
+        Some(ImportedStatus::Deleted) if !task.deleted => {
+            task = soft_delete_task(conn, &task.id)?;
+        }
+        _ => {}
+    }
+
+    Ok((task, created))
+}
+
+// プロジェクトをID・タイトル・スラッグの順で探し、見つからなければ作成する
+pub fn find_or_create_project(
+    conn: &mut SqliteConnection,
+    name: &str,
+) -> Result<String, anyhow::Error> {
+    if let Some(project) = get_project_by_id(conn, name)? {
+        return Ok(project.id);
+    }
+
+    let mut projects = list_projects(conn, Some(false), Some(false), None, None, None)?;
+    projects.extend(list_projects(
+        conn,
+        Some(true),
+        Some(false),
+        None,
+        None,
+        None,
+    )?);
+
+    let slug = slug_token(name);
+    if let Some(project) = projects
+        .iter()
+        .find(|p| p.title == name || slug_token(&p.title).eq_ignore_ascii_case(&slug))
+    {
+        return Ok(project.id.clone());
+    }
+
+    let project = create_project(conn, name.to_string(), NewProjectInput::default())?;
+    println!("Created project: {:?} ({})", project.title, project.id);
+    Ok(project.id)
+}
+
+// 取り込んだタスクの Markdown を書き出す。
+// 取り込み時に作成されたプロジェクトで Markdown がまだないものも併せて書き出す。
+pub fn write_imported_markdown(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    imported: &[(Task, Option<String>)],
+) {
+    for (task, body) in imported {
+        if let Err(e) = write_task_markdown(config, conn, task, body.clone()) {
+            eprintln!("Failed to write task: {}", e)
+        }
+
+        let Some(pid) = task.project_id.as_deref() else {
+            continue;
+        };
+        let project = get_project_by_id(conn, pid).unwrap_or_else(|e| {
+            eprintln!("Failed to load project: {}", e);
+            None
+        });
+        let project = project.filter(|p| !markdown_path(&config.paths.projects_dir, p).exists());
+        if let Some(project) = project
+            && let Err(e) = write_project_markdown(config, conn, &project, None)
+        {
+            eprintln!("Failed to write project: {}", e)
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::fs;
+
+use crate::{
+    config::AppConfig,
+    exchange::{IcalComponent, parse_ical, tasks_to_ical},
+    usecase::{load_exportable_tasks, upsert_imported_task, write_imported_markdown},
+};
+use anyhow::{Ok, anyhow};
+use diesel::{Connection, SqliteConnection};
+
+pub fn handle_export_ical(
+    conn: &mut SqliteConnection,
+    output: Option<String>,
+    component: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let component = match component.as_deref() {
+        Some("todo") | None => IcalComponent::Todo,
+        Some("event") => IcalComponent::Event,
+        Some(other) => return Err(anyhow!("Invalid component: {}", other)),
+    };
+
+    // 完了扱い（アーカイブ済み）のタスクも STATUS:COMPLETED として出力する
+    let items = load_exportable_tasks(conn)?;
+    let calendar = tasks_to_ical(&items, component);
+
+    match output {
+        Some(path) => {
+            fs::write(&path, calendar)?;
+            println!("✅ Exported {} tasks to {}", items.len(), path);
+        }
+        None => print!("{}", calendar),
+    }
+    Ok(())
+}
+
+pub fn handle_import_ical(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    file: String,
+) -> Result<(), anyhow::Error> {
+    let content = fs::read_to_string(&file)?;
+    let todos = parse_ical(&content).map_err(|e| anyhow!("Failed to parse {}: {}", file, e))?;
+
+    let mut created = 0;
+
+    // DB への反映はまとめて1トランザクションで行い、Markdown はコミット後に書き出す
+    let imported = conn.transaction::<_, anyhow::Error, _>(|conn| {
+        let mut imported = Vec::new();
+
+        for todo in todos {
+            let Some(item) = todo.to_imported() else {
+                eprintln!("Skipped an entry without SUMMARY: {:?}", todo.uid);
+                continue;
+            };
+
+            let (task, is_new) = upsert_imported_task(conn, &item)?;
+            if is_new {
+                created += 1;
+            }
+            imported.push((task, None));
+        }
+
+        Ok(imported)
+    })?;
+
+    write_imported_markdown(config, conn, &imported);
+
+    println!(
+        "Imported {} tasks from {} (created: {}, updated: {})",
+        imported.len(),
+        file,
+        created,
+        imported.len() - created
+    );
+    Ok(())
+}
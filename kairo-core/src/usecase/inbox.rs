@@ -0,0 +1,278 @@
+use crate::{
+    config::AppConfig,
+    model::{Note, NoteFilter},
+    store::*,
+    usecase::{handle_promote_note, write_note_markdown},
+    util::parse_markdown,
+};
+use diesel::SqliteConnection;
+use std::io::{self, BufRead, Write};
+
+// 本文のプレビューとして表示する行数
+const PREVIEW_LINES: usize = 8;
+
+#[derive(Debug, Default)]
+struct InboxSummary {
+    promoted: usize,
+    split: usize,
+    archived: usize,
+    deleted: usize,
+    skipped: usize,
+}
+
+// 未処理（アーカイブも削除もされていない）fleeting ノートを古い順に1件ずつ処理する。
+// 入力は input から1行ずつ読み、終端に達したら終了する。
+pub fn handle_inbox<R: BufRead>(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    input: &mut R,
+) -> Result<(), anyhow::Error> {
+    let notes = list_notes(
+        conn,
+        Some(false),
+        Some(false),
+        None,
+        Some("asc".to_string()),
+        None,
+        NoteFilter {
+            note_type: Some("fleeting".to_string()),
+            ..Default::default()
+        },
+    )?;
+    if notes.is_empty() {
+        println!("Inbox is empty");
+        return Ok(());
+    }
+
+    let mut summary = InboxSummary::default();
+    let total = notes.len();
+    'notes: for (index, note) in notes.into_iter().enumerate() {
+        print_inbox_note(config, conn, &note, index + 1, total)?;
+
+        loop {
+            let Some(action) = prompt(
+                input,
+                "[p]romote [s]plit [a]rchive [d]elete [t]ag [l]ink [n]ext [q]uit > ",
+            )?
+            else {
+                break 'notes;
+            };
+
+            let result = match action.as_str() {
+                "p" | "promote" => {
+                    handle_promote_note(config, conn, note.id.clone(), false, None, None).map(
+                        |_| {
+                            summary.promoted += 1;
+                            true
+                        },
+                    )
+                }
+                "s" | "split" => split_inbox_note(config, conn, &note, input, &mut summary),
+                "a" | "archive" => archive_note(conn, &note.id)
+                    .map_err(Into::into)
+                    .and_then(|archived| {
+                        write_note_markdown(config, conn, &archived, None).map(|_| archived)
+                    })
+                    .map(|archived| {
+                        println!("Archived note {}", archived.id);
+                        summary.archived += 1;
+                        true
+                    }),
+                "d" | "delete" => soft_delete_note(conn, &note.id)
+                    .map_err(Into::into)
+                    .and_then(|deleted_note| {
+                        write_note_markdown(config, conn, &deleted_note, None).map(|_| deleted_note)
+                    })
+                    .map(|deleted_note| {
+                        println!("Deleted note {}", deleted_note.id);
+                        summary.deleted += 1;
+                        true
+                    }),
+                "t" | "tag" => tag_inbox_note(config, conn, &note, input).map(|_| false),
+                "l" | "link" => link_inbox_note(config, conn, &note, input).map(|_| false),
+                "" | "n" | "next" => {
+                    summary.skipped += 1;
+                    Ok(true)
+                }
+                "q" | "quit" => break 'notes,
+                other => {
+                    println!("Unknown action: {}", other);
+                    continue;
+                }
+            };
+
+            // 失敗しても同じノートの操作を続けられるようにする
+            match result {
+                Ok(true) => break,
+                Ok(false) => {}
+                Err(e) => eprintln!("Failed: {}", e),
+            }
+        }
+    }
+
+    println!(
+        "Inbox: {} promoted, {} split, {} archived, {} deleted, {} skipped",
+        summary.promoted, summary.split, summary.archived, summary.deleted, summary.skipped
+    );
+    Ok(())
+}
+
+fn print_inbox_note(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    note: &Note,
+    position: usize,
+    total: usize,
+) -> Result<(), anyhow::Error> {
+    let tags: Vec<String> = get_tags_by_note_id(conn, &note.id)?
+        .into_iter()
+        .map(|t| t.tag_name)
+        .collect();
+
+    println!();
+    println!("[{}/{}] {} - {}", position, total, note.id, note.title);
+    println!(
+        "  SubType: {}  Tags: {}  Created: {}",
+        note.sub_type
+            .map(|s| format!("{:?}", s))
+            .unwrap_or_else(|| "-".to_string()),
+        if tags.is_empty() {
+            "-".to_string()
+        } else {
+            tags.join(", ")
+        },
+        note.created_at.format("%Y/%m/%d %H:%M")
+    );
+
+    if let Ok((_, body)) = parse_markdown(note, &config.paths.notes_dir) {
+        for line in body
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .take(PREVIEW_LINES)
+        {
+            println!("  | {}", line);
+        }
+    }
+    Ok(())
+}
+
+// None は入力の終端
+fn prompt<R: BufRead>(input: &mut R, message: &str) -> Result<Option<String>, anyhow::Error> {
+    print!("{}", message);
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        println!();
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+fn split_inbox_note<R: BufRead>(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    note: &Note,
+    input: &mut R,
+    summary: &mut InboxSummary,
+) -> Result<bool, anyhow::Error> {
+    let title = prompt(input, &format!("Title [{}]: ", note.title))?.unwrap_or_default();
+    let link_type =
+        prompt(input, "Link type (structure/reference) [reference]: ")?.unwrap_or_default();
+
+    handle_promote_note(
+        config,
+        conn,
+        note.id.clone(),
+        true,
+        Some(title).filter(|t| !t.is_empty()),
+        Some(link_type).filter(|t| !t.is_empty()),
+    )?;
+    summary.split += 1;
+
+    // 内容を移したら元の fleeting ノートは片付けることが多い
+    let answer = prompt(input, "Archive the fleeting note? [Y/n]: ")?.unwrap_or_default();
+    if answer.is_empty() || answer.eq_ignore_ascii_case("y") {
+        let archived = archive_note(conn, &note.id)?;
+        write_note_markdown(config, conn, &archived, None)?;
+        println!("Archived note {}", note.id);
+        summary.archived += 1;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+// 入力したタグを既存のタグに追加する
+fn tag_inbox_note<R: BufRead>(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    note: &Note,
+    input: &mut R,
+) -> Result<(), anyhow::Error> {
+    let answer = prompt(input, "Tags to add (comma separated): ")?.unwrap_or_default();
+    let added: Vec<String> = answer
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect();
+    if added.is_empty() {
+        return Ok(());
+    }
+
+    let mut tag_names: Vec<String> = get_tags_by_note_id(conn, &note.id)?
+        .into_iter()
+        .map(|t| t.tag_name)
+        .collect();
+    for tag in added {
+        if !tag_names.contains(&tag) {
+            tag_names.push(tag);
+        }
+    }
+
+    let updated = update_note(
+        conn,
+        &note.id,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(tag_names.clone()),
+    )?;
+    write_note_markdown(config, conn, &updated, None)?;
+    println!("Tags: {}", tag_names.join(", "));
+    Ok(())
+}
+
+fn link_inbox_note<R: BufRead>(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    note: &Note,
+    input: &mut R,
+) -> Result<(), anyhow::Error> {
+    let to_id = prompt(input, "Link to note ID: ")?.unwrap_or_default();
+    if to_id.is_empty() {
+        return Ok(());
+    }
+    if get_note_by_id(conn, &to_id)?.is_none() {
+        return Err(anyhow::anyhow!("Note not found: {}", to_id));
+    }
+    let link_type = prompt(
+        input,
+        "Link type (structure/reference/support/related/refute) [related]: ",
+    )?
+    .unwrap_or_default();
+    let link_type = if link_type.is_empty() {
+        "related".to_string()
+    } else {
+        link_type
+    };
+
+    let link = create_link(conn, note.id.clone(), to_id, link_type)?;
+    if let Some(from_note) = get_note_by_id(conn, &link.from_id)? {
+        write_note_markdown(config, conn, &from_note, None)?;
+    }
+    println!("Linked {} -> {} ({})", link.from_id, link.to_id, link.id);
+    Ok(())
+}
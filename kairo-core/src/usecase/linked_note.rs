@@ -0,0 +1,45 @@
+use crate::{
+    config::AppConfig,
+    store::{linked_note::*, note::get_note_by_id},
+    usecase::write_note_markdown,
+};
+use anyhow::Ok;
+use diesel::SqliteConnection;
+
+// リンクはリンク元ノートの front matter に書き出すので、作成・削除のたびに書き直す
+pub fn handle_create_link(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    from_id: String,
+    to_id: String,
+    link_type: String,
+) -> Result<(), anyhow::Error> {
+    let link = create_link(conn, from_id, to_id, link_type)?;
+    sync_link_source(config, conn, &link.from_id);
+
+    println!("{:?}", link);
+    Ok(())
+}
+
+pub fn handle_delete_link(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    link_id: String,
+) -> Result<(), anyhow::Error> {
+    let link = soft_delete_link(conn, &link_id)?;
+    sync_link_source(config, conn, &link.from_id);
+
+    println!("{:?}", link);
+    Ok(())
+}
+
+fn sync_link_source(config: &AppConfig, conn: &mut SqliteConnection, note_id: &str) {
+    let result = match get_note_by_id(conn, note_id) {
+        Result::Ok(Some(note)) => write_note_markdown(config, conn, &note, None),
+        Result::Ok(None) => return,
+        Err(e) => Err(e.into()),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to write note: {}", e)
+    }
+}
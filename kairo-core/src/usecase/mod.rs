@@ -1,7 +1,37 @@
+pub mod area;
+pub mod daily;
+pub mod exchange;
+pub mod ical;
+pub mod inbox;
+pub mod linked_note;
 pub mod note;
 pub mod project;
+pub mod query;
+pub mod report;
+pub mod show;
+pub mod tag;
 pub mod task;
+pub mod taskwarrior;
+pub mod time_entry;
+pub mod todotxt;
+pub mod trash;
+pub mod view;
 
+pub use area::*;
+pub use daily::*;
+pub use exchange::*;
+pub use ical::*;
+pub use inbox::*;
+pub use linked_note::*;
 pub use note::*;
 pub use project::*;
+pub use query::*;
+pub use report::*;
+pub use show::*;
+pub use tag::*;
 pub use task::*;
+pub use taskwarrior::*;
+pub use time_entry::*;
+pub use todotxt::*;
+pub use trash::*;
+pub use view::*;
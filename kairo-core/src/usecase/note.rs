@@ -1,52 +1,107 @@
 use crate::{
     config::AppConfig,
-    markdown::{NoteContent, NoteFrontMatter},
-    store::{note::*, tag::get_tags_by_note_id},
-    util::{parse_markdown, print_notes_as_table, write_to_markdown},
+    markdown::{NoteContent, NoteFrontMatter, NoteLink},
+    model::{NewNoteInput, Note, NoteFilter, TagFilter},
+    store::{linked_note::list_links, note::*, tag::get_tags_by_note_id},
+    usecase::print_document,
+    util::{
+        compose_body, find_template, markdown_relative_path, note_file_path, parse_markdown,
+        print_notes_as_table, read_markdown, read_markdown_body, remove_markdown,
+        remove_markdown_file, resolve_file_path, resolve_title_and_body, write_to_markdown,
+    },
 };
 use anyhow::Ok;
+use chrono::Local;
 use diesel::SqliteConnection;
+use std::io;
+
+const NOTE_PLACEHOLDERS: &[&str] = &[
+    "id", "title", "date", "tags", "project", "task", "type", "sub_type", "body",
+];
 
 pub fn handle_create_note(
     config: &AppConfig,
     conn: &mut SqliteConnection,
-    title: String,
-    note_type: &str,
-    sub_type: &str,
-    project_id: Option<String>,
-    task_id: Option<String>,
-    tags: Option<Vec<String>>,
+    title: Option<String>,
+    input: NewNoteInput,
+    template: Option<String>,
+    body: Option<String>,
 ) -> Result<(), anyhow::Error> {
-    let note = create_note(
-        conn, title, &note_type, &sub_type, project_id, task_id, tags,
-    )?;
+    let (title, content) = resolve_title_and_body(title, body)?;
+
+    // テンプレートは note/<type>/<sub_type>.md → note/<type>.md → note.md の順に探す
+    let candidates = [
+        format!("note/{}/{}.md", input.note_type, input.sub_type),
+        format!("note/{}.md", input.note_type),
+        "note.md".to_string(),
+    ];
+    let template = find_template(config, template.as_deref(), &candidates)?;
+    if let Some(template) = &template {
+        template.check(NOTE_PLACEHOLDERS)?;
+    }
+
+    let note = create_note(conn, title, input)?;
 
-    let dir = &config.paths.notes_dir;
     println!("{:?}", note);
     let tags = get_tags_by_note_id(conn, &note.id).unwrap();
-    let tags_str = tags.into_iter().map(|t| t.tag_name).collect();
+    let tags_str: Vec<String> = tags.into_iter().map(|t| t.tag_name).collect();
 
-    let front_matter = NoteFrontMatter {
-        item: note,
-        tags: tags_str,
-    };
-    let note_content = NoteContent {
-        front_matter,
-        body: None,
-    };
-    if let Err(e) = write_to_markdown(&note_content, dir) {
+    let body = compose_body(
+        template.as_ref(),
+        &[
+            ("id", note.id.clone()),
+            ("title", note.title.clone()),
+            ("date", Local::now().format("%Y-%m-%d").to_string()),
+            ("tags", tags_str.join(", ")),
+            ("project", note.project_id.clone().unwrap_or_default()),
+            ("task", note.task_id.clone().unwrap_or_default()),
+            ("type", format!("{:?}", note.note_type).to_lowercase()),
+            (
+                "sub_type",
+                note.sub_type
+                    .map(|s| format!("{:?}", s).to_lowercase())
+                    .unwrap_or_default(),
+            ),
+        ],
+        &note.title,
+        content.as_deref(),
+    );
+    if let Err(e) = write_note_markdown(config, conn, &note, body) {
         eprintln!("Failed to write note: {}", e)
     }
     println!("Run `kairo tui` to open dashboard");
     Ok(())
 }
 
+// 素早く書き留めるための fleeting ノート。タイトルがなければ1行目から付ける
+pub fn handle_capture(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    text: String,
+    title: Option<String>,
+    sub_type: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<(), anyhow::Error> {
+    if text.trim().is_empty() {
+        return Err(anyhow::anyhow!("Nothing to capture"));
+    }
+    let input = NewNoteInput {
+        note_type: "fleeting".to_string(),
+        sub_type: sub_type.unwrap_or_default(),
+        tags,
+        ..Default::default()
+    };
+    handle_create_note(config, conn, title, input, None, Some(text))
+}
+
 pub fn handle_list_notes(
     conn: &mut SqliteConnection,
     include_archived: Option<bool>,
     include_deleted: Option<bool>,
-    include_tags: Option<Vec<String>>,
+    include_tags: Option<TagFilter>,
     include_order: Option<String>,
+    include_area_id: Option<String>,
+    include_filter: NoteFilter,
 ) -> Result<(), anyhow::Error> {
     let notes = list_notes(
         conn,
@@ -54,6 +109,8 @@ pub fn handle_list_notes(
         include_deleted,
         include_tags,
         include_order,
+        include_area_id,
+        include_filter,
     )?;
     // for note in notes {
     //     println!("{note}");
@@ -75,6 +132,20 @@ pub fn handle_get_note(conn: &mut SqliteConnection, note_id: String) -> Result<(
     Ok(())
 }
 
+// front matter と本文を表示する
+pub fn handle_show_note(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    note_id: String,
+    raw: bool,
+    pager: bool,
+) -> Result<(), anyhow::Error> {
+    let note = get_note_by_id(conn, &note_id)?
+        .ok_or_else(|| anyhow::anyhow!("Note not found: {}", note_id))?;
+    let contents = parse_markdown(&note, &config.paths.notes_dir);
+    print_document(conn, note.to_string(), contents, raw, pager)
+}
+
 pub fn handle_update_note(
     config: &AppConfig,
     conn: &mut SqliteConnection,
@@ -90,26 +161,9 @@ pub fn handle_update_note(
         conn, &note_id, title, note_type, sub_type, project_id, task_id, tags,
     )?;
 
-    let dir = &config.paths.notes_dir;
     println!("{:?}", &updated_note);
-    let tags = get_tags_by_note_id(conn, &updated_note.id).unwrap();
-    let tags_str: Vec<_> = tags.into_iter().map(|t| t.tag_name).collect();
-
-    let contents = parse_markdown(&updated_note, dir)?;
-    // let front_matter = contents.0;
-    let body = Some(contents.1);
-
-    let note_front_matter = NoteFrontMatter {
-        item: updated_note.clone(),
-        tags: tags_str,
-    };
-
-    let note_content = NoteContent {
-        front_matter: note_front_matter,
-        body,
-    };
 
-    if let Err(e) = write_to_markdown(&note_content, dir) {
+    if let Err(e) = write_note_markdown(config, conn, &updated_note, None) {
         eprintln!("Failed to write note: {}", e)
     }
 
@@ -119,51 +173,184 @@ pub fn handle_update_note(
 }
 
 pub fn handle_archive_note(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     note_id: String,
 ) -> Result<(), anyhow::Error> {
     let note = archive_note(conn, &note_id)?;
+    if let Err(e) = write_note_markdown(config, conn, &note, None) {
+        eprintln!("Failed to write note: {}", e)
+    }
 
     println!("Archived note: {:?}", note.id);
     Ok(())
 }
 
 pub fn handle_delete_note(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     note_id: String,
 ) -> Result<(), anyhow::Error> {
     let note = soft_delete_note(conn, &note_id)?;
+    if let Err(e) = write_note_markdown(config, conn, &note, None) {
+        eprintln!("Failed to write note: {}", e)
+    }
 
     println!("Deleted note: {:?}", note.id);
     Ok(())
 }
 
 pub fn handle_purge_note(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     note_id: String,
 ) -> Result<(), anyhow::Error> {
-    let _note = delete_note(conn, &note_id)?;
+    let note = get_note_by_id(conn, &note_id)?;
+    let links = delete_note(conn, &note_id)?;
+    if let Some(note) = note
+        && let Err(e) = remove_markdown(&config.paths.notes_dir, &note)
+    {
+        eprintln!("Failed to remove note file: {}", e)
+    }
+
+    // リンク元のノートの front matter から、削除したノートへのリンクを消す
+    for link in links.iter().filter(|link| link.from_id != note_id) {
+        if let Some(source) = get_note_by_id(conn, &link.from_id)?
+            && let Err(e) = write_note_markdown(config, conn, &source, None)
+        {
+            eprintln!("Failed to write note: {}", e)
+        }
+    }
 
     println!("Purged note: {:?}", note_id);
     Ok(())
 }
 
 pub fn handle_unarchive_note(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     note_id: String,
 ) -> Result<(), anyhow::Error> {
     let note = unarchive_note(conn, &note_id)?;
+    if let Err(e) = write_note_markdown(config, conn, &note, None) {
+        eprintln!("Failed to write note: {}", e)
+    }
 
     println!("Unarchived note: {:?}", note.id);
     Ok(())
 }
 
 pub fn handle_restore_note(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     note_id: String,
 ) -> Result<(), anyhow::Error> {
     let note = restore_note(conn, &note_id)?;
+    if let Err(e) = write_note_markdown(config, conn, &note, None) {
+        eprintln!("Failed to write note: {}", e)
+    }
 
     println!("Restored note: {:?}", note.id);
     Ok(())
 }
+
+// split しない場合はノート自体を permanent にし、split する場合は新しい permanent ノートを作る
+pub fn handle_promote_note(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    note_id: String,
+    split: bool,
+    title: Option<String>,
+    link_type: Option<String>,
+) -> Result<(), anyhow::Error> {
+    if !split {
+        let note = promote_note(conn, &note_id)?;
+        write_note_markdown(config, conn, &note, None)?;
+        println!("Promoted note {} to permanent", note.id);
+        return Ok(());
+    }
+
+    let link_type = link_type.unwrap_or_else(|| "reference".to_string());
+    let (note, link) = split_note(conn, &note_id, title, &link_type)?;
+
+    // 書き直しの下地として、元のノートの本文を引き継ぐ
+    let source_body = match get_note_by_id(conn, &note_id)? {
+        Some(source) => parse_markdown(&source, &config.paths.notes_dir)
+            .ok()
+            .map(|contents| contents.1),
+        None => None,
+    };
+    write_note_markdown(config, conn, &note, source_body)?;
+    println!(
+        "Created permanent note {} from {} ({} link {})",
+        note.id, note_id, link_type, link.id
+    );
+    Ok(())
+}
+
+// 現在のタグとリンクで front matter を書き直す。body が None なら既存ファイルの本文を残す
+pub fn write_note_markdown(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    note: &Note,
+    body: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let dir = &config.paths.notes_dir;
+    let tags = get_tags_by_note_id(conn, &note.id)?;
+    let tags_str: Vec<_> = tags.into_iter().map(|t| t.tag_name).collect();
+    let links: Vec<_> = list_links(conn, Some(note.id.clone()), None)?
+        .into_iter()
+        .filter(|link| !link.deleted)
+        .rev()
+        .map(|link| NoteLink {
+            to: link.to_id,
+            link_type: link.link_type.map(|t| t.as_str().to_string()),
+        })
+        .collect();
+
+    // 既存のファイルがあれば、本文とユーザーが追加したキーを引き継ぐ
+    let (existing, existing_body) = match read_markdown::<NoteFrontMatter, Note>(dir, note) {
+        Result::Ok((front_matter, body)) => (front_matter, Some(body)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (None, None),
+        // DB はすでに更新済みなので、front matter が壊れていても本文を残して書き直す
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            eprintln!(
+                "Warning: {} (front matter rewritten; keys added by hand were not kept)",
+                e
+            );
+            (None, Some(read_markdown_body(dir, note)?))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let extra = existing
+        .map(|front_matter| front_matter.extra)
+        .unwrap_or_default();
+    let body = body.or(existing_body);
+
+    // レイアウトに合わせた場所に書き、場所が変わったら古いファイルを消す
+    let current = markdown_relative_path(note);
+    let desired = resolve_file_path(dir, &current, note_file_path(config, note)?, &note.id);
+    let stored = note.file_path.clone();
+    let mut note = note.clone();
+    note.file_path = Some(desired.clone());
+
+    let note_content = NoteContent {
+        front_matter: NoteFrontMatter {
+            item: note.clone(),
+            tags: tags_str,
+            links,
+            extra,
+        },
+        body,
+    };
+
+    write_to_markdown(&note_content, dir)?;
+
+    if stored.as_deref() != Some(desired.as_str()) {
+        set_note_file_path(conn, &note.id, &desired)?;
+    }
+    if current != desired && remove_markdown_file(dir, &current)? {
+        println!("Moved {} -> {}", current, desired);
+    }
+    Ok(())
+}
@@ -1,37 +1,63 @@
 use crate::{
     config::AppConfig,
     markdown::{ProjectContent, ProjectFrontMatter},
+    model::{
+        CascadeChange, CascadeEntity, NewProjectInput, Project, ProjectOverview, ProjectReport,
+        TagFilter, TaskPriority, UpdateProjectInput,
+    },
     store::*,
-    util::{parse_markdown, print_projects_as_table, write_to_markdown},
+    usecase::{build_area_rollups, print_document, write_note_markdown, write_task_markdown},
+    util::{
+        compose_body, find_template, markdown_relative_path, parse_markdown,
+        print_project_overview, print_project_report, print_project_tree, print_projects_as_table,
+        project_file_path, read_markdown, read_markdown_body, remove_markdown,
+        remove_markdown_file, resolve_file_path, resolve_title_and_body, upsert_generated_section,
+        write_to_markdown,
+    },
 };
-use anyhow::Ok;
+use anyhow::{Ok, anyhow};
+use chrono::{Local, NaiveDate};
 use diesel::SqliteConnection;
+use std::io;
+
+const PROJECT_PLACEHOLDERS: &[&str] = &[
+    "id", "title", "date", "tags", "status", "area", "parent", "body",
+];
 
 pub fn handle_create_project(
     config: &AppConfig,
     conn: &mut SqliteConnection,
-    title: String,
-    description: Option<String>,
-    tags: Option<Vec<String>>,
+    title: Option<String>,
+    input: NewProjectInput,
+    body: Option<String>,
 ) -> Result<(), anyhow::Error> {
-    let project = create_project(conn, title, description, tags)?;
+    let (title, content) = resolve_title_and_body(title, body)?;
+    let template = find_template(config, None, &["project.md".to_string()])?;
+    if let Some(template) = &template {
+        template.check(PROJECT_PLACEHOLDERS)?;
+    }
+
+    let project = create_project(conn, title, input)?;
 
-    let dir = &config.paths.projects_dir;
     println!("{:?}", project);
     let tags = get_tags_by_project_id(conn, &project.id).unwrap();
-    let tags_str = tags.into_iter().map(|t| t.tag_name).collect();
-
-    let front_matter = ProjectFrontMatter {
-        item: project,
-        tags: tags_str,
-    };
+    let tags_str: Vec<String> = tags.into_iter().map(|t| t.tag_name).collect();
 
-    let project_content = ProjectContent {
-        front_matter,
-        body: None,
-    };
-
-    if let Err(e) = write_to_markdown(&project_content, dir) {
+    let body = compose_body(
+        template.as_ref(),
+        &[
+            ("id", project.id.clone()),
+            ("title", project.title.clone()),
+            ("date", Local::now().format("%Y-%m-%d").to_string()),
+            ("tags", tags_str.join(", ")),
+            ("status", project.status.as_str().to_string()),
+            ("area", project.area_id.clone().unwrap_or_default()),
+            ("parent", project.parent_id.clone().unwrap_or_default()),
+        ],
+        &project.title,
+        content.as_deref(),
+    );
+    if let Err(e) = write_project_markdown(config, conn, &project, body) {
         eprintln!("Failed to write project: {}", e)
     }
     println!("Run `kairo tui` to open dashboard");
@@ -42,8 +68,9 @@ pub fn handle_list_projects(
     conn: &mut SqliteConnection,
     include_archived: Option<bool>,
     include_deleted: Option<bool>,
-    include_tags: Option<Vec<String>>,
+    include_tags: Option<TagFilter>,
     include_order: Option<String>,
+    include_area_id: Option<String>,
 ) -> Result<(), anyhow::Error> {
     let projects = list_projects(
         conn,
@@ -51,6 +78,7 @@ pub fn handle_list_projects(
         include_deleted,
         include_tags,
         include_order,
+        include_area_id,
     )?;
     // for project in projects {
     //     println!("{:?}", project);
@@ -75,36 +103,59 @@ pub fn handle_get_project(
     Ok(())
 }
 
-pub fn handle_update_project(
+// 概要の代わりに front matter と本文を表示する
+pub fn handle_show_project_content(
     config: &AppConfig,
     conn: &mut SqliteConnection,
     project_id: String,
-    title: Option<String>,
-    description: Option<String>,
-    tags: Option<Vec<String>>,
+    raw: bool,
+    pager: bool,
 ) -> Result<(), anyhow::Error> {
-    let updated_project = update_project(conn, &project_id, title, description, tags)?;
+    let project = get_project_by_id(conn, &project_id)?
+        .ok_or_else(|| anyhow!("Project not found: {}", project_id))?;
+    let contents = parse_markdown(&project, &config.paths.projects_dir);
+    print_document(conn, project.to_string(), contents, raw, pager)
+}
 
-    let dir = &config.paths.projects_dir;
-    println!("{:?}", &updated_project);
-    let tags = get_tags_by_project_id(conn, &updated_project.id).unwrap();
-    let tags_str: Vec<_> = tags.into_iter().map(|t| t.tag_name).collect();
+pub fn handle_show_project(
+    conn: &mut SqliteConnection,
+    project_id: String,
+) -> Result<(), anyhow::Error> {
+    let project = get_project_by_id(conn, &project_id)?
+        .ok_or_else(|| anyhow!("Project not found: {}", project_id))?;
 
-    let contents = parse_markdown(&updated_project, dir)?;
-    // let front_matter = contents.0;
-    let body = Some(contents.1);
+    let tags = get_tags_by_project_id(conn, &project.id)?
+        .into_iter()
+        .map(|t| t.tag_name)
+        .collect();
+    let sub_projects = get_sub_projects(conn, &project.id)?;
+    let tasks = get_tasks_by_project_id(conn, &project.id)?;
+    let notes = get_notes_by_project_id(conn, &project.id)?;
+    let note_ids: Vec<String> = notes.iter().map(|n| n.id.clone()).collect();
+    let links = get_links_among_notes(conn, &note_ids)?;
 
-    let project_front_matter = ProjectFrontMatter {
-        item: updated_project.clone(),
-        tags: tags_str,
-    };
+    print_project_overview(&ProjectOverview {
+        project,
+        tags,
+        sub_projects,
+        tasks,
+        notes,
+        links,
+    });
+    Ok(())
+}
 
-    let project_content = ProjectContent {
-        front_matter: project_front_matter,
-        body,
-    };
+pub fn handle_update_project(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    project_id: String,
+    input: UpdateProjectInput,
+) -> Result<(), anyhow::Error> {
+    let updated_project = update_project(conn, &project_id, input)?;
 
-    if let Err(e) = write_to_markdown(&project_content, dir) {
+    println!("{:?}", &updated_project);
+
+    if let Err(e) = write_project_markdown(config, conn, &updated_project, None) {
         eprintln!("Failed to write project: {}", e)
     }
 
@@ -114,51 +165,311 @@ pub fn handle_update_project(
 }
 
 pub fn handle_archive_project(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     project_id: String,
+    cascade: bool,
 ) -> Result<(), anyhow::Error> {
+    if cascade {
+        let (project, changes) = cascade_archive_project(conn, &project_id)?;
+        sync_cascade_markdown(config, conn, &project, &changes);
+        println!("Archived project: {:?}", project.id);
+        print_cascade_summary(&changes);
+        return Ok(());
+    }
+
     let project = archive_project(conn, &project_id)?;
+    if let Err(e) = write_project_markdown(config, conn, &project, None) {
+        eprintln!("Failed to write project: {}", e)
+    }
 
     println!("Archived project: {:?}", project.id);
     Ok(())
 }
 
 pub fn handle_delete_project(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     project_id: String,
+    cascade: bool,
 ) -> Result<(), anyhow::Error> {
+    if cascade {
+        let (project, changes) = cascade_delete_project(conn, &project_id)?;
+        sync_cascade_markdown(config, conn, &project, &changes);
+        println!("Deleted project: {:?}", project.id);
+        print_cascade_summary(&changes);
+        return Ok(());
+    }
+
     let project = soft_delete_project(conn, &project_id)?;
+    if let Err(e) = write_project_markdown(config, conn, &project, None) {
+        eprintln!("Failed to write project: {}", e)
+    }
 
     println!("Deleted project: {:?}", project.id);
     Ok(())
 }
 
 pub fn handle_purge_project(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     project_id: String,
 ) -> Result<(), anyhow::Error> {
-    let _project = delete_project(conn, &project_id)?;
+    let project = get_project_by_id(conn, &project_id)?;
+    let detached = delete_project(conn, &project_id)?;
+    if let Some(project) = project
+        && let Err(e) = remove_markdown(&config.paths.projects_dir, &project)
+    {
+        eprintln!("Failed to remove project file: {}", e)
+    }
+    for child in &detached {
+        if let Err(e) = write_project_markdown(config, conn, child, None) {
+            eprintln!("Failed to write project: {}", e)
+        }
+    }
 
     println!("Purged project: {:?}", project_id);
+    if !detached.is_empty() {
+        let ids: Vec<&str> = detached.iter().map(|p| p.id.as_str()).collect();
+        println!("  moved sub-projects to the top level: {}", ids.join(", "));
+    }
     Ok(())
 }
 
 pub fn handle_unarchive_project(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     project_id: String,
+    cascade: bool,
 ) -> Result<(), anyhow::Error> {
-    let project = unarchive_project(conn, &project_id)?;
+    if cascade {
+        let (project, changes) = cascade_unarchive_project(conn, &project_id)?;
+        sync_cascade_markdown(config, conn, &project, &changes);
+        println!("Unarchived project: {:?}", project.id);
+        print_cascade_summary(&changes);
+        return Ok(());
+    }
+
+    let project = unarchive_project_only(conn, &project_id)?;
+    if let Err(e) = write_project_markdown(config, conn, &project, None) {
+        eprintln!("Failed to write project: {}", e)
+    }
 
     println!("Unarchived project: {:?}", project.id);
     Ok(())
 }
 
 pub fn handle_restore_project(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     project_id: String,
+    cascade: bool,
 ) -> Result<(), anyhow::Error> {
-    let project = restore_project(conn, &project_id)?;
+    if cascade {
+        let (project, changes) = cascade_restore_project(conn, &project_id)?;
+        sync_cascade_markdown(config, conn, &project, &changes);
+        println!("Restored project: {:?}", project.id);
+        print_cascade_summary(&changes);
+        return Ok(());
+    }
+
+    let project = restore_project_only(conn, &project_id)?;
+    if let Err(e) = write_project_markdown(config, conn, &project, None) {
+        eprintln!("Failed to write project: {}", e)
+    }
 
     println!("Restored project: {:?}", project.id);
     Ok(())
 }
+
+pub fn handle_project_report(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    project_id: String,
+) -> Result<(), anyhow::Error> {
+    let project = get_project_by_id(conn, &project_id)?
+        .ok_or_else(|| anyhow!("Project not found: {}", project_id))?;
+    let today = Local::now().date_naive();
+
+    let report = build_project_report(conn, &project, today)?;
+    print_project_report(&report);
+
+    // 本文のユーザー記述はそのままに、生成セクションだけを差し替える
+    let existing = parse_markdown(&project, &config.paths.projects_dir)
+        .map(|contents| contents.1)
+        .unwrap_or_else(|_| format!("\n\n## {}\n", project.title));
+    let body = upsert_generated_section(&existing, "report", &report.to_markdown(today));
+    write_project_markdown(config, conn, &project, Some(body))?;
+
+    Ok(())
+}
+
+pub fn handle_project_tree(conn: &mut SqliteConnection) -> Result<(), anyhow::Error> {
+    let areas = list_areas(conn, Some(false), Some(false))?;
+    let rollups = build_area_rollups(conn, areas)?;
+
+    if rollups.is_empty() {
+        println!("No projects found");
+        return Ok(());
+    }
+    print_project_tree(&rollups);
+    Ok(())
+}
+
+// cascade で状態が変わったプロジェクト本体と子要素の Markdown をすべて書き直す
+fn sync_cascade_markdown(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    project: &Project,
+    changes: &[CascadeChange],
+) {
+    if let Err(e) = write_project_markdown(config, conn, project, None) {
+        eprintln!("Failed to write project: {}", e)
+    }
+
+    for change in changes {
+        let result = match change.entity_type {
+            CascadeEntity::Project => match get_project_by_id(conn, &change.entity_id) {
+                Result::Ok(Some(child)) => write_project_markdown(config, conn, &child, None),
+                Result::Ok(None) => continue,
+                Err(e) => Err(e.into()),
+            },
+            CascadeEntity::Task => match get_task_by_id(conn, &change.entity_id) {
+                Result::Ok(Some(task)) => write_task_markdown(config, conn, &task, None),
+                Result::Ok(None) => continue,
+                Err(e) => Err(e.into()),
+            },
+            CascadeEntity::Note => match get_note_by_id(conn, &change.entity_id) {
+                Result::Ok(Some(note)) => write_note_markdown(config, conn, &note, None),
+                Result::Ok(None) => continue,
+                Err(e) => Err(e.into()),
+            },
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to write {}: {}", change.entity_id, e)
+        }
+    }
+}
+
+fn print_cascade_summary(changes: &[CascadeChange]) {
+    let count = |entity: CascadeEntity| changes.iter().filter(|c| c.entity_type == entity).count();
+    println!(
+        "  cascade: {} sub-projects, {} tasks, {} notes",
+        count(CascadeEntity::Project),
+        count(CascadeEntity::Task),
+        count(CascadeEntity::Note)
+    );
+}
+
+// 期日が今日より前の未完了タスクを期限切れとして扱う
+fn build_project_report(
+    conn: &mut SqliteConnection,
+    project: &Project,
+    today: NaiveDate,
+) -> Result<ProjectReport, anyhow::Error> {
+    let tasks = get_tasks_by_project_id(conn, &project.id)?;
+
+    let mut report = ProjectReport {
+        project_id: project.id.clone(),
+        title: project.title.clone(),
+        status: project.status,
+        start_date: project.start_date,
+        target_date: project.target_date,
+        open: 0,
+        completed: 0,
+        deleted: 0,
+        high: 0,
+        medium: 0,
+        low: 0,
+        no_priority: 0,
+        overdue: Vec::new(),
+        linked_notes: count_notes_by_project_id(conn, &project.id)?,
+    };
+
+    for task in tasks {
+        if task.deleted {
+            report.deleted += 1;
+            continue;
+        }
+        if task.archived {
+            report.completed += 1;
+            continue;
+        }
+
+        report.open += 1;
+        match task.priority {
+            Some(TaskPriority::High) => report.high += 1,
+            Some(TaskPriority::Medium) => report.medium += 1,
+            Some(TaskPriority::Low) => report.low += 1,
+            None => report.no_priority += 1,
+        }
+        if task.due_date.is_some_and(|due| due.date() < today) {
+            report.overdue.push(task);
+        }
+    }
+
+    Ok(report)
+}
+
+// body が None の場合、既存の Markdown ファイルがあれば本文を残したまま FrontMatter を書き直す
+pub fn write_project_markdown(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    project: &Project,
+    body: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let dir = &config.paths.projects_dir;
+    let tags = get_tags_by_project_id(conn, &project.id)?;
+    let tags_str: Vec<_> = tags.into_iter().map(|t| t.tag_name).collect();
+
+    // 既存のファイルがあれば、本文とユーザーが追加したキーを引き継ぐ
+    let (existing, existing_body) = match read_markdown::<ProjectFrontMatter, Project>(dir, project)
+    {
+        Result::Ok((front_matter, body)) => (front_matter, Some(body)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (None, None),
+        // DB はすでに更新済みなので、front matter が壊れていても本文を残して書き直す
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            eprintln!(
+                "Warning: {} (front matter rewritten; keys added by hand were not kept)",
+                e
+            );
+            (None, Some(read_markdown_body(dir, project)?))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let extra = existing
+        .map(|front_matter| front_matter.extra)
+        .unwrap_or_default();
+    let body = body.or(existing_body);
+
+    // レイアウトに合わせた場所に書き、場所が変わったら古いファイルを消す
+    let current = markdown_relative_path(project);
+    let desired = resolve_file_path(
+        dir,
+        &current,
+        project_file_path(config, project)?,
+        &project.id,
+    );
+    let stored = project.file_path.clone();
+    let mut project = project.clone();
+    project.file_path = Some(desired.clone());
+
+    let project_content = ProjectContent {
+        front_matter: ProjectFrontMatter {
+            item: project.clone(),
+            tags: tags_str,
+            extra,
+        },
+        body,
+    };
+
+    write_to_markdown(&project_content, dir)?;
+
+    if stored.as_deref() != Some(desired.as_str()) {
+        set_project_file_path(conn, &project.id, &desired)?;
+    }
+    if current != desired && remove_markdown_file(dir, &current)? {
+        println!("Moved {} -> {}", current, desired);
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::{
+    model::{Note, Project, Task},
+    query::{Query, QueryEntity, parse_query},
+    store::*,
+    util::{
+        print_notes_as_table, print_projects_as_table, print_query_results_as_csv,
+        print_tasks_as_table,
+    },
+};
+use anyhow::Ok;
+use chrono::Local;
+use diesel::SqliteConnection;
+use serde::Serialize;
+
+pub fn handle_query(
+    conn: &mut SqliteConnection,
+    input: String,
+    format: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let query = parse_query_input(&input)?;
+    print_query_results(conn, &query, format)
+}
+
+// 解析エラーは入力の下に、エラー位置を ^ で示す
+pub fn parse_query_input(input: &str) -> Result<Query, anyhow::Error> {
+    let today = Local::now().date_naive();
+    parse_query(input, today).map_err(|e| {
+        anyhow::anyhow!(
+            "{}\n  {}\n  {}^",
+            e,
+            input,
+            " ".repeat(e.column.saturating_sub(1))
+        )
+    })
+}
+
+// JSON 出力では検索対象になったものだけを載せる
+#[derive(Serialize, Default)]
+struct QueryResults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<Vec<Note>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tasks: Option<Vec<Task>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    projects: Option<Vec<Project>>,
+}
+
+// 検索結果を table / json / csv のいずれかで出力する
+pub fn print_query_results(
+    conn: &mut SqliteConnection,
+    query: &Query,
+    format: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let mut results = QueryResults::default();
+    for entity in query.entities() {
+        match entity {
+            QueryEntity::Note => results.notes = Some(query_notes(conn, query)?),
+            QueryEntity::Task => results.tasks = Some(query_tasks(conn, query)?),
+            QueryEntity::Project => results.projects = Some(query_projects(conn, query)?),
+        }
+    }
+
+    match format.as_deref() {
+        Some("json") => println!("{}", serde_json::to_string_pretty(&results)?),
+        Some("csv") => print_query_results_as_csv(
+            results.notes.as_deref().unwrap_or_default(),
+            results.tasks.as_deref().unwrap_or_default(),
+            results.projects.as_deref().unwrap_or_default(),
+        ),
+        Some("table") | None => {
+            if query.entities().is_empty() {
+                println!("No entity type matches all conditions");
+            }
+            if let Some(notes) = &results.notes {
+                println!("Notes ({})", notes.len());
+                if !notes.is_empty() {
+                    print_notes_as_table(conn, notes);
+                }
+            }
+            if let Some(tasks) = &results.tasks {
+                println!("Tasks ({})", tasks.len());
+                if !tasks.is_empty() {
+                    print_tasks_as_table(conn, tasks);
+                }
+            }
+            if let Some(projects) = &results.projects {
+                println!("Projects ({})", projects.len());
+                if !projects.is_empty() {
+                    print_projects_as_table(conn, projects);
+                }
+            }
+        }
+        Some(other) => return Err(anyhow::anyhow!("Invalid format: {}", other)),
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    model::{TimeReportGroup, TimeReportRow},
+    store::*,
+    util::{print_time_report_as_csv, print_time_report_as_table},
+};
+use anyhow::Ok;
+use diesel::SqliteConnection;
+
+pub fn handle_time_report(
+    conn: &mut SqliteConnection,
+    from: Option<String>,
+    to: Option<String>,
+    project_id: Option<String>,
+    format: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let entries = list_time_entries(conn, from, to, project_id)?;
+
+    // 計測中のタイマーは集計対象外
+    let mut by_task: BTreeMap<String, i64> = BTreeMap::new();
+    for entry in entries.iter().filter(|e| !e.is_running()) {
+        *by_task.entry(entry.task_id.clone()).or_insert(0) += entry.duration_minutes();
+    }
+
+    let mut task_rows = Vec::new();
+    let mut by_project: BTreeMap<String, (String, i64)> = BTreeMap::new();
+    let mut by_tag: BTreeMap<String, i64> = BTreeMap::new();
+
+    for (task_id, minutes) in &by_task {
+        let task = get_task_by_id(conn, task_id)?;
+        let (task_title, task_project_id) = match task {
+            Some(t) => (t.title, t.project_id),
+            None => ("(not found)".to_string(), None),
+        };
+
+        task_rows.push(TimeReportRow {
+            group: TimeReportGroup::Task,
+            key: task_id.clone(),
+            name: task_title,
+            minutes: *minutes,
+        });
+
+        let (project_key, project_title) = match task_project_id {
+            Some(pid) => {
+                let title = get_project_by_id(conn, &pid)?
+                    .map(|p| p.title)
+                    .unwrap_or_else(|| "(not found)".to_string());
+                (pid, title)
+            }
+            None => ("-".to_string(), "(no project)".to_string()),
+        };
+        by_project
+            .entry(project_key)
+            .or_insert((project_title, 0))
+            .1 += minutes;
+
+        // タグ別集計は、タスクに付与された全タグにそれぞれ加算する
+        for tag in get_tags_by_task_id(conn, task_id)? {
+            *by_tag.entry(tag.tag_name).or_insert(0) += minutes;
+        }
+    }
+
+    let mut rows = task_rows;
+    rows.extend(
+        by_project
+            .into_iter()
+            .map(|(key, (name, minutes))| TimeReportRow {
+                group: TimeReportGroup::Project,
+                key,
+                name,
+                minutes,
+            }),
+    );
+    rows.extend(by_tag.into_iter().map(|(name, minutes)| TimeReportRow {
+        group: TimeReportGroup::Tag,
+        key: name.clone(),
+        name,
+        minutes,
+    }));
+
+    match format.as_deref() {
+        Some("csv") => print_time_report_as_csv(&rows),
+        Some("table") | None => print_time_report_as_table(&rows),
+        Some(other) => return Err(anyhow::anyhow!("Invalid format: {}", other)),
+    }
+    Ok(())
+}
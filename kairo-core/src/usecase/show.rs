@@ -0,0 +1,70 @@
+use crate::{
+    store::*,
+    util::{print_paged, render_markdown, use_color},
+};
+use diesel::SqliteConnection;
+use std::io;
+
+// front matter と本文を表示する。raw なら Markdown をそのまま、そうでなければ端末向けに整形する。
+// summary は Markdown ファイルがないときに代わりに表示する内容。
+pub fn print_document(
+    conn: &mut SqliteConnection,
+    summary: String,
+    contents: Result<(String, String), io::Error>,
+    raw: bool,
+    pager: bool,
+) -> Result<(), anyhow::Error> {
+    let (front_matter, body) = match contents {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            println!("{}", summary);
+            println!("(No Markdown file)");
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let text = if raw && front_matter.is_empty() {
+        body
+    } else if raw {
+        format!("---{}---{}", front_matter, body)
+    } else {
+        let color = use_color();
+        let mut lines: Vec<String> = front_matter
+            .trim()
+            .lines()
+            .map(|line| {
+                if color {
+                    format!("\x1b[2m{}\x1b[0m", line)
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect();
+        lines.push(String::new());
+
+        let mut resolve = |target: &str| resolve_wikilink(conn, target);
+        lines.push(render_markdown(body.trim(), color, &mut resolve));
+        lines.join("\n")
+    };
+
+    print_paged(text.trim_end(), pager)?;
+    Ok(())
+}
+
+// [[...]] の参照先を ID（ノート・タスク・プロジェクト）、ノートのタイトルの順に探す
+fn resolve_wikilink(conn: &mut SqliteConnection, target: &str) -> Option<String> {
+    if let Ok(Some(note)) = get_note_by_id(conn, target) {
+        return Some(note.title);
+    }
+    if let Ok(Some(task)) = get_task_by_id(conn, target) {
+        return Some(task.title);
+    }
+    if let Ok(Some(project)) = get_project_by_id(conn, target) {
+        return Some(project.title);
+    }
+    get_note_by_title(conn, target)
+        .ok()
+        .flatten()
+        .map(|note| note.title)
+}
@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+
+use crate::{
+    config::AppConfig,
+    model::{CoOccurringTag, Tag, TagStats, TagTreeNode, TaggedEntity},
+    store::*,
+    usecase::{
+        note::write_note_markdown, project::write_project_markdown, task::write_task_markdown,
+    },
+    util::{print_tag_aliases_as_table, print_tag_stats_as_table, print_tag_tree},
+};
+use anyhow::Ok;
+use diesel::SqliteConnection;
+
+// タグ名の変更はサブツリー全体に及ぶため、対象タグが付いている Markdown をまとめて書き直す
+pub fn handle_rename_tag(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    tag_id: String,
+    tag_name: String,
+) -> Result<(), anyhow::Error> {
+    let tag_ids = get_subtree_tag_ids(conn, &tag_id)?;
+    let tag = rename_tag(conn, &tag_id, tag_name)?;
+
+    println!("Renamed tag: {} -> {}", tag.id, tag.tag_name);
+    rewrite_tagged_markdown(config, conn, &tag_ids)?;
+    Ok(())
+}
+
+pub fn handle_merge_tags(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    from: String,
+    into: String,
+) -> Result<(), anyhow::Error> {
+    let from_tag = resolve_tag(conn, &from)?;
+    let into_tag = resolve_tag(conn, &into)?;
+
+    // 統合後は from の行が消えるため、影響する要素は統合前に集めておく
+    let tag_ids = vec![from_tag.id.clone()];
+    let note_ids = get_note_ids_by_tag_ids(conn, &tag_ids)?;
+    let task_ids = get_task_ids_by_tag_ids(conn, &tag_ids)?;
+    let project_ids = get_project_ids_by_tag_ids(conn, &tag_ids)?;
+
+    let tag = merge_tags(conn, &from_tag.id, &into_tag.id)?;
+
+    println!(
+        "Merged tag {} ({}) into {} ({})",
+        from_tag.tag_name, from_tag.id, tag.tag_name, tag.id
+    );
+    rewrite_markdown(config, conn, &note_ids, &task_ids, &project_ids)?;
+    Ok(())
+}
+
+// 削除・復元でタグの表示有無が変わるため、関連する Markdown を書き直す
+pub fn handle_delete_tag(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    tag_id: String,
+) -> Result<(), anyhow::Error> {
+    let tag = soft_delete_tag(conn, &tag_id)?;
+
+    println!("Deleted tag: {:?}", tag.id);
+    rewrite_tagged_markdown(config, conn, &[tag.id])?;
+    Ok(())
+}
+
+pub fn handle_restore_tag(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    tag_id: String,
+) -> Result<(), anyhow::Error> {
+    let tag = restore_tag(conn, &tag_id)?;
+
+    println!("Restored tag: {:?}", tag.id);
+    rewrite_tagged_markdown(config, conn, &[tag.id])?;
+    Ok(())
+}
+
+pub fn handle_purge_tag(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    tag_id: String,
+) -> Result<(), anyhow::Error> {
+    // 関連が消える前に影響する要素を集めておく
+    let tag_ids = vec![tag_id.clone()];
+    let note_ids = get_note_ids_by_tag_ids(conn, &tag_ids)?;
+    let task_ids = get_task_ids_by_tag_ids(conn, &tag_ids)?;
+    let project_ids = get_project_ids_by_tag_ids(conn, &tag_ids)?;
+
+    let tag = purge_tag(conn, &tag_id)?;
+
+    println!("Purged tag: {:?}", tag.id);
+    // 削除済みのタグは Markdown に出ていないため、書き直しは有効なタグのときだけ
+    if !tag.deleted {
+        rewrite_markdown(config, conn, &note_ids, &task_ids, &project_ids)?;
+    }
+    Ok(())
+}
+
+// 削除済みのタグでも、付いたままのノート・タスク・プロジェクトを確認できるようにする
+pub fn handle_get_tag(conn: &mut SqliteConnection, tag_id: String) -> Result<(), anyhow::Error> {
+    let tag = match get_tag_by_id(conn, &tag_id)? {
+        Some(tag) => tag,
+        None => {
+            println!("Tag not found");
+            return Ok(());
+        }
+    };
+
+    println!("{tag}");
+    let tag_ids = vec![tag.id.clone()];
+    let note_ids = get_note_ids_by_tag_ids(conn, &tag_ids)?;
+    let task_ids = get_task_ids_by_tag_ids(conn, &tag_ids)?;
+    let project_ids = get_project_ids_by_tag_ids(conn, &tag_ids)?;
+    for (label, ids) in [
+        ("Notes", note_ids),
+        ("Tasks", task_ids),
+        ("Projects", project_ids),
+    ] {
+        if !ids.is_empty() {
+            println!("{}: {}", label, ids.join(", "));
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_add_tag_alias(
+    conn: &mut SqliteConnection,
+    alias: String,
+    tag: String,
+) -> Result<(), anyhow::Error> {
+    let target = resolve_tag(conn, &tag)?;
+    let created = create_tag_alias(conn, &alias, &target.id)?;
+
+    println!("Added alias: {} -> {}", created.alias, target.tag_name);
+    Ok(())
+}
+
+pub fn handle_remove_tag_alias(
+    conn: &mut SqliteConnection,
+    alias: String,
+) -> Result<(), anyhow::Error> {
+    delete_tag_alias(conn, &alias)?;
+
+    println!("Removed alias: {}", alias);
+    Ok(())
+}
+
+pub fn handle_list_tag_aliases(conn: &mut SqliteConnection) -> Result<(), anyhow::Error> {
+    let aliases = list_tag_aliases(conn)?;
+    if aliases.is_empty() {
+        println!("No tag aliases found");
+        return Ok(());
+    }
+
+    print_tag_aliases_as_table(&aliases);
+    Ok(())
+}
+
+pub fn handle_tag_stats(
+    conn: &mut SqliteConnection,
+    sort: Option<String>,
+    order: Option<String>,
+    unused_only: bool,
+    format: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let tags = list_tags(conn, Some(false))?;
+    let mut stats = build_tag_stats(conn, &tags)?;
+    if unused_only {
+        stats.retain(|s| s.total() == 0);
+    }
+
+    // 名前順は昇順、件数・日付順は降順を既定にする
+    let sort_key = sort.as_deref().unwrap_or("name");
+    if !matches!(
+        sort_key,
+        "name" | "total" | "notes" | "tasks" | "projects" | "first" | "last"
+    ) {
+        return Err(anyhow::anyhow!("Invalid sort: {}", sort_key));
+    }
+    let descending = match order.as_deref() {
+        Some("asc") => false,
+        Some("desc") => true,
+        None => sort_key != "name",
+        Some(other) => return Err(anyhow::anyhow!("Invalid order: {}", other)),
+    };
+    stats.sort_by(|a, b| {
+        let ordering = match sort_key {
+            "total" => a.total().cmp(&b.total()),
+            "notes" => a.notes.cmp(&b.notes),
+            "tasks" => a.tasks.cmp(&b.tasks),
+            "projects" => a.projects.cmp(&b.projects),
+            "first" => a.first_used.cmp(&b.first_used),
+            "last" => a.last_used.cmp(&b.last_used),
+            _ => a.tag_name.cmp(&b.tag_name),
+        };
+        let ordering = if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        // 同順位はタグ名順
+        ordering.then_with(|| a.tag_name.cmp(&b.tag_name))
+    });
+
+    match format.as_deref() {
+        Some("json") => println!("{}", serde_json::to_string_pretty(&stats)?),
+        Some("table") | None => {
+            if stats.is_empty() {
+                println!("No tags found");
+            } else {
+                print_tag_stats_as_table(&stats);
+            }
+        }
+        Some(other) => return Err(anyhow::anyhow!("Invalid format: {}", other)),
+    }
+    Ok(())
+}
+
+// タグごとの件数・使用期間と、同じ要素に一緒に付いているタグの件数を集計する
+pub fn build_tag_stats(
+    conn: &mut SqliteConnection,
+    tags: &[Tag],
+) -> Result<Vec<TagStats>, anyhow::Error> {
+    let assignments = list_tag_assignments(conn)?;
+    let names: HashMap<&str, &str> = tags
+        .iter()
+        .map(|t| (t.id.as_str(), t.tag_name.as_str()))
+        .collect();
+
+    let mut stats: HashMap<&str, TagStats> = tags
+        .iter()
+        .map(|t| {
+            (
+                t.id.as_str(),
+                TagStats {
+                    tag_id: t.id.clone(),
+                    tag_name: t.tag_name.clone(),
+                    notes: 0,
+                    tasks: 0,
+                    projects: 0,
+                    first_used: None,
+                    last_used: None,
+                    co_occurring: Vec::new(),
+                },
+            )
+        })
+        .collect();
+
+    let mut tags_by_entity: HashMap<(TaggedEntity, &str), Vec<&str>> = HashMap::new();
+    for assignment in &assignments {
+        let Some(stat) = stats.get_mut(assignment.tag_id.as_str()) else {
+            continue;
+        };
+        match assignment.entity_type {
+            TaggedEntity::Note => stat.notes += 1,
+            TaggedEntity::Task => stat.tasks += 1,
+            TaggedEntity::Project => stat.projects += 1,
+        }
+        stat.first_used = Some(
+            stat.first_used
+                .map_or(assignment.created_at, |d| d.min(assignment.created_at)),
+        );
+        stat.last_used = Some(
+            stat.last_used
+                .map_or(assignment.created_at, |d| d.max(assignment.created_at)),
+        );
+        tags_by_entity
+            .entry((assignment.entity_type, assignment.entity_id.as_str()))
+            .or_default()
+            .push(assignment.tag_id.as_str());
+    }
+
+    let mut co_counts: HashMap<&str, HashMap<&str, i64>> = HashMap::new();
+    for tag_ids in tags_by_entity.values() {
+        for a in tag_ids {
+            for b in tag_ids.iter().filter(|b| *b != a) {
+                *co_counts.entry(a).or_default().entry(b).or_default() += 1;
+            }
+        }
+    }
+
+    for (tag_id, stat) in stats.iter_mut() {
+        let mut co_occurring: Vec<CoOccurringTag> = co_counts
+            .get(tag_id)
+            .map(|counts| {
+                counts
+                    .iter()
+                    .filter_map(|(other, count)| {
+                        names.get(other).map(|name| CoOccurringTag {
+                            tag_name: name.to_string(),
+                            count: *count,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        co_occurring.sort_by(|a, b| b.count.cmp(&a.count).then(a.tag_name.cmp(&b.tag_name)));
+        stat.co_occurring = co_occurring;
+    }
+
+    Ok(stats.into_values().collect())
+}
+
+pub fn handle_tag_tree(conn: &mut SqliteConnection) -> Result<(), anyhow::Error> {
+    let tags = list_tags(conn, Some(false))?;
+    if tags.is_empty() {
+        println!("No tags found");
+        return Ok(());
+    }
+
+    let usage = count_tag_usage(conn)?;
+    let roots = build_tag_tree(&tags, |tag| usage.get(&tag.id).copied().unwrap_or(0));
+
+    print_tag_tree(&roots);
+    Ok(())
+}
+
+// スラッシュ区切りのタグ名から階層を組み立てる（各階層は名前順）
+pub fn build_tag_tree<F>(tags: &[Tag], count_of: F) -> Vec<TagTreeNode>
+where
+    F: Fn(&Tag) -> i64,
+{
+    let mut sorted: Vec<&Tag> = tags.iter().collect();
+    sorted.sort_by(|a, b| a.tag_name.cmp(&b.tag_name));
+
+    let mut roots: Vec<TagTreeNode> = Vec::new();
+    for tag in sorted {
+        let mut level = &mut roots;
+        let mut full_name = String::new();
+        let segments: Vec<&str> = tag.tag_name.split('/').collect();
+
+        for (index, segment) in segments.iter().enumerate() {
+            if !full_name.is_empty() {
+                full_name.push('/');
+            }
+            full_name.push_str(segment);
+
+            let position = match level.iter().position(|node| node.segment == *segment) {
+                Some(position) => position,
+                None => {
+                    level.push(TagTreeNode {
+                        segment: segment.to_string(),
+                        full_name: full_name.clone(),
+                        ..Default::default()
+                    });
+                    level.len() - 1
+                }
+            };
+
+            if index + 1 == segments.len() {
+                level[position].tag_id = Some(tag.id.clone());
+                level[position].count = count_of(tag);
+            }
+            level = &mut level[position].children;
+        }
+    }
+
+    roots
+}
+
+// タグIDを優先し、見つからなければタグ名（別名を含む）として解決する
+fn resolve_tag(conn: &mut SqliteConnection, id_or_name: &str) -> Result<Tag, anyhow::Error> {
+    if let Some(tag) = get_tag_by_id(conn, id_or_name)? {
+        return Ok(tag);
+    }
+    get_tag_by_name(conn, id_or_name.to_string())?
+        .ok_or_else(|| anyhow::anyhow!("Tag not found: {}", id_or_name))
+}
+
+fn rewrite_tagged_markdown(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    tag_ids: &[String],
+) -> Result<(), anyhow::Error> {
+    let note_ids = get_note_ids_by_tag_ids(conn, tag_ids)?;
+    let task_ids = get_task_ids_by_tag_ids(conn, tag_ids)?;
+    let project_ids = get_project_ids_by_tag_ids(conn, tag_ids)?;
+    rewrite_markdown(config, conn, &note_ids, &task_ids, &project_ids)
+}
+
+// 指定した要素の front matter を現在のタグで書き直す（本文はそのまま）。
+// DB はすでに更新済みなので、書き込めないファイルがあっても残りを書き直し、最後にまとめて報告する
+fn rewrite_markdown(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    note_ids: &[String],
+    task_ids: &[String],
+    project_ids: &[String],
+) -> Result<(), anyhow::Error> {
+    let mut rewritten = 0;
+    let mut failed = Vec::new();
+    let mut record = |id: &str, result: Result<(), anyhow::Error>| match result {
+        Result::Ok(()) => rewritten += 1,
+        Err(e) => {
+            eprintln!("Failed to rewrite {}: {}", id, e);
+            failed.push(id.to_string());
+        }
+    };
+
+    for note_id in note_ids {
+        if let Some(note) = get_note_by_id(conn, note_id)? {
+            record(note_id, write_note_markdown(config, conn, &note, None));
+        }
+    }
+    for task_id in task_ids {
+        if let Some(task) = get_task_by_id(conn, task_id)? {
+            record(task_id, write_task_markdown(config, conn, &task, None));
+        }
+    }
+    for project_id in project_ids {
+        if let Some(project) = get_project_by_id(conn, project_id)? {
+            record(
+                project_id,
+                write_project_markdown(config, conn, &project, None),
+            );
+        }
+    }
+
+    println!("Rewrote {} Markdown file(s)", rewritten);
+    if !failed.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Could not rewrite {} Markdown file(s): {}",
+            failed.len(),
+            failed.join(", ")
+        ));
+    }
+    Ok(())
+}
@@ -1,48 +1,68 @@
 use crate::{
     config::AppConfig,
     markdown::{TaskContent, TaskFrontMatter},
+    model::{NewTaskInput, TagFilter, Task},
     store::*,
-    util::{parse_markdown, print_tasks_as_table, write_to_markdown},
+    usecase::print_document,
+    util::{
+        compose_body, find_template, markdown_relative_path, parse_markdown, print_tasks_as_table,
+        read_markdown, read_markdown_body, remove_markdown, remove_markdown_file,
+        resolve_file_path, resolve_title_and_body, task_file_path, write_to_markdown,
+    },
 };
 use anyhow::Ok;
+use chrono::Local;
 use diesel::SqliteConnection;
+use std::io;
+
+const TASK_PLACEHOLDERS: &[&str] = &[
+    "id", "title", "date", "tags", "project", "priority", "due", "body",
+];
 
 pub fn handle_create_task(
     config: &AppConfig,
     conn: &mut SqliteConnection,
-    title: String,
-    description: Option<String>,
-    priority: Option<String>,
-    due_date: Option<String>,
-    project_id: Option<String>,
-    tags: Option<Vec<String>>,
+    title: Option<String>,
+    input: NewTaskInput,
+    body: Option<String>,
 ) -> Result<(), anyhow::Error> {
-    let task = create_task(
-        conn,
-        title,
-        description,
-        priority,
-        due_date,
-        project_id,
-        tags,
-    )?;
+    let (title, content) = resolve_title_and_body(title, body)?;
+    let template = find_template(config, None, &["task.md".to_string()])?;
+    if let Some(template) = &template {
+        template.check(TASK_PLACEHOLDERS)?;
+    }
+
+    let task = create_task(conn, title, input)?;
 
-    let dir = &config.paths.tasks_dir;
     println!("{:?}", task);
     let tags = get_tags_by_task_id(conn, &task.id).unwrap();
-    let tags_str = tags.into_iter().map(|t| t.tag_name).collect();
+    let tags_str: Vec<String> = tags.into_iter().map(|t| t.tag_name).collect();
 
-    let front_matter = TaskFrontMatter {
-        item: task,
-        tags: tags_str,
-    };
-
-    let task_content = TaskContent {
-        front_matter,
-        body: None,
-    };
-
-    if let Err(e) = write_to_markdown(&task_content, dir) {
+    let body = compose_body(
+        template.as_ref(),
+        &[
+            ("id", task.id.clone()),
+            ("title", task.title.clone()),
+            ("date", Local::now().format("%Y-%m-%d").to_string()),
+            ("tags", tags_str.join(", ")),
+            ("project", task.project_id.clone().unwrap_or_default()),
+            (
+                "priority",
+                task.priority
+                    .map(|p| format!("{:?}", p).to_lowercase())
+                    .unwrap_or_default(),
+            ),
+            (
+                "due",
+                task.due_date
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default(),
+            ),
+        ],
+        &task.title,
+        content.as_deref(),
+    );
+    if let Err(e) = write_task_markdown(config, conn, &task, body) {
         eprintln!("Failed to write task: {}", e)
     }
     println!("Run `kairo tui` to open dashboard");
@@ -53,10 +73,11 @@ pub fn handle_list_tasks(
     conn: &mut SqliteConnection,
     include_archived: Option<bool>,
     include_deleted: Option<bool>,
-    include_tags: Option<Vec<String>>,
+    include_tags: Option<TagFilter>,
     include_order: Option<String>,
     include_priority: Option<String>,
     include_project_id: Option<String>,
+    include_area_id: Option<String>,
 ) -> Result<(), anyhow::Error> {
     let tasks = list_tasks(
         conn,
@@ -66,6 +87,7 @@ pub fn handle_list_tasks(
         include_order,
         include_priority,
         include_project_id,
+        include_area_id,
     )?;
     // for task in tasks {
     //     println!("{:?}", task);
@@ -87,6 +109,20 @@ pub fn handle_get_task(conn: &mut SqliteConnection, task_id: String) -> Result<(
     Ok(())
 }
 
+// front matter と本文を表示する
+pub fn handle_show_task(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    task_id: String,
+    raw: bool,
+    pager: bool,
+) -> Result<(), anyhow::Error> {
+    let task = get_task_by_id(conn, &task_id)?
+        .ok_or_else(|| anyhow::anyhow!("Task not found: {}", task_id))?;
+    let contents = parse_markdown(&task, &config.paths.tasks_dir);
+    print_document(conn, task.to_string(), contents, raw, pager)
+}
+
 pub fn handle_update_task(
     config: &AppConfig,
     conn: &mut SqliteConnection,
@@ -109,26 +145,10 @@ pub fn handle_update_task(
         tags,
     )?;
 
-    let dir = &config.paths.tasks_dir;
     println!("{:?}", &updated_task);
-    let tags = get_tags_by_task_id(conn, &updated_task.id).unwrap();
-    let tags_str: Vec<_> = tags.into_iter().map(|t| t.tag_name).collect();
-
-    let contents = parse_markdown(&updated_task, dir)?;
-    let body = Some(contents.1);
-
-    let task_front_matter = TaskFrontMatter {
-        item: updated_task.clone(),
-        tags: tags_str,
-    };
-
-    let task_content = TaskContent {
-        front_matter: task_front_matter,
-        body,
-    };
 
-    if let Err(e) = write_to_markdown(&task_content, dir) {
-        eprintln!("Failed to write note: {}", e)
+    if let Err(e) = write_task_markdown(config, conn, &updated_task, None) {
+        eprintln!("Failed to write task: {}", e)
     }
 
     // println!("Updated note: {:?}", updated_task.id);
@@ -137,51 +157,131 @@ pub fn handle_update_task(
 }
 
 pub fn handle_archive_task(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     task_id: String,
 ) -> Result<(), anyhow::Error> {
     let task = archive_task(conn, &task_id)?;
+    if let Err(e) = write_task_markdown(config, conn, &task, None) {
+        eprintln!("Failed to write task: {}", e)
+    }
 
     println!("Archived task: {:?}", task.id);
     Ok(())
 }
 
 pub fn handle_delete_task(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     task_id: String,
 ) -> Result<(), anyhow::Error> {
     let task = soft_delete_task(conn, &task_id)?;
+    if let Err(e) = write_task_markdown(config, conn, &task, None) {
+        eprintln!("Failed to write task: {}", e)
+    }
 
     println!("Deleted task: {:?}", task.id);
     Ok(())
 }
 
 pub fn handle_purge_task(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     task_id: String,
 ) -> Result<(), anyhow::Error> {
-    let _task = delete_task(conn, &task_id)?;
+    let task = get_task_by_id(conn, &task_id)?;
+    delete_task(conn, &task_id)?;
+    if let Some(task) = task
+        && let Err(e) = remove_markdown(&config.paths.tasks_dir, &task)
+    {
+        eprintln!("Failed to remove task file: {}", e)
+    }
 
     println!("Purged task: {:?}", task_id);
     Ok(())
 }
 
 pub fn handle_unarchive_task(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     task_id: String,
 ) -> Result<(), anyhow::Error> {
     let task = unarchive_task(conn, &task_id)?;
+    if let Err(e) = write_task_markdown(config, conn, &task, None) {
+        eprintln!("Failed to write task: {}", e)
+    }
 
     println!("Unarchived task: {:?}", task.id);
     Ok(())
 }
 
 pub fn handle_restore_task(
+    config: &AppConfig,
     conn: &mut SqliteConnection,
     task_id: String,
 ) -> Result<(), anyhow::Error> {
     let task = restore_task(conn, &task_id)?;
+    if let Err(e) = write_task_markdown(config, conn, &task, None) {
+        eprintln!("Failed to write task: {}", e)
+    }
 
     println!("Restored task: {:?}", task.id);
     Ok(())
 }
+
+// body が None の場合、既存の Markdown ファイルがあれば本文を残したまま FrontMatter を書き直す
+pub fn write_task_markdown(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    task: &Task,
+    body: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let dir = &config.paths.tasks_dir;
+    let tags = get_tags_by_task_id(conn, &task.id)?;
+    let tags_str: Vec<_> = tags.into_iter().map(|t| t.tag_name).collect();
+
+    // 既存のファイルがあれば、本文とユーザーが追加したキーを引き継ぐ
+    let (existing, existing_body) = match read_markdown::<TaskFrontMatter, Task>(dir, task) {
+        Result::Ok((front_matter, body)) => (front_matter, Some(body)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (None, None),
+        // DB はすでに更新済みなので、front matter が壊れていても本文を残して書き直す
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            eprintln!(
+                "Warning: {} (front matter rewritten; keys added by hand were not kept)",
+                e
+            );
+            (None, Some(read_markdown_body(dir, task)?))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let extra = existing
+        .map(|front_matter| front_matter.extra)
+        .unwrap_or_default();
+    let body = body.or(existing_body);
+
+    // レイアウトに合わせた場所に書き、場所が変わったら古いファイルを消す
+    let current = markdown_relative_path(task);
+    let desired = resolve_file_path(dir, &current, task_file_path(config, task)?, &task.id);
+    let stored = task.file_path.clone();
+    let mut task = task.clone();
+    task.file_path = Some(desired.clone());
+
+    let task_content = TaskContent {
+        front_matter: TaskFrontMatter {
+            item: task.clone(),
+            tags: tags_str,
+            extra,
+        },
+        body,
+    };
+
+    write_to_markdown(&task_content, dir)?;
+
+    if stored.as_deref() != Some(desired.as_str()) {
+        set_task_file_path(conn, &task.id, &desired)?;
+    }
+    if current != desired && remove_markdown_file(dir, &current)? {
+        println!("Moved {} -> {}", current, desired);
+    }
+    Ok(())
+}
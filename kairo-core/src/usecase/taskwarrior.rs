@@ -0,0 +1,107 @@
+use std::{collections::HashMap, fs};
+
+use crate::{
+    config::AppConfig,
+    exchange::{
+        ANNOTATIONS_SECTION, EXTERNAL_ID_SOURCE, markdown_to_annotations, parse_taskwarrior,
+        tasks_to_taskwarrior,
+    },
+    store::*,
+    usecase::{load_exportable_tasks, upsert_imported_task, write_imported_markdown},
+    util::{parse_markdown, read_generated_section, upsert_generated_section},
+};
+use anyhow::{Ok, anyhow};
+use diesel::{Connection, SqliteConnection};
+
+pub fn handle_export_taskwarrior(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    output: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let items = load_exportable_tasks(conn)?;
+
+    // project にはプロジェクトのタイトルを使う
+    let mut projects = list_projects(conn, Some(false), Some(false), None, None, None)?;
+    projects.extend(list_projects(
+        conn,
+        Some(true),
+        Some(false),
+        None,
+        None,
+        None,
+    )?);
+
+    // Taskwarrior から取り込んだタスクは元の uuid で書き出し、同じタスクとして読み込ませる
+    let uuids: HashMap<String, String> = list_task_external_ids(conn, EXTERNAL_ID_SOURCE)?
+        .into_iter()
+        .map(|link| (link.task_id, link.external_id))
+        .collect();
+
+    // 取り込み時に本文へ書いた注釈を読み戻す。Markdown が読めないタスクは注釈なしで出力する
+    let mut annotations = HashMap::new();
+    for (task, _) in &items {
+        let Result::Ok((_, body)) = parse_markdown(task, &config.paths.tasks_dir) else {
+            continue;
+        };
+        if let Some(section) = read_generated_section(&body, ANNOTATIONS_SECTION) {
+            annotations.insert(task.id.clone(), markdown_to_annotations(section));
+        }
+    }
+
+    let json = tasks_to_taskwarrior(&items, &projects, &uuids, &annotations)?;
+
+    match output {
+        Some(path) => {
+            fs::write(&path, json)?;
+            println!("✅ Exported {} tasks to {}", items.len(), path);
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+pub fn handle_import_taskwarrior(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    file: String,
+) -> Result<(), anyhow::Error> {
+    let content = fs::read_to_string(&file)?;
+    let items =
+        parse_taskwarrior(&content).map_err(|e| anyhow!("Failed to parse {}: {}", file, e))?;
+
+    let mut created = 0;
+
+    // DB への反映はまとめて1トランザクションで行い、Markdown はコミット後に書き出す
+    let imported = conn.transaction::<_, anyhow::Error, _>(|conn| {
+        let mut imported = Vec::new();
+
+        for item in &items {
+            let (task, is_new) = upsert_imported_task(conn, item)?;
+            if is_new {
+                created += 1;
+            }
+
+            // 注釈は既存の本文を残したまま専用のセクションに書き込む
+            let body = item.body.as_ref().map(|annotations| {
+                let existing = parse_markdown(&task, &config.paths.tasks_dir)
+                    .map(|contents| contents.1)
+                    .unwrap_or_else(|_| format!("\n\n## {}\n", task.title));
+                upsert_generated_section(&existing, ANNOTATIONS_SECTION, annotations)
+            });
+            imported.push((task, body));
+        }
+
+        Ok(imported)
+    })?;
+
+    write_imported_markdown(config, conn, &imported);
+
+    println!(
+        "Imported {} tasks from {} (created: {}, updated: {})",
+        imported.len(),
+        file,
+        created,
+        imported.len() - created
+    );
+    Ok(())
+}
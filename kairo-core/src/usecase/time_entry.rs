@@ -0,0 +1,45 @@
+use crate::store::*;
+use anyhow::Ok;
+use diesel::SqliteConnection;
+
+pub fn handle_start_timer(
+    conn: &mut SqliteConnection,
+    task_id: String,
+    note: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let entry = start_timer(conn, &task_id, note)?;
+
+    println!("Started timer for task: {:?} ({})", entry.task_id, entry.id);
+    Ok(())
+}
+
+pub fn handle_stop_timer(conn: &mut SqliteConnection) -> Result<(), anyhow::Error> {
+    let entry = stop_timer(conn)?;
+
+    let minutes = entry.duration_minutes();
+    println!(
+        "Stopped timer for task: {:?} ({}h{:02}m)",
+        entry.task_id,
+        minutes / 60,
+        minutes % 60
+    );
+    Ok(())
+}
+
+pub fn handle_log_time(
+    conn: &mut SqliteConnection,
+    task_id: String,
+    duration: String,
+    note: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let entry = log_time(conn, &task_id, &duration, note)?;
+
+    let minutes = entry.duration_minutes();
+    println!(
+        "Logged {}h{:02}m for task: {:?}",
+        minutes / 60,
+        minutes % 60,
+        entry.task_id
+    );
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use std::fs;
+
+use crate::{
+    config::AppConfig,
+    exchange::{parse_todotxt, tasks_to_todotxt},
+    store::*,
+    usecase::{load_exportable_tasks, upsert_imported_task, write_imported_markdown},
+};
+use anyhow::{Ok, anyhow};
+use diesel::{Connection, SqliteConnection};
+
+pub fn handle_export_todotxt(
+    conn: &mut SqliteConnection,
+    output: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let items = load_exportable_tasks(conn)?;
+
+    // +project にはプロジェクトのタイトルを使う
+    let mut projects = list_projects(conn, Some(false), Some(false), None, None, None)?;
+    projects.extend(list_projects(
+        conn,
+        Some(true),
+        Some(false),
+        None,
+        None,
+        None,
+    )?);
+
+    let todo_txt = tasks_to_todotxt(&items, &projects);
+
+    match output {
+        Some(path) => {
+            fs::write(&path, todo_txt)?;
+            println!("✅ Exported {} tasks to {}", items.len(), path);
+        }
+        None => print!("{}", todo_txt),
+    }
+    Ok(())
+}
+
+pub fn handle_import_todotxt(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    file: String,
+) -> Result<(), anyhow::Error> {
+    let content = fs::read_to_string(&file)?;
+    let items = parse_todotxt(&content).map_err(|e| anyhow!("Failed to parse {}: {}", file, e))?;
+
+    let mut created = 0;
+
+    // DB への反映はまとめて1トランザクションで行い、Markdown はコミット後に書き出す
+    let imported = conn.transaction::<_, anyhow::Error, _>(|conn| {
+        let mut imported = Vec::new();
+
+        for item in items {
+            let (task, is_new) = upsert_imported_task(conn, &item)?;
+            if is_new {
+                created += 1;
+            }
+            imported.push((task, None));
+        }
+
+        Ok(imported)
+    })?;
+
+    write_imported_markdown(config, conn, &imported);
+
+    println!(
+        "Imported {} tasks from {} (created: {}, updated: {})",
+        imported.len(),
+        file,
+        created,
+        imported.len() - created
+    );
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use crate::{
+    config::AppConfig,
+    store::*,
+    usecase::{handle_purge_note, handle_purge_project, handle_purge_task},
+};
+use anyhow::{Ok, anyhow};
+use chrono::{Duration, Utc};
+use diesel::SqliteConnection;
+use regex::Regex;
+
+// 論理削除済みのノート・タスク・プロジェクトを完全に削除する。
+// older_than を指定すると、それより前に削除したものだけを対象にする。
+// 1件ずつ別のトランザクションで削除し、失敗したものは最後にまとめて報告する。
+pub fn handle_empty_trash(
+    config: &AppConfig,
+    conn: &mut SqliteConnection,
+    older_than: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let before = match older_than {
+        Some(age) => Some(
+            Utc::now()
+                .naive_utc()
+                .checked_sub_signed(parse_age(&age)?)
+                .ok_or_else(|| anyhow!("Duration is too long: {}", age))?,
+        ),
+        None => None,
+    };
+
+    let notes = get_deleted_notes(conn, before)?;
+    let tasks = get_deleted_tasks(conn, before)?;
+    let projects = get_deleted_projects(conn, before)?;
+
+    let mut failed = Vec::new();
+    let mut record = |id: &str, result: Result<(), anyhow::Error>| match result {
+        Result::Ok(()) => 1,
+        Err(e) => {
+            eprintln!("Failed to purge {}: {}", id, e);
+            failed.push(id.to_string());
+            0
+        }
+    };
+    let mut purged = [0; 3];
+    for note in &notes {
+        purged[0] += record(&note.id, handle_purge_note(config, conn, note.id.clone()));
+    }
+    for task in &tasks {
+        purged[1] += record(&task.id, handle_purge_task(config, conn, task.id.clone()));
+    }
+    for project in &projects {
+        purged[2] += record(
+            &project.id,
+            handle_purge_project(config, conn, project.id.clone()),
+        );
+    }
+
+    println!(
+        "Emptied trash: {} notes, {} tasks, {} projects",
+        purged[0], purged[1], purged[2]
+    );
+    if !failed.is_empty() {
+        return Err(anyhow!(
+            "Could not purge {} item(s): {}",
+            failed.len(),
+            failed.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+// "30d", "2w", "12h" 形式の期間
+fn parse_age(input: &str) -> Result<Duration, anyhow::Error> {
+    let re = Regex::new(r"^(\d+)([hdw])$").unwrap();
+    let caps = re.captures(input.trim()).ok_or_else(|| {
+        anyhow!(
+            "Invalid duration: {} (use a number with h, d or w, e.g. 30d)",
+            input
+        )
+    })?;
+    let value: i64 = caps[1]
+        .parse()
+        .map_err(|_| anyhow!("Invalid duration: {}", input))?;
+
+    match &caps[2] {
+        "h" => Duration::try_hours(value),
+        "w" => Duration::try_weeks(value),
+        _ => Duration::try_days(value),
+    }
+    .ok_or_else(|| anyhow!("Duration is too long: {}", input))
+}
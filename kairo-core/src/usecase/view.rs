@@ -0,0 +1,116 @@
+use crate::{
+    store::*,
+    usecase::{parse_query_input, print_query_results},
+    util::print_views_as_table,
+};
+use anyhow::Ok;
+use diesel::SqliteConnection;
+
+// `kairo view save` のオプション。query 以外はそれぞれ `kairo query` の条件に置き換える
+#[derive(Debug, Clone, Default)]
+pub struct ViewConditions {
+    pub entities: Vec<String>,
+    pub note_type: Option<String>,
+    pub sub_type: Option<String>,
+    pub tags: Vec<String>,
+    pub tags_not: Vec<String>,
+    pub project: Option<String>,
+    pub area: Option<String>,
+    pub priority: Option<String>,
+    pub status: Option<String>,
+    pub title: Option<String>,
+    pub query: Option<String>,
+}
+
+impl ViewConditions {
+    pub fn to_query_text(&self) -> String {
+        let mut terms = Vec::new();
+        // is: を複数指定すると、いずれかの種類を対象にする
+        for entity in &self.entities {
+            terms.push(format!("is:{}", entity.to_lowercase()));
+        }
+        let fields = [
+            ("type", &self.note_type),
+            ("sub", &self.sub_type),
+            ("project", &self.project),
+            ("area", &self.area),
+            ("priority", &self.priority),
+            ("status", &self.status),
+            ("title", &self.title),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                terms.push(format!("{}:{}", field, quote_value(value)));
+            }
+        }
+        for tag in &self.tags {
+            terms.push(format!("tag:{}", quote_value(tag)));
+        }
+        for tag in &self.tags_not {
+            terms.push(format!("-tag:{}", quote_value(tag)));
+        }
+        if let Some(query) = &self.query {
+            terms.push(query.trim().to_string());
+        }
+        terms.join(" ")
+    }
+}
+
+// 空白を含む値は引用符で囲む
+fn quote_value(value: &str) -> String {
+    if value.contains(char::is_whitespace) {
+        format!("\"{}\"", value.replace('"', ""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn handle_save_view(
+    conn: &mut SqliteConnection,
+    name: String,
+    conditions: ViewConditions,
+) -> Result<(), anyhow::Error> {
+    let query_text = conditions.to_query_text();
+    if query_text.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Specify at least one condition or --query for the view"
+        ));
+    }
+    // 相対日付（today など）は実行時に解決するため、保存するのは条件の文字列
+    parse_query_input(&query_text)?;
+
+    let existed = get_view(conn, &name)?.is_some();
+    let view = save_view(conn, &name, &query_text)?;
+    if existed {
+        println!("Updated view {}: {}", view.name, view.query);
+    } else {
+        println!("Saved view {}: {}", view.name, view.query);
+    }
+    Ok(())
+}
+
+pub fn handle_run_view(
+    conn: &mut SqliteConnection,
+    name: String,
+    format: Option<String>,
+) -> Result<(), anyhow::Error> {
+    let view = get_view(conn, &name)?.ok_or_else(|| anyhow::anyhow!("View not found: {}", name))?;
+    let query = parse_query_input(&view.query)?;
+    print_query_results(conn, &query, format)
+}
+
+pub fn handle_list_views(conn: &mut SqliteConnection) -> Result<(), anyhow::Error> {
+    let views = list_views(conn)?;
+    if views.is_empty() {
+        println!("No views found");
+    } else {
+        print_views_as_table(&views);
+    }
+    Ok(())
+}
+
+pub fn handle_delete_view(conn: &mut SqliteConnection, name: String) -> Result<(), anyhow::Error> {
+    delete_view(conn, &name)?;
+    println!("Deleted view {}", name.trim().to_lowercase());
+    Ok(())
+}
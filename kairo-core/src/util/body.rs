@@ -0,0 +1,94 @@
+use crate::util::{Template, template_body};
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+// 自動で付けるタイトルの最大文字数
+const MAX_DERIVED_TITLE_CHARS: usize = 80;
+
+// --body / --body-file の内容を読む。"-" は標準入力
+pub fn read_body_input(
+    body: Option<String>,
+    body_file: Option<String>,
+) -> Result<Option<String>, io::Error> {
+    match (body, body_file.as_deref()) {
+        (Some(_), Some(_)) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Specify either --body or --body-file, not both",
+        )),
+        (Some(body), None) => Ok(Some(body)),
+        (None, Some("-")) => {
+            let mut body = String::new();
+            io::stdin().read_to_string(&mut body)?;
+            Ok(Some(body))
+        }
+        (None, Some(path)) => fs::read_to_string(path).map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+// タイトルがなければ本文の1行目をタイトルにし、残りを本文にする。
+// 本文が空になれば None を返す。
+pub fn resolve_title_and_body(
+    title: Option<String>,
+    body: Option<String>,
+) -> Result<(String, Option<String>), io::Error> {
+    let body = body.map(|b| b.replace("\r\n", "\n"));
+    let non_empty =
+        |text: &str| Some(text.trim_matches('\n').to_string()).filter(|t| !t.trim().is_empty());
+
+    if let Some(title) = title.filter(|t| !t.trim().is_empty()) {
+        return Ok((
+            title.trim().to_string(),
+            body.as_deref().and_then(non_empty),
+        ));
+    }
+
+    let Some(body) = body else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Specify a title or a body",
+        ));
+    };
+    let text = body.trim_start();
+    let (first_line, rest) = text.split_once('\n').unwrap_or((text, ""));
+    let title: String = first_line
+        .trim_start_matches('#')
+        .trim()
+        .chars()
+        .take(MAX_DERIVED_TITLE_CHARS)
+        .collect();
+    if title.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Cannot derive a title from an empty body",
+        ));
+    }
+    Ok((title, non_empty(rest)))
+}
+
+// 新規作成時の本文を組み立てる。テンプレートに {{body}} があればそこへ、なければ末尾へ本文を入れる。
+// テンプレートも本文もなければ None（write_to_markdown の既定の本文）を返す。
+pub fn compose_body(
+    template: Option<&Template>,
+    values: &[(&str, String)],
+    title: &str,
+    content: Option<&str>,
+) -> Option<String> {
+    match (template, content) {
+        (Some(template), content) => {
+            let mut values = values.to_vec();
+            values.push(("body", content.unwrap_or_default().to_string()));
+            let mut rendered = template.render(&values);
+            if let Some(content) = content
+                && !template.uses("body")
+            {
+                rendered = format!("{}\n{}\n", rendered.trim_end(), content);
+            }
+            Some(template_body(&rendered))
+        }
+        (None, Some(content)) => Some(template_body(&format!("## {}\n\n{}\n", title, content))),
+        (None, None) => None,
+    }
+}
@@ -1,13 +1,18 @@
 use crate::config::AppConfig;
 use config::{Config, ConfigError, File};
 use etcetera::{BaseStrategy, choose_base_strategy};
+use std::path::PathBuf;
+
+// kairo の設定ディレクトリ（例：$HOME/.config/kairo）
+pub fn kairo_config_dir() -> PathBuf {
+    let strategy = choose_base_strategy().expect("Unable to find the config directory!");
+    strategy.config_dir().join("kairo")
+}
 
 pub fn load_config() -> Result<AppConfig, ConfigError> {
     // Documentでconfigパスを限定することが前提
 
-    let strategy = choose_base_strategy().expect("Unable to find the config directory!");
-    let mut path = strategy.config_dir();
-    path.push("kairo");
+    let mut path = kairo_config_dir();
     path.push("config.toml");
 
     if !path.exists() {
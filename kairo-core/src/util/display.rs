@@ -1,5 +1,9 @@
 use crate::interface::HasItem;
-use crate::model::{LinkedNote, Note, Project, Tag, Task};
+use crate::model::{
+    AreaRollup, LinkedNote, Note, NoteType, Project, ProjectOverview, ProjectReport,
+    ProjectTreeNode, SavedView, SubType, Tag, TagAlias, TagStats, TagTreeNode, Task, TaskPriority,
+    TimeReportRow,
+};
 use crate::store::{
     get_note_by_id, get_tags_by_note_id, get_tags_by_project_id, get_tags_by_task_id,
 };
@@ -68,6 +72,8 @@ pub fn print_projects_as_table(conn: &mut SqliteConnection, projects: &[Project]
         "Title",
         "Description",
         "Tags",
+        "Status",
+        "Target",
         "Created",
         "Updated",
         "Archived",
@@ -80,6 +86,10 @@ pub fn print_projects_as_table(conn: &mut SqliteConnection, projects: &[Project]
         if let Some(str) = &project.description {
             description = format!("{:?}", str);
         }
+        let mut target_date = String::new();
+        if let Some(target) = project.target_date {
+            target_date = target.format("%Y/%m/%d").to_string();
+        }
 
         let format_created = project.created_at.format("%Y/%m/%d %H:%M:%S").to_string();
         let format_updated = project.updated_at.format("%Y/%m/%d %H:%M:%S").to_string();
@@ -94,6 +104,8 @@ pub fn print_projects_as_table(conn: &mut SqliteConnection, projects: &[Project]
             project.title,
             description,
             str_tags,
+            format!("{:?}", project.status),
+            target_date,
             format_created,
             format_updated,
             project.archived,
@@ -176,6 +188,90 @@ pub fn print_tags_as_table(tags: &[Tag]) {
     table.printstd();
 }
 
+pub fn print_tag_stats_as_table(stats: &[TagStats]) {
+    let mut table = Table::new();
+
+    // Header
+    table.add_row(row![
+        "ID",
+        "Tag name",
+        "Notes",
+        "Tasks",
+        "Projects",
+        "Total",
+        "First used",
+        "Last used",
+        "Co-occurring"
+    ]);
+
+    // Rows
+    for stat in stats {
+        let format_date = |date: Option<chrono::NaiveDateTime>| {
+            date.map(|d| d.format("%Y/%m/%d").to_string())
+                .unwrap_or_else(|| "-".to_string())
+        };
+        // 表では上位3件だけを表示する（JSON ではすべて出力）
+        let co_occurring: Vec<String> = stat
+            .co_occurring
+            .iter()
+            .take(3)
+            .map(|co| format!("{} ({})", co.tag_name, co.count))
+            .collect();
+
+        table.add_row(row![
+            stat.tag_id,
+            stat.tag_name,
+            stat.notes,
+            stat.tasks,
+            stat.projects,
+            stat.total(),
+            format_date(stat.first_used),
+            format_date(stat.last_used),
+            co_occurring.join(", ")
+        ]);
+    }
+
+    table.printstd();
+}
+
+pub fn print_tag_aliases_as_table(aliases: &[(TagAlias, Tag)]) {
+    let mut table = Table::new();
+
+    // Header
+    table.add_row(row!["Alias", "Tag ID", "Tag name", "Created"]);
+
+    // Rows
+    for (alias, tag) in aliases {
+        table.add_row(row![
+            alias.alias,
+            tag.id,
+            tag.tag_name,
+            alias.created_at.format("%Y/%m/%d")
+        ]);
+    }
+
+    table.printstd();
+}
+
+pub fn print_views_as_table(views: &[SavedView]) {
+    let mut table = Table::new();
+
+    // Header
+    table.add_row(row!["Name", "Query", "Created", "Updated"]);
+
+    // Rows
+    for view in views {
+        table.add_row(row![
+            view.name,
+            view.query,
+            view.created_at.format("%Y/%m/%d"),
+            view.updated_at.format("%Y/%m/%d")
+        ]);
+    }
+
+    table.printstd();
+}
+
 pub fn print_links_as_table(conn: &mut SqliteConnection, links: &[LinkedNote]) {
     let mut table = Table::new();
 
@@ -209,3 +305,388 @@ pub fn print_links_as_table(conn: &mut SqliteConnection, links: &[LinkedNote]) {
 
     table.printstd();
 }
+
+pub fn print_time_report_as_table(rows: &[TimeReportRow]) {
+    let mut table = Table::new();
+
+    // Header
+    table.add_row(row!["Group", "ID", "Name", "Hours"]);
+
+    // Rows
+    for report_row in rows {
+        table.add_row(row![
+            format!("{:?}", report_row.group),
+            report_row.key,
+            report_row.name,
+            format!("{:.2}", report_row.hours())
+        ]);
+    }
+
+    table.printstd();
+}
+
+pub fn print_time_report_as_csv(rows: &[TimeReportRow]) {
+    // Header
+    println!("group,id,name,hours");
+
+    // Rows
+    for report_row in rows {
+        println!(
+            "{},{},{},{:.2}",
+            csv_field(&format!("{:?}", report_row.group).to_lowercase()),
+            csv_field(&report_row.key),
+            csv_field(&report_row.name),
+            report_row.hours()
+        );
+    }
+}
+
+// 種類の異なる検索結果を共通の列にそろえて出力する
+pub fn print_query_results_as_csv(notes: &[Note], tasks: &[Task], projects: &[Project]) {
+    // Header
+    println!("entity,id,title,created_at,updated_at");
+
+    // Rows
+    let rows = notes
+        .iter()
+        .map(|n| ("note", &n.id, &n.title, n.created_at, n.updated_at))
+        .chain(
+            tasks
+                .iter()
+                .map(|t| ("task", &t.id, &t.title, t.created_at, t.updated_at)),
+        )
+        .chain(
+            projects
+                .iter()
+                .map(|p| ("project", &p.id, &p.title, p.created_at, p.updated_at)),
+        );
+    for (entity, entity_id, title, created_at, updated_at) in rows {
+        println!(
+            "{},{},{},{},{}",
+            entity,
+            csv_field(entity_id),
+            csv_field(title),
+            created_at.format("%Y-%m-%d %H:%M:%S"),
+            updated_at.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+}
+
+pub fn print_project_report(report: &ProjectReport) {
+    println!(
+        "{} - {} ({:?})",
+        report.project_id, report.title, report.status
+    );
+    println!(
+        "Progress: {:.0}% ({} / {} tasks completed)",
+        report.percent_complete(),
+        report.completed,
+        report.open + report.completed
+    );
+    println!("Linked notes: {}", report.linked_notes);
+
+    let mut table = Table::new();
+
+    // Header
+    table.add_row(row![
+        "Open",
+        "Completed",
+        "Deleted",
+        "High",
+        "Medium",
+        "Low",
+        "None"
+    ]);
+
+    // Rows
+    table.add_row(row![
+        report.open,
+        report.completed,
+        report.deleted,
+        report.high,
+        report.medium,
+        report.low,
+        report.no_priority
+    ]);
+
+    table.printstd();
+
+    if report.overdue.is_empty() {
+        return;
+    }
+
+    println!("Overdue tasks:");
+    let mut overdue_table = Table::new();
+    overdue_table.add_row(row!["ID", "Title", "Priority", "DueDate"]);
+    for task in &report.overdue {
+        let priority = task
+            .priority
+            .map(|p| format!("{:?}", p))
+            .unwrap_or_default();
+        let due_date = task
+            .due_date
+            .map(|d| d.format("%Y/%m/%d").to_string())
+            .unwrap_or_default();
+        overdue_table.add_row(row![task.id, task.title, priority, due_date]);
+    }
+    overdue_table.printstd();
+}
+
+pub fn print_areas_as_table(rollups: &[AreaRollup]) {
+    let mut table = Table::new();
+
+    // Header
+    table.add_row(row![
+        "ID",
+        "Title",
+        "Description",
+        "Projects",
+        "Open Tasks",
+        "Completed",
+        "Notes",
+        "Archived",
+        "Deleted"
+    ]);
+
+    // Rows
+    for rollup in rollups {
+        let Some(area) = &rollup.area else { continue };
+        let mut description = String::new();
+        if let Some(str) = &area.description {
+            description = format!("{:?}", str);
+        }
+
+        table.add_row(row![
+            area.id,
+            area.title,
+            description,
+            rollup.project_count(),
+            rollup.open_tasks(),
+            rollup.completed_tasks(),
+            rollup.notes(),
+            area.archived,
+            area.deleted
+        ]);
+    }
+
+    table.printstd();
+}
+
+// エリアを見出しに、プロジェクトの親子関係を罫線で表示する。
+// 件数はサブプロジェクトを含めた合計。
+pub fn print_project_tree(rollups: &[AreaRollup]) {
+    for rollup in rollups {
+        let heading = match &rollup.area {
+            Some(area) => format!("{} - {}", area.id, area.title),
+            None => "(no area)".to_string(),
+        };
+        println!(
+            "{}  [projects: {}, open: {}, done: {}, notes: {}]",
+            heading,
+            rollup.project_count(),
+            rollup.open_tasks(),
+            rollup.completed_tasks(),
+            rollup.notes()
+        );
+
+        let count = rollup.projects.len();
+        for (index, node) in rollup.projects.iter().enumerate() {
+            print_tree_node(node, "", index + 1 == count);
+        }
+    }
+}
+
+fn print_tree_node(node: &ProjectTreeNode, prefix: &str, is_last: bool) {
+    let branch = if is_last { "└── " } else { "├── " };
+    println!(
+        "{}{}{} - {} ({:?})  [open: {}, done: {}, notes: {}]",
+        prefix,
+        branch,
+        node.project.id,
+        node.project.title,
+        node.project.status,
+        node.open_tasks,
+        node.completed_tasks,
+        node.notes
+    );
+
+    let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
+    let count = node.children.len();
+    for (index, child) in node.children.iter().enumerate() {
+        print_tree_node(child, &child_prefix, index + 1 == count);
+    }
+}
+
+// プロジェクトの詳細に続けて、タスクを状態・優先度別に、ノートを種類別に表示する
+pub fn print_project_overview(overview: &ProjectOverview) {
+    print!("{}", overview.project);
+    if !overview.tags.is_empty() {
+        println!("Tags: {}", overview.tags.join(", "));
+    }
+    if !overview.sub_projects.is_empty() {
+        println!("Sub-projects:");
+        for sub in &overview.sub_projects {
+            println!("  - {} {} ({:?})", sub.id, sub.title, sub.status);
+        }
+    }
+
+    // Tasks
+    let open: Vec<&Task> = overview
+        .tasks
+        .iter()
+        .filter(|t| !t.archived && !t.deleted)
+        .collect();
+    let completed: Vec<&Task> = overview
+        .tasks
+        .iter()
+        .filter(|t| t.archived && !t.deleted)
+        .collect();
+    println!();
+    println!(
+        "== Tasks ({} open, {} completed) ==",
+        open.len(),
+        completed.len()
+    );
+    if !open.is_empty() {
+        println!("Open");
+        for priority in [
+            Some(TaskPriority::High),
+            Some(TaskPriority::Medium),
+            Some(TaskPriority::Low),
+            None,
+        ] {
+            let group: Vec<&&Task> = open.iter().filter(|t| t.priority == priority).collect();
+            if group.is_empty() {
+                continue;
+            }
+            let label = priority.map(|p| format!("{:?}", p)).unwrap_or("-".into());
+            println!("  [{}]", label);
+            for task in group {
+                println!("    - {}", format_task_line(task));
+            }
+        }
+    }
+    if !completed.is_empty() {
+        println!("Completed");
+        for task in completed {
+            println!("  - {}", format_task_line(task));
+        }
+    }
+
+    // Notes
+    println!();
+    println!("== Notes ({}) ==", overview.notes.len());
+    for note_type in [NoteType::Fleeting, NoteType::Permanent] {
+        let by_type: Vec<&Note> = overview
+            .notes
+            .iter()
+            .filter(|n| n.note_type == note_type)
+            .collect();
+        if by_type.is_empty() {
+            continue;
+        }
+        println!("{:?}", note_type);
+        for sub_type in [
+            Some(SubType::Question),
+            Some(SubType::Investigation),
+            Some(SubType::Log),
+            Some(SubType::Idea),
+            Some(SubType::Reference),
+            Some(SubType::Literature),
+            Some(SubType::Quote),
+            None,
+        ] {
+            let group: Vec<&&Note> = by_type.iter().filter(|n| n.sub_type == sub_type).collect();
+            if group.is_empty() {
+                continue;
+            }
+            let label = sub_type.map(|s| format!("{:?}", s)).unwrap_or("-".into());
+            println!("  [{}]", label);
+            for note in group {
+                let archived = if note.archived { " (archived)" } else { "" };
+                println!("    - {} {}{}", note.id, note.title, archived);
+            }
+        }
+    }
+
+    // Links
+    if !overview.links.is_empty() {
+        let title_of = |note_id: &str| {
+            overview
+                .notes
+                .iter()
+                .find(|n| n.id == note_id)
+                .map(|n| n.title.clone())
+                .unwrap_or_default()
+        };
+        println!();
+        println!("== Links ({}) ==", overview.links.len());
+        for link in &overview.links {
+            let ln_type = link
+                .link_type
+                .map(|ty| format!(" ({:?})", ty))
+                .unwrap_or_default();
+            println!(
+                "  - {} {} -> {} {}{}",
+                link.from_id,
+                title_of(&link.from_id),
+                link.to_id,
+                title_of(&link.to_id),
+                ln_type
+            );
+        }
+    }
+}
+
+fn format_task_line(task: &Task) -> String {
+    match task.due_date {
+        Some(due) => format!(
+            "{} {} (due {})",
+            task.id,
+            task.title,
+            due.format("%Y/%m/%d")
+        ),
+        None => format!("{} {}", task.id, task.title),
+    }
+}
+
+// 階層タグを罫線で表示する。子を持つタグは自身の件数と子孫を含む合計を並べる。
+pub fn print_tag_tree(roots: &[TagTreeNode]) {
+    let count = roots.len();
+    for (index, node) in roots.iter().enumerate() {
+        print_tag_tree_node(node, "", index + 1 == count, true);
+    }
+}
+
+fn print_tag_tree_node(node: &TagTreeNode, prefix: &str, is_last: bool, is_root: bool) {
+    let branch = match (is_root, is_last) {
+        (true, _) => "",
+        (false, true) => "└── ",
+        (false, false) => "├── ",
+    };
+    let usage = match (&node.tag_id, node.children.is_empty()) {
+        (Some(_), true) => format!("({})", node.count),
+        (Some(_), false) => format!("({}, total {})", node.count, node.total()),
+        (None, _) => format!("(total {})", node.total()),
+    };
+    println!("{}{}{} {}", prefix, branch, node.segment, usage);
+
+    let child_prefix = match (is_root, is_last) {
+        (true, _) => prefix.to_string(),
+        (false, true) => format!("{}    ", prefix),
+        (false, false) => format!("{}│   ", prefix),
+    };
+    let count = node.children.len();
+    for (index, child) in node.children.iter().enumerate() {
+        print_tag_tree_node(child, &child_prefix, index + 1 == count, false);
+    }
+}
+
+// カンマ・ダブルクォート・改行を含むフィールドはダブルクォートで囲む
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{
+    config::AppConfig,
+    model::{Note, Project, Task},
+};
+use chrono::NaiveDateTime;
+use std::{io, path::Path};
+
+pub const NOTE_LAYOUT_PLACEHOLDERS: &[&str] = &[
+    "id", "slug", "date", "year", "month", "day", "type", "sub_type", "project", "task",
+];
+pub const TASK_LAYOUT_PLACEHOLDERS: &[&str] = &[
+    "id", "slug", "date", "year", "month", "day", "project", "priority",
+];
+pub const PROJECT_LAYOUT_PLACEHOLDERS: &[&str] = &[
+    "id", "slug", "date", "year", "month", "day", "status", "area", "parent",
+];
+
+// アーカイブ済み・論理削除済みのファイルを置くサブディレクトリ
+pub const ARCHIVE_DIR: &str = "archive";
+pub const TRASH_DIR: &str = ".trash";
+
+const DEFAULT_LAYOUT: &str = "{id}";
+const MAX_SLUG_LEN: usize = 60;
+
+// タイトルをファイル名に使える形にする。英数字（日本語を含む）以外は `-` にまとめる
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.trim().chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug
+        .trim_end_matches('-')
+        .chars()
+        .take(MAX_SLUG_LEN)
+        .collect();
+    slug.trim_end_matches('-').to_string()
+}
+
+// ノートの Markdown ファイルの、notes_dir からの相対パス
+pub fn note_file_path(config: &AppConfig, note: &Note) -> Result<String, io::Error> {
+    let mut values = date_values(&note.id, &note.title, note.created_at);
+    values.extend([
+        ("type", format!("{:?}", note.note_type).to_lowercase()),
+        (
+            "sub_type",
+            note.sub_type
+                .map(|s| format!("{:?}", s).to_lowercase())
+                .unwrap_or_default(),
+        ),
+        ("project", note.project_id.clone().unwrap_or_default()),
+        ("task", note.task_id.clone().unwrap_or_default()),
+    ]);
+    let path = render_layout(
+        config.layout.notes.as_deref(),
+        NOTE_LAYOUT_PLACEHOLDERS,
+        &values,
+        &note.id,
+    )?;
+    Ok(with_state_dir(path, note.archived, note.deleted))
+}
+
+// タスクの Markdown ファイルの、tasks_dir からの相対パス
+pub fn task_file_path(config: &AppConfig, task: &Task) -> Result<String, io::Error> {
+    let mut values = date_values(&task.id, &task.title, task.created_at);
+    values.extend([
+        ("project", task.project_id.clone().unwrap_or_default()),
+        (
+            "priority",
+            task.priority
+                .map(|p| format!("{:?}", p).to_lowercase())
+                .unwrap_or_default(),
+        ),
+    ]);
+    let path = render_layout(
+        config.layout.tasks.as_deref(),
+        TASK_LAYOUT_PLACEHOLDERS,
+        &values,
+        &task.id,
+    )?;
+    Ok(with_state_dir(path, task.archived, task.deleted))
+}
+
+// プロジェクトの Markdown ファイルの、projects_dir からの相対パス
+pub fn project_file_path(config: &AppConfig, project: &Project) -> Result<String, io::Error> {
+    let mut values = date_values(&project.id, &project.title, project.created_at);
+    values.extend([
+        ("status", project.status.as_str().to_string()),
+        ("area", project.area_id.clone().unwrap_or_default()),
+        ("parent", project.parent_id.clone().unwrap_or_default()),
+    ]);
+    let path = render_layout(
+        config.layout.projects.as_deref(),
+        PROJECT_LAYOUT_PLACEHOLDERS,
+        &values,
+        &project.id,
+    )?;
+    Ok(with_state_dir(path, project.archived, project.deleted))
+}
+
+// 書き込み先を決める。レイアウト上のパスがほかのファイルに使われていれば、ID を付けて区別する
+pub fn resolve_file_path(dir: &str, current: &str, desired: String, id: &str) -> String {
+    if desired == current || !Path::new(dir).join(&desired).exists() {
+        return desired;
+    }
+    let stem = desired.trim_end_matches(".md");
+    if stem.ends_with(id) {
+        return desired;
+    }
+    format!("{}-{}.md", stem, id)
+}
+
+// 論理削除済みは .trash/、アーカイブ済みは archive/ の下に置き、エディタや grep から外す
+fn with_state_dir(path: String, archived: bool, deleted: bool) -> String {
+    if deleted {
+        format!("{}/{}", TRASH_DIR, path)
+    } else if archived {
+        format!("{}/{}", ARCHIVE_DIR, path)
+    } else {
+        path
+    }
+}
+
+fn date_values(id: &str, title: &str, created_at: NaiveDateTime) -> Vec<(&'static str, String)> {
+    vec![
+        ("id", id.to_string()),
+        ("slug", slugify(title)),
+        ("date", created_at.format("%Y-%m-%d").to_string()),
+        ("year", created_at.format("%Y").to_string()),
+        ("month", created_at.format("%m").to_string()),
+        ("day", created_at.format("%d").to_string()),
+    ]
+}
+
+// `{key}` を値に置き換えて相対パスにする。
+// 値が空になったディレクトリは省き、ファイル名が空なら ID を使う。
+fn render_layout(
+    layout: Option<&str>,
+    allowed: &[&str],
+    values: &[(&str, String)],
+    id: &str,
+) -> Result<String, io::Error> {
+    let layout = layout.unwrap_or(DEFAULT_LAYOUT);
+    let error = |message: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} in layout `{}`", message, layout),
+        )
+    };
+
+    let mut rendered = String::new();
+    let mut rest = layout;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start + 1..].find('}') else {
+            return Err(error("Unclosed placeholder".to_string()));
+        };
+        let key = rest[start + 1..start + 1 + len].trim();
+        if !allowed.contains(&key) {
+            return Err(error(format!(
+                "Unknown placeholder {{{}}} (available: {})",
+                key,
+                allowed.join(", ")
+            )));
+        }
+        rendered.push_str(&rest[..start]);
+        if let Some((_, value)) = values.iter().find(|(k, _)| *k == key) {
+            // 値に含まれる区切り文字でディレクトリが増えないようにする
+            rendered.push_str(&value.replace(['/', '\\'], "-"));
+        }
+        rest = &rest[start + 1 + len + 1..];
+    }
+    rendered.push_str(rest);
+
+    let is_empty = |segment: &str| segment.is_empty() || segment == "." || segment == "..";
+    let mut segments: Vec<&str> = rendered
+        .trim_end_matches(".md")
+        .split(['/', '\\'])
+        .map(str::trim)
+        .collect();
+    // 最後の区切りの後が空なら、手前のディレクトリ名ではなく ID をファイル名にする
+    let file_name = match segments.pop() {
+        Some(name) if !is_empty(name.trim_matches('-')) => name.to_string(),
+        _ => id.to_string(),
+    };
+    segments.retain(|segment| !is_empty(segment));
+    segments.push(&file_name);
+    Ok(format!("{}.md", segments.join("/")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn values() -> Vec<(&'static str, String)> {
+        let created_at =
+            NaiveDateTime::parse_from_str("2025-03-09 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let mut values = date_values("task-001", "Write the Report", created_at);
+        values.extend([
+            ("project", "p-001".to_string()),
+            ("priority", String::new()),
+        ]);
+        values
+    }
+
+    fn render(layout: &str) -> Result<String, io::Error> {
+        render_layout(
+            Some(layout),
+            TASK_LAYOUT_PLACEHOLDERS,
+            &values(),
+            "task-001",
+        )
+    }
+
+    // テストごとに別のディレクトリを作る
+    fn temp_dir(name: &str) -> String {
+        let dir =
+            std::env::temp_dir().join(format!("kairo-layout-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn expands_placeholders() {
+        assert_eq!(
+            render("{year}/{month}/{date}-{slug}").unwrap(),
+            "2025/03/2025-03-09-write-the-report.md"
+        );
+        assert_eq!(render("{project}/{ id }").unwrap(), "p-001/task-001.md");
+        assert_eq!(render("{id}.md").unwrap(), "task-001.md");
+    }
+
+    #[test]
+    fn uses_id_by_default() {
+        let path = render_layout(None, TASK_LAYOUT_PLACEHOLDERS, &values(), "task-001").unwrap();
+        assert_eq!(path, "task-001.md");
+    }
+
+    #[test]
+    fn drops_empty_directories_and_falls_back_to_id() {
+        assert_eq!(render("{priority}/{slug}").unwrap(), "write-the-report.md");
+        assert_eq!(render("{project}/{priority}").unwrap(), "p-001/task-001.md");
+        assert_eq!(
+            render("{project}/{priority}-").unwrap(),
+            "p-001/task-001.md"
+        );
+    }
+
+    #[test]
+    fn keeps_paths_inside_the_directory() {
+        assert_eq!(render("../{id}").unwrap(), "task-001.md");
+        assert_eq!(render("/./{project}//{id}").unwrap(), "p-001/task-001.md");
+
+        let values = [("id", "a/b\\c".to_string())];
+        let path = render_layout(Some("{id}"), &["id"], &values, "x").unwrap();
+        assert_eq!(path, "a-b-c.md");
+    }
+
+    #[test]
+    fn rejects_unknown_and_unclosed_placeholders() {
+        let error = render("{type}/{id}").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(error.to_string().starts_with("Unknown placeholder {type}"));
+
+        let error = render("{project/{id}").unwrap_err();
+        assert!(error.to_string().starts_with("Unknown placeholder"));
+
+        let error = render("{id").unwrap_err();
+        assert_eq!(error.to_string(), "Unclosed placeholder in layout `{id`");
+    }
+
+    #[test]
+    fn slugifies_ascii_titles() {
+        assert_eq!(slugify("  Hello, World!  "), "hello-world");
+        assert_eq!(slugify("C++ / Rust -- notes"), "c-rust-notes");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn keeps_non_ascii_letters_in_slugs() {
+        assert_eq!(slugify("所有権とは？ 借用の話"), "所有権とは-借用の話");
+        assert_eq!(slugify("Ünïcödé Straße"), "ünïcödé-straße");
+        assert_eq!(slugify("絵文字🎉テスト"), "絵文字-テスト");
+    }
+
+    #[test]
+    fn limits_slug_length_without_trailing_dash() {
+        let slug = slugify(&format!("{} tail", "a".repeat(59)));
+        assert_eq!(slug, "a".repeat(59));
+
+        let slug = slugify(&"長".repeat(100));
+        assert_eq!(slug.chars().count(), MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn puts_archived_and_deleted_files_in_state_dirs() {
+        assert_eq!(with_state_dir("a.md".to_string(), false, false), "a.md");
+        assert_eq!(
+            with_state_dir("a.md".to_string(), true, false),
+            "archive/a.md"
+        );
+        assert_eq!(
+            with_state_dir("a.md".to_string(), true, true),
+            ".trash/a.md"
+        );
+    }
+
+    #[test]
+    fn adds_id_suffix_when_path_is_taken() {
+        let dir = temp_dir("taken");
+        fs::write(Path::new(&dir).join("report.md"), "").unwrap();
+
+        assert_eq!(
+            resolve_file_path(&dir, "task-002.md", "report.md".to_string(), "task-002"),
+            "report-task-002.md"
+        );
+        // 自分のファイルならそのまま使う
+        assert_eq!(
+            resolve_file_path(&dir, "report.md", "report.md".to_string(), "task-001"),
+            "report.md"
+        );
+        // 空いていればそのまま使う
+        assert_eq!(
+            resolve_file_path(&dir, "task-002.md", "other.md".to_string(), "task-002"),
+            "other.md"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn does_not_add_id_suffix_twice() {
+        let dir = temp_dir("suffixed");
+        fs::write(Path::new(&dir).join("report-task-002.md"), "").unwrap();
+
+        assert_eq!(
+            resolve_file_path(
+                &dir,
+                "task-002.md",
+                "report-task-002.md".to_string(),
+                "task-002"
+            ),
+            "report-task-002.md"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
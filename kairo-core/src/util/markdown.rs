@@ -1,8 +1,11 @@
 use crate::interface::MarkdownParsable;
 use crate::interface::{FrontMatterExportable, HasItem, MarkdownExportable};
-use serde::Serialize;
+use regex::Regex;
+use serde::{Serialize, de::DeserializeOwned};
+use std::fmt;
 use std::fs;
 use std::io;
+use std::path::{Path, PathBuf};
 
 // この関数は、FrontMatterとBodyをマッピングした構造体をシリアライズして
 // Markdown ファイルを生成する。
@@ -27,7 +30,9 @@ where
     })?;
 
     let content = if let Some(body) = item.get_body() {
-        format!("---\n{}---{}", serialized, body)
+        // front matter のなかったファイルの本文などは改行で始まらないので、閉じの `---` の後に改行を補う
+        let separator = if body.starts_with('\n') { "" } else { "\n" };
+        format!("---\n{}---{}{}", serialized, separator, body)
     } else {
         format!(
             "---\n{}---\n\n## {}\n\n",
@@ -36,40 +41,352 @@ where
         )
     };
 
-    let path = format!("{}/{}.md", dir, item.get_front_matter().get_item().id());
+    let relative = markdown_relative_path(item.get_front_matter().get_item());
+    let path = Path::new(dir).join(&relative);
+    let parent = path.parent().unwrap_or(Path::new(dir));
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
 
-    fs::create_dir_all(dir)?;
-    fs::write(&path, content)?;
+    // 書き込み途中で中断されても既存ファイルが壊れないよう、一時ファイルに書いてから置き換える
+    let tmp_path = parent.join(format!(".{}.tmp", file_name));
+    fs::create_dir_all(parent)?;
+    fs::write(&tmp_path, content)?;
+    if let Err(e) = fs::rename(&tmp_path, &path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
 
-    println!(
-        "✅ Markdown saved to {}.md",
-        item.get_front_matter().get_item().id()
-    );
+    println!("✅ Markdown saved to {}", relative);
     Ok(())
 }
 
-// この関数は Markdown 形式の文字列を解析して、
-// FrontMatter と 本文を分割して返す。
+// この関数は item の Markdown ファイルの、dir からの相対パスを返す。
+// 保存されたパスがなければ `{id}.md` とみなす。
+pub fn markdown_relative_path<I: HasItem>(item: &I) -> String {
+    match item.file_path() {
+        Some(path) => path.to_string(),
+        None => format!("{}.md", item.id()),
+    }
+}
+
+pub fn markdown_path<I: HasItem>(dir: &str, item: &I) -> PathBuf {
+    Path::new(dir).join(markdown_relative_path(item))
+}
+
+// この関数は item の Markdown ファイルを削除する。
+// ファイルがもともと存在しない場合は何もしない。
+pub fn remove_markdown<I: HasItem>(dir: &str, item: &I) -> Result<bool, io::Error> {
+    remove_markdown_file(dir, &markdown_relative_path(item))
+}
+
+// この関数は dir からの相対パスで Markdown ファイルを削除し、空になったサブディレクトリも片付ける。
+// ファイルがなければ何もせず false を返す。
+pub fn remove_markdown_file(dir: &str, relative: &str) -> Result<bool, io::Error> {
+    let root = Path::new(dir);
+    let path = root.join(relative);
+    match fs::remove_file(&path) {
+        Ok(()) => println!("🗑️ Markdown removed: {}", relative),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    }
+
+    let mut parent = path.parent();
+    while let Some(current) = parent {
+        if current == root || !current.starts_with(root) || fs::remove_dir(current).is_err() {
+            break;
+        }
+        parent = current.parent();
+    }
+    Ok(true)
+}
+
+// front matter の解析エラー。line はファイル先頭からの行番号（1始まり）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatterError {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for FrontMatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for FrontMatterError {}
+
+impl From<FrontMatterError> for io::Error {
+    fn from(e: FrontMatterError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+// Markdown ファイルを front matter（YAML）と本文に分けたもの。
 //
-// `---` を区切り文字として使うが、本文中にも現れる可能性があるため、
-// 先頭から3つまでの分割に限定している。
+// body は閉じの `---` の直後から始まるので、write_to_markdown にそのまま渡せる。
+// front matter がないファイルでは yaml は None になり、body はファイル全体になる。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownDocument {
+    pub yaml: Option<String>,
+    // yaml の1行目がファイルの何行目か
+    pub yaml_line: usize,
+    pub body: String,
+}
+
+// この関数は Markdown 形式の文字列を front matter と本文に分割する。
+//
+// BOM は取り除き、改行は LF にそろえる。区切りは行全体が `---` の行だけを見るので、
+// YAML の文字列や本文の途中に `---` があっても誤って分割しない。
+pub fn split_front_matter(content: &str) -> Result<MarkdownDocument, FrontMatterError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let content = content.replace("\r\n", "\n");
+
+    let first_line_end = content.find('\n').unwrap_or(content.len());
+    if content[..first_line_end].trim_end() != "---" {
+        return Ok(MarkdownDocument {
+            yaml: None,
+            yaml_line: 0,
+            body: content,
+        });
+    }
+
+    let yaml_start = (first_line_end + 1).min(content.len());
+    let mut offset = yaml_start;
+    for line in content[yaml_start..].split_inclusive('\n') {
+        let delimiter = line.trim_end();
+        if delimiter == "---" || delimiter == "..." {
+            let body_start = offset + delimiter.len();
+            return Ok(MarkdownDocument {
+                yaml: Some(content[yaml_start..offset].to_string()),
+                yaml_line: 2,
+                body: content[body_start..].to_string(),
+            });
+        }
+        offset += line.len();
+    }
+
+    Err(FrontMatterError {
+        line: Some(1),
+        message: "front matter is not closed with `---`".to_string(),
+    })
+}
+
+// この関数は front matter を型付きで読み込み、本文と一緒に返す。
+// front matter がなければ None を返す。YAML のエラーはファイルの行番号で報告する。
+//
+// FrontMatter は Item を flatten しているため、そのまま読むと型の誤りの位置がわからない。
+// 先に Item として読んで、誤りのある行を報告できるようにしている。
+pub fn parse_front_matter<F, I>(content: &str) -> Result<(Option<F>, String), FrontMatterError>
+where
+    // F(FrontMatter)
+    F: FrontMatterExportable<I> + DeserializeOwned,
+    // I(Item)
+    I: HasItem + DeserializeOwned,
+{
+    let document = split_front_matter(content)?;
+    let Some(yaml) = document.yaml else {
+        return Ok((None, document.body));
+    };
+
+    let front_matter = serde_yaml::from_str::<I>(&yaml)
+        .and_then(|_| serde_yaml::from_str::<F>(&yaml))
+        .map_err(|e| {
+            let to_file_line = |line: usize| document.yaml_line + line - 1;
+            let location = e.location().map(|l| (l.line(), l.column()));
+
+            // serde_yaml のメッセージ中の位置は YAML 内の行番号なので、ファイルの行番号に直す。
+            // 先頭の位置は line として持つので、メッセージからは取り除く
+            let re = Regex::new(r" at line (\d+) column (\d+)").unwrap();
+            let message = re
+                .replace_all(&e.to_string(), |caps: &regex::Captures| {
+                    let line: usize = caps[1].parse().unwrap_or(1);
+                    let column: usize = caps[2].parse().unwrap_or(1);
+                    if location == Some((line, column)) {
+                        String::new()
+                    } else {
+                        format!(" at line {} column {}", to_file_line(line), column)
+                    }
+                })
+                .to_string();
+
+            FrontMatterError {
+                line: Some(location.map_or(document.yaml_line, |(line, _)| to_file_line(line))),
+                message,
+            }
+        })?;
+    Ok((Some(front_matter), document.body))
+}
+
+// この関数は item の Markdown ファイルを読み、型付きの front matter と本文を返す。
+pub fn read_markdown<F, I>(dir: &str, item: &I) -> Result<(Option<F>, String), io::Error>
+where
+    F: FrontMatterExportable<I> + DeserializeOwned,
+    I: HasItem + DeserializeOwned,
+{
+    let relative = markdown_relative_path(item);
+    let content = fs::read_to_string(Path::new(dir).join(&relative))?;
+    parse_front_matter(&content)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", relative, e)))
+}
+
+// この関数は front matter が読めないファイルから本文だけを取り出す。
+// front matter の区切りも見つからなければ、内容を失わないようファイル全体を本文とする。
+pub fn read_markdown_body<I: HasItem>(dir: &str, item: &I) -> Result<String, io::Error> {
+    let content = fs::read_to_string(markdown_path(dir, item))?;
+    Ok(match split_front_matter(&content) {
+        Ok(document) => document.body,
+        Err(_) => content,
+    })
+}
+
+// この関数は Markdown ファイルを読み、front matter の YAML と本文を文字列のまま返す。
+//
+// front matter がないファイルでは YAML は空文字列になる。
 pub fn parse_markdown<T, U>(item: &T, dir: &str) -> Result<(String, String), io::Error>
 where
     T: MarkdownParsable<U> + Serialize,
     U: HasItem,
 {
-    let path = format!("{}/{}.md", dir, item.get_item().id());
+    let relative = markdown_relative_path(item.get_item());
+
+    let content = fs::read_to_string(Path::new(dir).join(&relative))?;
+    let document = split_front_matter(&content)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", relative, e)))?;
+
+    let front_matter = document
+        .yaml
+        .map(|yaml| format!("\n{}", yaml))
+        .unwrap_or_default();
+    Ok((front_matter, document.body))
+}
+
+// この関数は本文中の kairo が生成するセクション（コメントの開始・終了マーカーで囲まれた部分）を
+// 置き換える。マーカーがなければ本文の末尾に追加する。
+//
+// マーカーの外側はユーザーが書いた内容なので変更しない。
+pub fn upsert_generated_section(body: &str, name: &str, content: &str) -> String {
+    let start_marker = format!("<!-- kairo:{}:start -->", name);
+    let end_marker = format!("<!-- kairo:{}:end -->", name);
+    let section = format!("{}\n{}\n{}", start_marker, content.trim_end(), end_marker);
+
+    if let Some(start) = body.find(&start_marker)
+        && let Some(end_offset) = body[start..].find(&end_marker)
+    {
+        let end = start + end_offset + end_marker.len();
+        return format!("{}{}{}", &body[..start], section, &body[end..]);
+    }
+
+    let trimmed = body.trim_end();
+    format!("{}\n\n{}\n", trimmed, section)
+}
+
+// upsert_generated_section で書き込んだセクションの中身を返す
+pub fn read_generated_section<'a>(body: &'a str, name: &str) -> Option<&'a str> {
+    let start_marker = format!("<!-- kairo:{}:start -->", name);
+    let end_marker = format!("<!-- kairo:{}:end -->", name);
+
+    let start = body.find(&start_marker)? + start_marker.len();
+    let end = start + body[start..].find(&end_marker)?;
+    Some(body[start..end].trim_matches('\n'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{markdown::NoteFrontMatter, model::Note};
+
+    const NOTE_YAML: &str = "id: 20250101T000000\ntitle: Sample\nnote_type: Fleeting\nsub_type: Idea\ncreated_at: 2025-01-01T00:00:00\nupdated_at: 2025-01-01T00:00:00\narchived: false\ndeleted: false\nproject_id: null\ntask_id: null\ntags: []\n";
+
+    fn parse_note(content: &str) -> Result<(Option<NoteFrontMatter>, String), FrontMatterError> {
+        parse_front_matter::<NoteFrontMatter, Note>(content)
+    }
+
+    #[test]
+    fn splits_front_matter_and_body() {
+        let document = split_front_matter("---\ntitle: a\n---\n\n## a\n").unwrap();
+        assert_eq!(document.yaml.as_deref(), Some("title: a\n"));
+        assert_eq!(document.yaml_line, 2);
+        assert_eq!(document.body, "\n\n## a\n");
+    }
+
+    #[test]
+    fn normalizes_crlf() {
+        let document = split_front_matter("---\r\ntitle: a\r\n---\r\n\r\nbody\r\n").unwrap();
+        assert_eq!(document.yaml.as_deref(), Some("title: a\n"));
+        assert_eq!(document.body, "\n\nbody\n");
+    }
+
+    #[test]
+    fn strips_bom() {
+        let document = split_front_matter("\u{feff}---\ntitle: a\n---\nbody").unwrap();
+        assert_eq!(document.yaml.as_deref(), Some("title: a\n"));
+        assert_eq!(document.body, "\nbody");
+    }
+
+    #[test]
+    fn ignores_dashes_inside_yaml_strings_and_body() {
+        let content = "---\ntitle: 'a --- b'\n---\nbefore --- after\n---\nend\n";
+        let document = split_front_matter(content).unwrap();
+        assert_eq!(document.yaml.as_deref(), Some("title: 'a --- b'\n"));
+        assert_eq!(document.body, "\nbefore --- after\n---\nend\n");
+    }
+
+    #[test]
+    fn treats_file_without_front_matter_as_body() {
+        let document = split_front_matter("# Heading\n\ntext\n").unwrap();
+        assert_eq!(document.yaml, None);
+        assert_eq!(document.body, "# Heading\n\ntext\n");
+
+        let (front_matter, body) = parse_note("# Heading\n").unwrap();
+        assert!(front_matter.is_none());
+        assert_eq!(body, "# Heading\n");
+    }
+
+    #[test]
+    fn reports_unclosed_front_matter_on_line_one() {
+        let error = split_front_matter("---\ntitle: a\n").unwrap_err();
+        assert_eq!(error.line, Some(1));
+    }
 
-    let content = fs::read_to_string(path)?;
-    let re = String::from("---");
+    #[test]
+    fn reports_yaml_syntax_errors_with_file_line() {
+        let content = format!(
+            "---\n{}---\n",
+            NOTE_YAML.replace("tags: []\n", "tags: []\n  bad: indent\n")
+        );
+        let error = parse_note(&content).unwrap_err();
+        // YAML 内の行番号ではなく、ファイルの行番号（front matter の開始 `---` が1行目）で示す
+        assert_eq!(error.line, Some(13), "{}", error);
+        assert!(!error.message.contains("at line"), "{}", error);
+    }
 
-    let splitted_contents = content.splitn(3, &re);
+    #[test]
+    fn reports_type_errors_on_the_offending_line() {
+        let content = format!(
+            "---\n{}---\n",
+            NOTE_YAML.replace("archived: false", "archived: maybe")
+        );
+        let error = parse_note(&content).unwrap_err();
+        assert_eq!(error.line, Some(8));
+        assert!(error.message.contains("archived"), "{}", error);
+    }
 
-    let contents: Vec<_> = splitted_contents
-        .into_iter()
-        .map(|content| content.to_string())
-        .collect();
+    #[test]
+    fn keeps_unknown_keys() {
+        let content = format!(
+            "---\n{}source:\n  url: http://example.com\n---\n\nbody\n",
+            NOTE_YAML
+        );
+        let (front_matter, body) = parse_note(&content).unwrap();
+        let front_matter = front_matter.unwrap();
+        assert_eq!(front_matter.item.title, "Sample");
+        assert!(front_matter.extra.contains_key("source"));
+        assert!(!front_matter.extra.contains_key("title"));
+        assert_eq!(body, "\n\nbody\n");
 
-    let markdown_content = (contents[1].clone(), contents[2].clone());
-    Ok(markdown_content)
+        let serialized = serde_yaml::to_string(&front_matter).unwrap();
+        assert!(serialized.contains("source:"), "{}", serialized);
+    }
 }
@@ -1,7 +1,15 @@
+pub mod body;
 pub mod config_loader;
 pub mod display;
+pub mod layout;
 pub mod markdown;
+pub mod template;
+pub mod terminal;
 
+pub use body::*;
 pub use config_loader::*;
 pub use display::*;
+pub use layout::*;
 pub use markdown::*;
+pub use template::*;
+pub use terminal::*;
@@ -0,0 +1,123 @@
+use crate::{config::AppConfig, util::kairo_config_dir};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+// 新規作成時の本文に使うテンプレート。`{{title}}` のようなプレースホルダーを値に置き換える
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+impl Template {
+    // 使えないプレースホルダーがあれば、作成前にエラーにする
+    pub fn check(&self, allowed: &[&str]) -> Result<(), io::Error> {
+        for key in placeholders(&self.text).map_err(|e| self.error(e))? {
+            if !allowed.contains(&key.as_str()) {
+                return Err(self.error(format!(
+                    "Unknown placeholder {{{{{}}}}} (available: {})",
+                    key,
+                    allowed.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn uses(&self, key: &str) -> bool {
+        placeholders(&self.text).is_ok_and(|keys| keys.iter().any(|k| k == key))
+    }
+
+    // 値のないプレースホルダーは空文字にする
+    pub fn render(&self, values: &[(&str, String)]) -> String {
+        let mut rendered = String::new();
+        let mut rest = self.text.as_str();
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            let key = rest[start + 2..start + 2 + len].trim();
+            rendered.push_str(&rest[..start]);
+            if let Some((_, value)) = values.iter().find(|(k, _)| *k == key) {
+                rendered.push_str(value);
+            }
+            rest = &rest[start + 2 + len + 2..];
+        }
+        rendered.push_str(rest);
+        rendered
+    }
+
+    fn error(&self, message: String) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} in template {}", message, self.path.display()),
+        )
+    }
+}
+
+// write_to_markdown の本文は front matter の `---` の直後に続くため、空行を挟んで渡す
+pub fn template_body(rendered: &str) -> String {
+    let mut body = format!("\n\n{}", rendered.trim_start_matches(['\r', '\n']));
+    if !body.ends_with('\n') {
+        body.push('\n');
+    }
+    body
+}
+
+pub fn templates_dir(config: &AppConfig) -> PathBuf {
+    match &config.paths.templates_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => kairo_config_dir().join("templates"),
+    }
+}
+
+// name を指定した場合は <templates_dir>/<name>.md を必ず使う。
+// 指定がなければ candidates を順に探し、どれもなければ None（既定の本文）を返す。
+pub fn find_template(
+    config: &AppConfig,
+    name: Option<&str>,
+    candidates: &[String],
+) -> Result<Option<Template>, io::Error> {
+    let dir = templates_dir(config);
+
+    if let Some(name) = name {
+        let path = dir.join(format!("{}.md", name.trim_end_matches(".md")));
+        if !path.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Template not found: {}", path.display()),
+            ));
+        }
+        return load_template(&path).map(Some);
+    }
+
+    for candidate in candidates {
+        let path = dir.join(candidate);
+        if path.is_file() {
+            return load_template(&path).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+fn load_template(path: &Path) -> Result<Template, io::Error> {
+    Ok(Template {
+        path: path.to_path_buf(),
+        text: fs::read_to_string(path)?,
+    })
+}
+
+fn placeholders(text: &str) -> Result<Vec<String>, String> {
+    let mut keys = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            return Err("Unclosed placeholder".to_string());
+        };
+        keys.push(rest[start + 2..start + 2 + len].trim().to_string());
+        rest = &rest[start + 2 + len + 2..];
+    }
+    Ok(keys)
+}
@@ -0,0 +1,204 @@
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const UNDERLINE: &str = "\x1b[4m";
+const RED: &str = "\x1b[31m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+// ページャーを使わずに表示する最大行数（端末の行数が分からないときの目安）
+const DEFAULT_PAGE_LINES: usize = 40;
+
+// 端末へ出力していて NO_COLOR が指定されていなければ色を付ける
+pub fn use_color() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+// 見出し・リスト・コードブロック・引用・インラインコード・強調・[[wikilink]] を端末向けに整形する。
+// resolve は wikilink の参照先（ID）からタイトルを返す。見つからなければ None。
+pub fn render_markdown(
+    body: &str,
+    color: bool,
+    resolve: &mut dyn FnMut(&str) -> Option<String>,
+) -> String {
+    let style = |code: &str, text: &str| {
+        if color {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            let language = trimmed.trim_start_matches('`').trim();
+            if in_code && !language.is_empty() {
+                lines.push(style(DIM, &format!("    [{}]", language)));
+            }
+            continue;
+        }
+        if in_code {
+            lines.push(style(CYAN, &format!("    {}", line)));
+            continue;
+        }
+
+        // kairo の生成セクションのマーカーなど、HTML コメントだけの行は表示しない
+        if trimmed.starts_with("<!--") && trimmed.ends_with("-->") {
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            let text = render_inline(trimmed[level..].trim(), color, resolve);
+            let code = if level <= 2 {
+                format!("{}{}", BOLD, UNDERLINE)
+            } else {
+                BOLD.to_string()
+            };
+            lines.push(style(&code, &text));
+            continue;
+        }
+
+        if matches!(trimmed, "---" | "***" | "___") {
+            lines.push(style(DIM, &"─".repeat(40)));
+            continue;
+        }
+
+        if let Some(quote) = trimmed.strip_prefix('>') {
+            let text = render_inline(quote.trim_start(), color, resolve);
+            lines.push(format!("{} {}", style(DIM, "│"), text));
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        {
+            let (bullet, text) = if let Some(rest) = item.strip_prefix("[ ] ") {
+                ("☐", rest)
+            } else if let Some(rest) = item
+                .strip_prefix("[x] ")
+                .or_else(|| item.strip_prefix("[X] "))
+            {
+                ("☑", rest)
+            } else {
+                ("•", item)
+            };
+            lines.push(format!(
+                "{}  {} {}",
+                indent,
+                bullet,
+                render_inline(text, color, resolve)
+            ));
+            continue;
+        }
+
+        lines.push(format!(
+            "{}{}",
+            indent,
+            render_inline(trimmed, color, resolve)
+        ));
+    }
+    lines.join("\n")
+}
+
+fn render_inline(
+    text: &str,
+    color: bool,
+    resolve: &mut dyn FnMut(&str) -> Option<String>,
+) -> String {
+    let style = |code: &str, text: &str| {
+        if color {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut rendered = String::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("[[")
+            && let Some(end) = after.find("]]")
+        {
+            // [[ID]] または [[ID|表示名]]
+            let inner = &after[..end];
+            let (target, label) = match inner.split_once('|') {
+                Some((target, label)) => (target.trim(), Some(label.trim())),
+                None => (inner.trim(), None),
+            };
+            match resolve(target) {
+                Some(title) => {
+                    rendered.push_str(&style(UNDERLINE, label.unwrap_or(&title)));
+                    // タイトルで参照しているときは参照先を重ねて表示しない
+                    if target != title {
+                        rendered.push_str(&style(DIM, &format!(" ({})", target)));
+                    }
+                }
+                None => rendered.push_str(&style(RED, &format!("[[{}]]", inner))),
+            }
+            rest = &after[end + 2..];
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix('`')
+            && let Some(end) = after.find('`')
+        {
+            rendered.push_str(&style(CYAN, &after[..end]));
+            rest = &after[end + 1..];
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("**")
+            && let Some(end) = after.find("**")
+        {
+            rendered.push_str(&style(BOLD, &after[..end]));
+            rest = &after[end + 2..];
+            continue;
+        }
+
+        let next = rest.chars().next().map_or(1, char::len_utf8);
+        rendered.push_str(&rest[..next]);
+        rest = &rest[next..];
+    }
+    rendered
+}
+
+// 端末の高さを超える出力は $PAGER（既定は less -R）で表示する。
+// ページャーを起動できなければそのまま出力する。
+pub fn print_paged(text: &str, use_pager: bool) -> Result<(), io::Error> {
+    let page_lines = env::var("LINES")
+        .ok()
+        .and_then(|lines| lines.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_PAGE_LINES);
+
+    if use_pager && io::stdout().is_terminal() && text.lines().count() > page_lines {
+        let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+        let mut parts = pager.split_whitespace();
+        if let Some(program) = parts.next()
+            && let Ok(mut child) = Command::new(program)
+                .args(parts)
+                .stdin(Stdio::piped())
+                .spawn()
+        {
+            if let Some(stdin) = child.stdin.as_mut() {
+                // ページャーを途中で閉じた場合の書き込みエラーは無視する
+                let _ = writeln!(stdin, "{}", text);
+            }
+            child.wait()?;
+            return Ok(());
+        }
+    }
+
+    println!("{}", text);
+    Ok(())
+}